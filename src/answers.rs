@@ -0,0 +1,207 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+use crate::solution::Answer;
+use crate::util::file_to_vec;
+
+// The recorded answers for a puzzle input, stored next to the input
+// e.g. data/2022/day01/input.txt -> data/2022/day01/answers.txt
+//
+//   part1: 71502
+//   part2: 208191
+//
+// Multi-line answers start on the line after the part header
+//
+//   part2:
+//   ####..##...##..#..#.####.###..####..##..
+//   #....#..#.#..#.#..#....#.#..#.#....#..#.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    parts: [Option<String>; 2],
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Verdict {
+    Correct,
+    Wrong(String),
+    Unknown,
+}
+
+pub fn answers_file(input_file: &str) -> String {
+    let path = Path::new(input_file);
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+    let name = if stem == "input" {
+        "answers.txt".to_string()
+    } else {
+        format!("{stem}.answers.txt")
+    };
+    return path.with_file_name(name).to_string_lossy().to_string();
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        return Answers::default();
+    }
+
+    // A missing answers file means none of the answers are known yet
+    pub fn load(filename: &str) -> io::Result<Answers> {
+        return match file_to_vec(filename) {
+            Ok(lines) => Ok(Answers::parse(&lines)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+            Err(err) => Err(err),
+        };
+    }
+
+    pub fn save(&self, filename: &str) -> io::Result<()> {
+        return fs::write(filename, self.to_lines().join("\n") + "\n");
+    }
+
+    pub fn parse(lines: &[String]) -> Answers {
+        let mut answers = Answers::new();
+        let mut part_lines: Option<(usize, Vec<&str>)> = None;
+        for line in lines {
+            let header = line
+                .strip_prefix("part1:")
+                .map(|rest| (0, rest))
+                .or_else(|| line.strip_prefix("part2:").map(|rest| (1, rest)));
+            match header {
+                Some((part, rest)) => {
+                    if let Some((p, values)) = part_lines.take() {
+                        answers.set_lines(p, &values);
+                    }
+                    let rest = rest.trim();
+                    if rest.is_empty() {
+                        part_lines = Some((part, Vec::new()));
+                    } else {
+                        answers.parts[part] = Some(rest.to_string());
+                    }
+                }
+                None => {
+                    if let Some((_, values)) = part_lines.as_mut() {
+                        values.push(line.trim_end());
+                    }
+                }
+            }
+        }
+        if let Some((p, values)) = part_lines.take() {
+            answers.set_lines(p, &values);
+        }
+        return answers;
+    }
+
+    fn set_lines(&mut self, part: usize, values: &[&str]) {
+        let mut count = values.len();
+        while count > 0 && values[count - 1].is_empty() {
+            count -= 1;
+        }
+        if count > 0 {
+            self.parts[part] = Some(values[0..count].join("\n"));
+        }
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = Vec::new();
+        for p in 0..2 {
+            if let Some(answer) = &self.parts[p] {
+                if answer.contains('\n') {
+                    lines.push(format!("part{}:", p + 1));
+                    for line in answer.lines() {
+                        lines.push(line.to_string());
+                    }
+                } else {
+                    lines.push(format!("part{}: {answer}", p + 1));
+                }
+            }
+        }
+        return lines;
+    }
+
+    pub fn get(&self, part: usize) -> Option<&str> {
+        return self.parts[part - 1].as_deref();
+    }
+
+    pub fn set(&mut self, part: usize, answer: &Answer) {
+        self.parts[part - 1] = match answer {
+            Answer::None => None,
+            _ => Some(answer.to_string()),
+        };
+    }
+
+    // Adds the answers of given that are missing, returns the parts it added and the parts
+    // where given has another answer than the one kept (part, given, kept)
+    pub fn merge(&mut self, given: &Answers) -> (Vec<usize>, Vec<(usize, String, String)>) {
+        let mut added = Vec::new();
+        let mut differ = Vec::new();
+        for p in 0..2 {
+            match (&given.parts[p], &self.parts[p]) {
+                (Some(answer), None) => {
+                    self.parts[p] = Some(answer.clone());
+                    added.push(p + 1);
+                }
+                (Some(answer), Some(kept)) if answer != kept => {
+                    differ.push((p + 1, answer.clone(), kept.clone()));
+                }
+                _ => {}
+            }
+        }
+        return (added, differ);
+    }
+
+    pub fn check(&self, part: usize, answer: &Answer) -> Verdict {
+        return match self.get(part) {
+            None => Verdict::Unknown,
+            Some(expected) if expected == answer.to_string() => Verdict::Correct,
+            Some(expected) => Verdict::Wrong(expected.to_string()),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn parse() {
+        let lines = str_array_to_string_array(vec!["part1: 24000", "part2:", "#..#", ".##.", ""]);
+        let answers = Answers::parse(&lines);
+        assert_eq!(answers.get(1), Some("24000"));
+        assert_eq!(answers.get(2), Some("#..#\n.##."));
+        assert_eq!(answers.to_lines(), lines[0..4].to_vec());
+
+        let lines = str_array_to_string_array(vec!["part2: CMZ"]);
+        let answers = Answers::parse(&lines);
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("CMZ"));
+    }
+
+    #[test]
+    fn check() {
+        let mut answers = Answers::new();
+        answers.set(1, &Answer::Number(24000));
+        assert_eq!(answers.check(1, &Answer::Number(24000)), Verdict::Correct);
+        assert_eq!(
+            answers.check(1, &Answer::Number(45000)),
+            Verdict::Wrong("24000".to_string())
+        );
+        assert_eq!(answers.check(2, &Answer::Number(45000)), Verdict::Unknown);
+        assert_eq!(
+            answers_file("data/2022/day01/input.txt"),
+            "data/2022/day01/answers.txt"
+        );
+        assert_eq!(answers_file("mine/day1.txt"), "mine/day1.answers.txt");
+    }
+
+    #[test]
+    fn merge() {
+        let mut answers = Answers::parse(&str_array_to_string_array(vec!["part1: 15"]));
+        let given = Answers::parse(&str_array_to_string_array(vec!["part1: 16", "part2: 12"]));
+        let (added, differ) = answers.merge(&given);
+        assert_eq!(added, vec![2]);
+        assert_eq!(differ, vec![(1, "16".to_string(), "15".to_string())]);
+        assert_eq!(answers.get(1), Some("15"));
+        assert_eq!(answers.get(2), Some("12"));
+        assert_eq!(answers.merge(&given).0, Vec::<usize>::new());
+    }
+}
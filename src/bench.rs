@@ -0,0 +1,336 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::time::Duration;
+
+use crate::cli::{BenchOptions, Options};
+use crate::error::{self, Error};
+use crate::report::{draw_table, format_ms};
+use crate::runner::{input_file, load_input, run_part_with};
+use crate::solution::{Answer, Puzzle};
+use crate::util::file_to_vec;
+
+// Changes smaller than this are treated as timing noise and never flagged
+const NOISE_FLOOR: Duration = Duration::from_micros(20);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        };
+    }
+
+    fn from_name(name: &str) -> Option<Stage> {
+        return match name {
+            "parse" => Some(Stage::Parse),
+            "part1" => Some(Stage::Part1),
+            "part2" => Some(Stage::Part2),
+            _ => None,
+        };
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+// The timings of one stage of a day over all the timed runs
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchResult {
+    pub day: u32,
+    pub stage: Stage,
+    pub stats: Stats,
+}
+
+// A bench result compared against the same day and stage in a baseline
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Comparison {
+    pub baseline: Duration,
+    // Relative change of the median : 0.1 is 10% slower
+    pub change: f64,
+    pub regression: bool,
+}
+
+pub fn stats(samples: &[Duration]) -> Stats {
+    let mut sorted = samples.to_vec();
+    sorted.sort();
+    let count = sorted.len();
+    let median = if count % 2 == 1 {
+        sorted[count / 2]
+    } else {
+        (sorted[count / 2 - 1] + sorted[count / 2]) / 2
+    };
+    let mean = sorted.iter().map(|s| s.as_secs_f64()).sum::<f64>() / count as f64;
+    let mut variance = 0.0;
+    if count > 1 {
+        for s in &sorted {
+            let delta = s.as_secs_f64() - mean;
+            variance += delta * delta;
+        }
+        variance /= (count - 1) as f64;
+    }
+    return Stats {
+        min: sorted[0],
+        median,
+        mean: Duration::from_secs_f64(mean),
+        stddev: Duration::from_secs_f64(variance.sqrt()),
+    };
+}
+
+// Baseline files are CSV with times in nanoseconds
+//   day,stage,min_ns,median_ns,mean_ns,stddev_ns
+//   1,parse,28714,29120,29870,1404
+pub fn to_baseline(results: &[BenchResult]) -> String {
+    let mut csv = String::from("day,stage,min_ns,median_ns,mean_ns,stddev_ns\n");
+    for r in results {
+        csv += &format!(
+            "{},{},{},{},{},{}\n",
+            r.day,
+            r.stage.name(),
+            r.stats.min.as_nanos(),
+            r.stats.median.as_nanos(),
+            r.stats.mean.as_nanos(),
+            r.stats.stddev.as_nanos()
+        );
+    }
+    return csv;
+}
+
+pub fn save_baseline(filename: &str, results: &[BenchResult]) -> io::Result<()> {
+    return fs::write(filename, to_baseline(results));
+}
+
+pub fn parse_baseline(lines: &[String]) -> Result<Vec<BenchResult>, String> {
+    let mut results = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if i == 0 || line.trim().is_empty() {
+            continue;
+        }
+        let bad_line = || format!("Invalid baseline line {} '{line}'", i + 1);
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() != 6 {
+            return Err(bad_line());
+        }
+        let day = fields[0].parse().map_err(|_| bad_line())?;
+        let stage = Stage::from_name(fields[1]).ok_or_else(bad_line)?;
+        let mut times = [Duration::ZERO; 4];
+        for t in 0..4 {
+            let nanos: u64 = fields[t + 2].parse().map_err(|_| bad_line())?;
+            times[t] = Duration::from_nanos(nanos);
+        }
+        results.push(BenchResult {
+            day,
+            stage,
+            stats: Stats {
+                min: times[0],
+                median: times[1],
+                mean: times[2],
+                stddev: times[3],
+            },
+        });
+    }
+    return Ok(results);
+}
+
+pub fn load_baseline(filename: &str) -> Result<Vec<BenchResult>, String> {
+    let lines = file_to_vec(filename)
+        .map_err(|err| format!("Could not load baseline '{filename}' {err}"))?;
+    return parse_baseline(&lines);
+}
+
+// threshold is the relative slow down of the median that counts as a regression
+pub fn compare(
+    results: &[BenchResult],
+    baseline: &[BenchResult],
+    threshold: f64,
+) -> Vec<Option<Comparison>> {
+    let mut baseline_medians = HashMap::new();
+    for b in baseline {
+        baseline_medians.insert((b.day, b.stage), b.stats.median);
+    }
+    let mut comparisons = Vec::new();
+    for r in results {
+        let comparison = baseline_medians.get(&(r.day, r.stage)).map(|&base| {
+            let median = r.stats.median;
+            let change = if base.is_zero() {
+                0.0
+            } else {
+                median.as_secs_f64() / base.as_secs_f64() - 1.0
+            };
+            let regression = change > threshold && median > base + NOISE_FLOOR;
+            Comparison {
+                baseline: base,
+                change,
+                regression,
+            }
+        });
+        comparisons.push(comparison);
+    }
+    return comparisons;
+}
+
+// comparisons is empty when there is no baseline
+pub fn to_table(results: &[BenchResult], comparisons: &[Option<Comparison>]) -> String {
+    let mut header = vec!["Day", "Stage", "Min", "Median", "Mean", "StdDev"];
+    if !comparisons.is_empty() {
+        header.extend(["Baseline", "Change", "Flag"]);
+    }
+    let mut rows = Vec::new();
+    for i in 0..results.len() {
+        let r = &results[i];
+        let mut row = vec![
+            format!("{:02}", r.day),
+            r.stage.name().to_string(),
+            format_ms(r.stats.min),
+            format_ms(r.stats.median),
+            format_ms(r.stats.mean),
+            format_ms(r.stats.stddev),
+        ];
+        if !comparisons.is_empty() {
+            match comparisons[i] {
+                Some(c) => {
+                    row.push(format_ms(c.baseline));
+                    row.push(format!("{:+.1}%", c.change * 100.0));
+                    row.push(if c.regression { "REGRESSION" } else { "" }.to_string());
+                }
+                None => row.extend([String::new(), String::new(), String::new()]),
+            }
+        }
+        rows.push(row);
+    }
+    let mut right_aligned = vec![false; header.len()];
+    for c in 2..header.len().min(8) {
+        right_aligned[c] = true;
+    }
+    return draw_table(&header, &rows, &right_aligned);
+}
+
+// Times the parse and each part of a day, the input is only loaded once
+pub fn bench_day(
+    puzzle: &Puzzle,
+    options: &Options,
+    bench_options: &BenchOptions,
+) -> error::Result<Vec<BenchResult>> {
+    let input =
+        load_input(puzzle, options).map_err(|err| Error::io(&input_file(puzzle, options), err))?;
+    let mut samples: [Vec<Duration>; 3] = [Vec::new(), Vec::new(), Vec::new()];
+    for run in 0..(bench_options.warmup + bench_options.runs) {
+        for part in 1..=2 {
+            if !options.selects_part(part) {
+                continue;
+            }
+            let part_run = run_part_with(puzzle, &options.settings, &input, part);
+            if part_run.answer? == Answer::None || run < bench_options.warmup {
+                continue;
+            }
+            samples[0].push(part_run.parse_time);
+            samples[part].push(part_run.solve_time);
+        }
+    }
+    let mut results = Vec::new();
+    for (s, stage) in [Stage::Parse, Stage::Part1, Stage::Part2]
+        .iter()
+        .enumerate()
+    {
+        if !samples[s].is_empty() {
+            results.push(BenchResult {
+                day: puzzle.day,
+                stage: *stage,
+                stats: stats(&samples[s]),
+            });
+        }
+    }
+    return Ok(results);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::parse_args;
+    use crate::util::str_array_to_string_array;
+    use crate::year2022::day01;
+
+    fn micros(values: Vec<u64>) -> Vec<Duration> {
+        return values.into_iter().map(Duration::from_micros).collect();
+    }
+
+    #[test]
+    fn statistics() {
+        let s = stats(&micros(vec![40, 10, 30, 20]));
+        assert_eq!(s.min, Duration::from_micros(10));
+        assert_eq!(s.median, Duration::from_micros(25));
+        assert_eq!(s.mean, Duration::from_micros(25));
+        assert_eq!(s.stddev.as_nanos(), 12910);
+
+        let s = stats(&micros(vec![7]));
+        assert_eq!(s.median, Duration::from_micros(7));
+        assert_eq!(s.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn baseline() {
+        let results = vec![
+            BenchResult {
+                day: 1,
+                stage: Stage::Parse,
+                stats: stats(&micros(vec![100, 120, 110])),
+            },
+            BenchResult {
+                day: 16,
+                stage: Stage::Part2,
+                stats: stats(&micros(vec![1000])),
+            },
+        ];
+        let lines: Vec<String> = to_baseline(&results)
+            .lines()
+            .map(|l| l.to_string())
+            .collect();
+        assert_eq!(parse_baseline(&lines), Ok(results.clone()));
+
+        let bad = str_array_to_string_array(vec!["header", "1,solve,1,2,3,4"]);
+        assert!(parse_baseline(&bad).is_err());
+
+        let mut slower = results.clone();
+        slower[0].stats.median = Duration::from_micros(150);
+        slower[1].stats.median = Duration::from_micros(1050);
+        let comparisons = compare(&slower, &results, 0.1);
+        assert!(comparisons[0].unwrap().regression);
+        assert!(!comparisons[1].unwrap().regression);
+        assert!((comparisons[1].unwrap().change - 0.05).abs() < 1e-9);
+
+        let comparisons = compare(&slower, &results[0..1], 0.1);
+        assert_eq!(comparisons[1], None);
+    }
+
+    #[test]
+    fn bench_example() {
+        let bench_options = BenchOptions {
+            runs: 2,
+            warmup: 0,
+            save: None,
+            baseline: None,
+            threshold: 0.1,
+        };
+        let options = parse_args(&str_array_to_string_array(vec!["--name", "example1"])).unwrap();
+        let results = bench_day(&day01::PUZZLE, &options, &bench_options).unwrap();
+        let stages: Vec<Stage> = results.iter().map(|r| r.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+
+        let options = parse_args(&str_array_to_string_array(vec!["--name", "nobody"])).unwrap();
+        let err = bench_day(&day01::PUZZLE, &options, &bench_options).unwrap_err();
+        assert!(matches!(err, Error::Io { .. }));
+    }
+}
@@ -0,0 +1,656 @@
+use crate::render::ImageFormat;
+use crate::report::Format;
+
+pub static USAGE: &str = "\
+Usage: aoc2022 [OPTIONS] [DAYS]
+       aoc2022 bench [BENCH OPTIONS] [OPTIONS] [DAYS]
+       aoc2022 matrix [--year YEAR] [--part 1|2] [--jobs N] [DAYS]
+       aoc2022 examples [--from PATH] [DAYS]
+       aoc2022 render [RENDER OPTIONS] [OPTIONS] [DAYS]
+       aoc2022 visualize [--fps N] [--part 1|2] [--input PATH | --name NAME] DAY
+       aoc2022 new [--year YEAR] DAY
+
+Runs the Advent of Code solutions of a year, all days are run when DAYS is not given
+bench times the parse and each part of the days over repeated runs
+matrix runs the days against every input in data/YYYY/dayNN and shows a pass/fail table
+examples saves the examples in the puzzle descriptions as data/YYYY/dayNN/exampleN.txt
+with the answers the solutions give that the descriptions mention
+render saves each step of the simulations of days 9, 14, 17, 23 and 24 as numbered images
+in OUT/YYYY/dayNN/partN
+visualize steps through the simulation of day 9, 14, 17, 23 or 24 in the terminal (part 1
+unless --part is given) : space plays and pauses, n steps, + and - change the speed, the
+arrow keys or h, j, k and l scroll and q quits
+new makes src/yearYYYY/dayNN.rs from templates/day.rs, makes data/YYYY/dayNN and adds
+the day to the PUZZLES of the year, a new year is added to the YEARS in src/solution.rs
+
+DAYS:
+  7                   run day 7
+  7+                  run day 7 and every later day
+  3,5,10-14           run a list of days and day ranges
+
+Options:
+  -y, --year <YEAR>   run the days of YEAR (default the latest year with solutions)
+  -p, --part <1|2>    only run the given part
+  -i, --input <PATH>  read the puzzle input from PATH instead of data/YYYY/dayNN/input.txt
+                      use - to read the puzzle input from stdin (only for a single day)
+  -N, --name <NAME>   read the puzzle input from data/YYYY/dayNN/NAME.txt and check it
+                      against data/YYYY/dayNN/NAME.answers.txt
+      --stream        read the input a line at a time instead of loading it, for inputs too
+                      big to load (day 1 of 2022), with --input - it needs --part as well
+      --set <OPT=VAL> set an option of the day, only for a single day and the answers are not
+                      checked or recorded, the days with options are
+                      2022 day 2   rules=PATH   play the game in the rules file PATH
+                                                e.g. data/2022/day02/*.rules
+                               max-wins=K   the best score winning at most K rounds
+                               opponent=W,W,W
+                                            the expected score against an opponent that
+                                            plays each shape with a chance in proportion
+                                            to its weight instead of the guide
+                               trials=N     simulate N games against the opponent
+                               seed=S       the seed of the simulation (default 1)
+  -r, --record        save the answers to the answers file next to the input
+                      e.g. data/YYYY/dayNN/answers.txt, instead of checking them
+  -f, --format <FMT>  output format: text (default), json, csv or table
+                      json, csv and table give one record per day and part with the answer,
+                      its status and the parse and solve timings
+  -j, --jobs <N>      run up to N days and parts at the same time (default 1)
+  -v, --verbose       write debug output to stderr, -vv for trace output as well
+                      the level can also be set with AOC_LOG=debug or AOC_LOG=trace
+  -h, --help          show this help
+
+Bench options:
+  -n, --runs <N>          number of timed runs (default 5)
+  -w, --warmup <N>        number of untimed runs before the timed runs (default 1)
+      --save <PATH>       save the results as a baseline file
+      --baseline <PATH>   compare the results against a saved baseline file
+      --threshold <PCT>   median slow down in percent flagged as a regression (default 10)
+
+Examples options:
+      --from <PATH>       read the description from a puzzle page .html or .md file
+                          instead of the comment in src/yearYYYY/dayNN.rs (only for a single day)
+
+Render options:
+  -o, --out <DIR>         directory for the images (default frames)
+      --image <FMT>       image format: png (default) or ppm
+      --scale <N>         size in pixels of each cell (default 4)
+      --every <N>         only save every Nth step, the last step is always saved (default 1)
+
+Visualize options:
+      --fps <N>           steps a second when playing (default 10)
+";
+
+#[derive(Debug, PartialEq)]
+pub struct BenchOptions {
+    pub runs: usize,
+    pub warmup: usize,
+    pub save: Option<String>,
+    pub baseline: Option<String>,
+    // Relative slow down : 0.1 is 10%
+    pub threshold: f64,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RenderOptions {
+    pub out: String,
+    pub image: ImageFormat,
+    pub scale: usize,
+    pub every: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run,
+    Bench(BenchOptions),
+    Matrix,
+    // The puzzle description to read instead of the one in the day's source
+    Examples { from: Option<String> },
+    Render(RenderOptions),
+    // Steps a second
+    Visualize { fps: usize },
+    // The day to make a new module for
+    New(u32),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub command: Command,
+    // None means the latest year
+    pub year: Option<u32>,
+    // Inclusive ranges of days to run : empty means run every day
+    pub days: Vec<(u32, u32)>,
+    pub part: Option<usize>,
+    pub input: Option<String>,
+    // Read the input a line at a time instead of loading it
+    pub stream: bool,
+    // Day options name=value in the order given
+    pub settings: Vec<(String, String)>,
+    // A named input next to the default input : "alice" is data/YYYY/dayNN/alice.txt
+    pub name: Option<String>,
+    pub record: bool,
+    pub format: Format,
+    pub jobs: usize,
+    // The number of times --verbose is given
+    pub verbose: usize,
+    pub help: bool,
+}
+
+impl Options {
+    pub fn selects_day(&self, day: u32) -> bool {
+        if self.days.is_empty() {
+            return true;
+        }
+        return self
+            .days
+            .iter()
+            .any(|&(first, last)| first <= day && day <= last);
+    }
+
+    pub fn selects_part(&self, part: usize) -> bool {
+        return match self.part {
+            Some(p) => p == part,
+            None => true,
+        };
+    }
+
+    // Reading from stdin is requested with "-"
+    pub fn input_is_stdin(&self) -> bool {
+        return self.input.as_deref() == Some("-");
+    }
+}
+
+fn parse_day(text: &str) -> Result<u32, String> {
+    let day: u32 = text
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid day '{text}'"))?;
+    if day == 0 {
+        return Err(format!("Invalid day '{text}' days start at 1"));
+    }
+    return Ok(day);
+}
+
+// "7", "7+", "3,5,10-14"
+pub fn parse_days(spec: &str) -> Result<Vec<(u32, u32)>, String> {
+    let mut days = Vec::new();
+    for tok in spec.split(',') {
+        if let Some(first) = tok.strip_suffix('+') {
+            days.push((parse_day(first)?, u32::MAX));
+        } else if let Some((first, last)) = tok.split_once('-') {
+            let first = parse_day(first)?;
+            let last = parse_day(last)?;
+            if first > last {
+                return Err(format!("Invalid day range '{tok}'"));
+            }
+            days.push((first, last));
+        } else {
+            let day = parse_day(tok)?;
+            days.push((day, day));
+        }
+    }
+    return Ok(days);
+}
+
+// Advent of Code started in 2015
+fn parse_year(text: &str) -> Result<u32, String> {
+    return match text.parse::<u32>() {
+        Ok(year) if (2015..10000).contains(&year) => Ok(year),
+        _ => Err(format!("Invalid year '{text}' expected 2015 or later")),
+    };
+}
+
+fn parse_part(text: &str) -> Result<usize, String> {
+    return match text {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("Invalid part '{text}' expected 1 or 2")),
+    };
+}
+
+fn parse_format(text: &str) -> Result<Format, String> {
+    return match text {
+        "text" => Ok(Format::Text),
+        "json" => Ok(Format::Json),
+        "csv" => Ok(Format::Csv),
+        "table" => Ok(Format::Table),
+        _ => Err(format!(
+            "Invalid format '{text}' expected text, json, csv or table"
+        )),
+    };
+}
+
+fn parse_image_format(text: &str) -> Result<ImageFormat, String> {
+    return match text {
+        "png" => Ok(ImageFormat::Png),
+        "ppm" => Ok(ImageFormat::Ppm),
+        _ => Err(format!("Invalid image format '{text}' expected png or ppm")),
+    };
+}
+
+fn parse_count(name: &str, text: &str, min: usize) -> Result<usize, String> {
+    return match text.parse::<usize>() {
+        Ok(count) if count >= min => Ok(count),
+        _ => Err(format!(
+            "Invalid {name} '{text}' expected a number >= {min}"
+        )),
+    };
+}
+
+fn parse_threshold(text: &str) -> Result<f64, String> {
+    return match text.trim_end_matches('%').parse::<f64>() {
+        Ok(percent) if percent >= 0.0 => Ok(percent / 100.0),
+        _ => Err(format!("Invalid threshold '{text}' expected a percentage")),
+    };
+}
+
+// Returns the value for an option given as "--name value" or "--name=value"
+fn option_value<'a>(
+    name: &str,
+    inline_value: Option<&'a str>,
+    args: &mut impl Iterator<Item = &'a String>,
+) -> Result<&'a str, String> {
+    if let Some(value) = inline_value {
+        return Ok(value);
+    }
+    return match args.next() {
+        Some(value) => Ok(value),
+        None => Err(format!("Missing value for {name}")),
+    };
+}
+
+// args does not include the program name
+pub fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut args = args;
+    let mut command = Command::Run;
+    if args.first().map(|a| a.as_str()) == Some("bench") {
+        command = Command::Bench(BenchOptions {
+            runs: 5,
+            warmup: 1,
+            save: None,
+            baseline: None,
+            threshold: 0.1,
+        });
+        args = &args[1..];
+    } else if args.first().map(|a| a.as_str()) == Some("matrix") {
+        command = Command::Matrix;
+        args = &args[1..];
+    } else if args.first().map(|a| a.as_str()) == Some("examples") {
+        command = Command::Examples { from: None };
+        args = &args[1..];
+    } else if args.first().map(|a| a.as_str()) == Some("render") {
+        command = Command::Render(RenderOptions {
+            out: "frames".to_string(),
+            image: ImageFormat::Png,
+            scale: 4,
+            every: 1,
+        });
+        args = &args[1..];
+    } else if args.first().map(|a| a.as_str()) == Some("visualize") {
+        command = Command::Visualize { fps: 10 };
+        args = &args[1..];
+    } else if args.first().map(|a| a.as_str()) == Some("new") {
+        command = Command::New(0);
+        args = &args[1..];
+    }
+    let mut options = Options {
+        command,
+        year: None,
+        days: Vec::new(),
+        part: None,
+        input: None,
+        stream: false,
+        settings: Vec::new(),
+        name: None,
+        record: false,
+        format: Format::Text,
+        jobs: 1,
+        verbose: 0,
+        help: false,
+    };
+    let mut found_days = false;
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        let (name, inline_value) = match arg.split_once('=') {
+            Some((name, value)) if name.starts_with("--") => (name, Some(value)),
+            _ => (arg.as_str(), None),
+        };
+        match name {
+            "-h" | "--help" => options.help = true,
+            "-r" | "--record" => options.record = true,
+            "--stream" => options.stream = true,
+            "-v" | "--verbose" => options.verbose += 1,
+            "-vv" => options.verbose += 2,
+            "-y" | "--year" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                options.year = Some(parse_year(value)?);
+            }
+            "-p" | "--part" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                options.part = Some(parse_part(value)?);
+            }
+            "-f" | "--format" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                options.format = parse_format(value)?;
+            }
+            "-j" | "--jobs" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                options.jobs = parse_count("jobs", value, 1)?;
+            }
+            "-i" | "--input" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                options.input = Some(value.to_string());
+            }
+            "--set" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                match value.split_once('=') {
+                    Some((option, option_value)) if !option.is_empty() => options
+                        .settings
+                        .push((option.to_string(), option_value.to_string())),
+                    _ => return Err(format!("Invalid option '{value}' expected NAME=VALUE")),
+                }
+            }
+            "-N" | "--name" => {
+                let value = option_value(name, inline_value, &mut iter)?;
+                if value.is_empty() || value.contains(['/', '\\']) || value.ends_with(".answers") {
+                    return Err(format!("Invalid input name '{value}'"));
+                }
+                options.name = Some(value.to_string());
+            }
+            "-n" | "--runs" | "-w" | "--warmup" | "--save" | "--baseline" | "--threshold" => {
+                let bench = match &mut options.command {
+                    Command::Bench(bench) => bench,
+                    _ => return Err(format!("{name} can only be used with bench")),
+                };
+                let value = option_value(name, inline_value, &mut iter)?;
+                match name {
+                    "-n" | "--runs" => bench.runs = parse_count("runs", value, 1)?,
+                    "-w" | "--warmup" => bench.warmup = parse_count("warmup", value, 0)?,
+                    "--save" => bench.save = Some(value.to_string()),
+                    "--baseline" => bench.baseline = Some(value.to_string()),
+                    _ => bench.threshold = parse_threshold(value)?,
+                }
+            }
+            "--from" => {
+                let Command::Examples { from } = &mut options.command else {
+                    return Err(format!("{name} can only be used with examples"));
+                };
+                let value = option_value(name, inline_value, &mut iter)?;
+                *from = Some(value.to_string());
+            }
+            "-o" | "--out" | "--image" | "--scale" | "--every" => {
+                let Command::Render(render) = &mut options.command else {
+                    return Err(format!("{name} can only be used with render"));
+                };
+                let value = option_value(name, inline_value, &mut iter)?;
+                match name {
+                    "-o" | "--out" => render.out = value.to_string(),
+                    "--image" => render.image = parse_image_format(value)?,
+                    "--scale" => render.scale = parse_count("scale", value, 1)?,
+                    _ => render.every = parse_count("every", value, 1)?,
+                }
+            }
+            "--fps" => {
+                let Command::Visualize { fps } = &mut options.command else {
+                    return Err(format!("{name} can only be used with visualize"));
+                };
+                let value = option_value(name, inline_value, &mut iter)?;
+                *fps = parse_count("fps", value, 1)?.min(1000);
+            }
+            _ => {
+                if name.starts_with('-') {
+                    return Err(format!("Unknown option '{arg}'"));
+                }
+                if found_days {
+                    return Err(format!("Unexpected argument '{arg}'"));
+                }
+                options.days = parse_days(arg)?;
+                found_days = true;
+            }
+        }
+    }
+    if options.input.is_some() && options.name.is_some() {
+        return Err("--input and --name can not be used together".to_string());
+    }
+    if options.stream {
+        if options.command != Command::Run {
+            return Err("--stream can only be used when running the days".to_string());
+        }
+        // Stdin can only be read once
+        if options.input_is_stdin() && options.part.is_none() {
+            return Err("--stream with --input - needs --part".to_string());
+        }
+    }
+    if !options.settings.is_empty() {
+        if options.record {
+            return Err("--record can not be used with --set".to_string());
+        }
+        if !matches!(options.command, Command::Run | Command::Bench(_)) {
+            return Err("--set can only be used when running or benchmarking the days".to_string());
+        }
+    }
+    if let Command::Bench(_) = options.command {
+        if options.record {
+            return Err("--record can not be used with bench".to_string());
+        }
+        if options.format != Format::Text {
+            return Err("--format can not be used with bench".to_string());
+        }
+        // Parallel runs would disturb the timings
+        if options.jobs > 1 {
+            return Err("--jobs can not be used with bench".to_string());
+        }
+    }
+    // Every input is run and checked
+    if options.command == Command::Matrix
+        && (options.input.is_some()
+            || options.name.is_some()
+            || options.record
+            || options.format != Format::Text)
+    {
+        return Err(
+            "--input, --name, --record and --format can not be used with matrix".to_string(),
+        );
+    }
+    if let Command::Examples { .. } = options.command {
+        if options.input.is_some()
+            || options.name.is_some()
+            || options.record
+            || options.format != Format::Text
+        {
+            return Err(
+                "--input, --name, --record and --format can not be used with examples".to_string(),
+            );
+        }
+    }
+    // The days are run one at a time and their answers are not checked
+    if let Command::Render(_) = options.command {
+        if options.record || options.format != Format::Text || options.jobs > 1 {
+            return Err("--record, --format and --jobs can not be used with render".to_string());
+        }
+    }
+    if let Command::Visualize { .. } = options.command {
+        if options.record || options.format != Format::Text || options.jobs > 1 {
+            return Err("--record, --format and --jobs can not be used with visualize".to_string());
+        }
+        if options.input_is_stdin() {
+            return Err("visualize reads keys from stdin so the input can not be -".to_string());
+        }
+        match options.days.as_slice() {
+            [(first, last)] if first == last => {}
+            _ => return Err("visualize needs a single day".to_string()),
+        }
+    }
+    if let Command::New(day) = &mut options.command {
+        match options.days.as_slice() {
+            [(first, last)] if first == last && *first <= 25 => *day = *first,
+            _ => return Err("new needs a single day from 1 to 25".to_string()),
+        }
+    }
+    return Ok(options);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn days() {
+        assert_eq!(parse_days("7"), Ok(vec![(7, 7)]));
+        assert_eq!(parse_days("7+"), Ok(vec![(7, u32::MAX)]));
+        assert_eq!(parse_days("3,5,10-14"), Ok(vec![(3, 3), (5, 5), (10, 14)]));
+        assert!(parse_days("x").is_err());
+        assert!(parse_days("0").is_err());
+        assert!(parse_days("14-10").is_err());
+        assert!(parse_days("3,,5").is_err());
+    }
+
+    #[test]
+    fn options() {
+        let args = str_array_to_string_array(vec!["--part", "2", "-i=x", "3,5"]);
+        assert!(parse_args(&args).is_err());
+
+        let args = str_array_to_string_array(vec!["--part=2", "--input", "-", "3,5", "-r"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.days, vec![(3, 3), (5, 5)]);
+        assert!(options.selects_day(5));
+        assert!(!options.selects_day(4));
+        assert!(!options.selects_part(1));
+        assert!(options.selects_part(2));
+        assert!(options.input_is_stdin());
+        assert!(options.record);
+        assert_eq!(options.format, Format::Text);
+        assert_eq!(options.command, Command::Run);
+
+        let args = str_array_to_string_array(vec!["-f", "csv", "-j", "4", "-v", "--verbose"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(options.jobs, 4);
+        assert_eq!(options.verbose, 2);
+
+        let options = parse_args(&[]).unwrap();
+        assert!(options.selects_day(25));
+        assert!(options.selects_part(1));
+        assert!(!options.help);
+
+        let args = str_array_to_string_array(vec!["examples", "--from=day06.html", "6"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Examples {
+                from: Some("day06.html".to_string())
+            }
+        );
+        assert_eq!(options.days, vec![(6, 6)]);
+
+        let args = str_array_to_string_array(vec!["--stream", "-i", "-", "-p", "2", "1"]);
+        let options = parse_args(&args).unwrap();
+        assert!(options.stream);
+        assert!(options.input_is_stdin());
+
+        let args = str_array_to_string_array(vec!["--set", "rules=a.rules", "--set=x=1=2", "2"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(
+            options.settings,
+            vec![
+                ("rules".to_string(), "a.rules".to_string()),
+                ("x".to_string(), "1=2".to_string())
+            ]
+        );
+
+        let args = str_array_to_string_array(vec!["--name", "alice", "1-3"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.name, Some("alice".to_string()));
+        assert_eq!(options.input, None);
+
+        let args = str_array_to_string_array(vec!["matrix", "-p", "1", "-j", "2"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.command, Command::Matrix);
+        assert!(!options.selects_part(2));
+
+        let args = str_array_to_string_array(vec!["render", "--every=10", "-o", "out", "14"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(
+            options.command,
+            Command::Render(RenderOptions {
+                out: "out".to_string(),
+                image: ImageFormat::Png,
+                scale: 4,
+                every: 10,
+            })
+        );
+        assert_eq!(options.days, vec![(14, 14)]);
+
+        let args = str_array_to_string_array(vec!["visualize", "--fps=30", "-p", "2", "17"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.command, Command::Visualize { fps: 30 });
+        assert_eq!(options.days, vec![(17, 17)]);
+
+        let args = str_array_to_string_array(vec!["new", "12", "--year", "2023"]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.command, Command::New(12));
+        assert_eq!(options.year, Some(2023));
+
+        for bad in [
+            vec!["--part", "3"],
+            vec!["--part"],
+            vec!["-x"],
+            vec!["--format=xml"],
+            vec!["--jobs", "0"],
+            vec!["bench", "-j", "2"],
+            vec!["--from", "day01.html"],
+            vec!["examples", "-r"],
+            vec!["--name", "a", "--input", "b.txt"],
+            vec!["--name", "../input"],
+            vec!["--name=input.answers"],
+            vec!["matrix", "--name", "alice"],
+            vec!["matrix", "-f", "json"],
+            vec!["--scale", "2"],
+            vec!["render", "--image", "gif"],
+            vec!["render", "--every", "0"],
+            vec!["render", "-r"],
+            vec!["visualize"],
+            vec!["visualize", "9,14"],
+            vec!["visualize", "-i", "-", "9"],
+            vec!["--fps", "5", "9"],
+            vec!["new"],
+            vec!["new", "3-5"],
+            vec!["new", "26"],
+            vec!["-y", "22"],
+            vec!["--stream", "-i", "-", "1"],
+            vec!["--set", "rules"],
+            vec!["--set", "=1"],
+            vec!["--set", "a=1", "-r"],
+            vec!["matrix", "--set", "a=1"],
+            vec!["bench", "--stream", "1"],
+            vec!["1", "2"],
+        ] {
+            let args = str_array_to_string_array(bad);
+            assert!(parse_args(&args).is_err());
+        }
+    }
+
+    #[test]
+    fn bench() {
+        let args = str_array_to_string_array(vec![
+            "bench",
+            "-n",
+            "20",
+            "--threshold=5%",
+            "--save",
+            "base.csv",
+            "16",
+        ]);
+        let options = parse_args(&args).unwrap();
+        assert_eq!(options.days, vec![(16, 16)]);
+        assert_eq!(
+            options.command,
+            Command::Bench(BenchOptions {
+                runs: 20,
+                warmup: 1,
+                save: Some("base.csv".to_string()),
+                baseline: None,
+                threshold: 0.05,
+            })
+        );
+    }
+}
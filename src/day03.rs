@@ -1,237 +0,0 @@
-use crate::file_to_vec;
-
-/*
-
---- Day 3: Rucksack Reorganization ---
-
-One Elf has the important job of loading all of the rucksacks with supplies for the jungle journey.
-Unfortunately, that Elf didn't quite follow the packing instructions, and so a few items now need to be rearranged.
-
-Each rucksack has two large compartments.
-All items of a given type are meant to go into exactly one of the two compartments.
-The Elf that did the packing failed to follow this rule for exactly one item type per rucksack.
-
-The Elves have made a list of all of the items currently in each rucksack (your puzzle input), but they need your help finding the errors.
-Every item type is identified by a single lowercase or uppercase letter (that is, a and A refer to different types of items).
-
-The list of items for each rucksack is given as characters all on a single line.
-A given rucksack always has the same number of items in each of its two compartments, so the first half of the characters represent items in the first compartment, while the second half of the characters represent items in the second compartment.
-
-For example, suppose you have the following list of contents from six rucksacks:
-
-vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-The first rucksack contains the items vJrwpWtwJgWrhcsFMMfFFhFp, which means its first compartment contains the items vJrwpWtwJgWr, while the second compartment contains the items hcsFMMfFFhFp.
-The only item type that appears in both compartments is lowercase p.
-The second rucksack's compartments contain jqHRNqRjqzjGDLGL and rsFMfFZSrLrFZsSL.
-The only item type that appears in both compartments is uppercase L.
-The third rucksack's compartments contain PmmdzqPrV and vPwwTWBwg; the only common item type is uppercase P.
-The fourth rucksack's compartments only share item type v.
-The fifth rucksack's compartments only share item type t.
-The sixth rucksack's compartments only share item type s.
-To help prioritize item rearrangement, every item type can be converted to a priority:
-
-Lowercase item types a through z have priorities 1 through 26.
-Uppercase item types A through Z have priorities 27 through 52.
-In the above example, the priority of the item type that appears in both compartments of each rucksack is 16 (p), 38 (L), 42 (P), 22 (v), 20 (t), and 19 (s); the sum of these is 157.
-
-Find the item type that appears in both compartments of each rucksack.
-
-What is the sum of the priorities of those item types?
-
-Your puzzle answer was 7737.
-
---- Part Two ---
-
-As you finish identifying the misplaced items, the Elves come to you with another issue.
-
-For safety, the Elves are divided into groups of three.
-Every Elf carries a badge that identifies their group.
-For efficiency, within each group of three Elves, the badge is the only item type carried by all three Elves.
-That is, if a group's badge is item type B, then all three Elves will have item type B somewhere in their rucksack, and at most two of the Elves will be carrying any other item type.
-
-The problem is that someone forgot to put this year's updated authenticity sticker on the badges.
-All of the badges need to be pulled out of the rucksacks so the new authenticity stickers can be attached.
-
-Additionally, nobody wrote down which item type corresponds to each group's badges.
-The only way to tell which item type is the right one is by finding the one item type that is common between all three Elves in each group.
-
-Every set of three lines in your list corresponds to a single group, but each group can have a different badge item type.
-So, in the above example, the first group's rucksacks are the first three lines:
-
-vJrwpWtwJgWrhcsFMMfFFhFp
-jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
-PmmdzqPrVvPwwTWBwg
-And the second group's rucksacks are the next three lines:
-
-wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
-ttgJtRGJQctTZtZT
-CrZsJsPPZsGzwwsLwLmpwMDw
-In the first group, the only item type that appears in all three rucksacks is lowercase r; this must be their badges.
-In the second group, their badge item type must be Z.
-
-Priorities for these items must still be found to organize the sticker attachment efforts: here, they are 18 (r) for the first group and 52 (Z) for the second group.
-The sum of these is 70.
-
-Find the item type that corresponds to the badges of each three-Elf group.
-
-What is the sum of the priorities of those item types?
-
-*/
-
-static INPUT_FILE: &str = "data/day03/input.txt";
-
-pub fn run() {
-    println!("Day03: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day03: End");
-}
-
-struct Day {
-    part1: bool,
-    left_strings: Vec<Vec<u8>>,
-    right_strings: Vec<Vec<u8>>,
-    strings: Vec<Vec<u8>>,
-}
-
-impl Day {
-    fn instance(part1: bool) -> Day {
-        Day {
-            part1: part1,
-            left_strings: Vec::new(),
-            right_strings: Vec::new(),
-            strings: Vec::new(),
-        }
-    }
-
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.sum_priorities();
-            println!("Day03: Result1 {result1}");
-            let expected = 7737;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.sum_priorities_three();
-            println!("Day03: Result2 {result2}");
-            let expected = 2697;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            let rucksack_size = line.len() / 2;
-            let left = line[0..rucksack_size].as_bytes().to_vec();
-            let right = line[rucksack_size..].as_bytes().to_vec();
-            self.left_strings.push(left);
-            self.right_strings.push(right);
-            self.strings.push(line.as_bytes().to_vec());
-        }
-    }
-
-    fn sum_priorities(&self) -> i64 {
-        let count = self.left_strings.len();
-        let mut total = 0_i64;
-        for i in 0..count {
-            let l = &self.left_strings[i];
-            let r = &self.right_strings[i];
-            for b in l {
-                if r.contains(b) {
-                    let c = *b;
-                    if c >= 'a' as u8 && c <= 'z' as u8 {
-                        let priority = c - 'a' as u8;
-                        total += priority as i64;
-                        total += 1;
-                    } else {
-                        let priority = c - 'A' as u8;
-                        total += priority as i64;
-                        total += 27;
-                    }
-                    break;
-                }
-            }
-        }
-        return total;
-    }
-
-    fn sum_priorities_three(&self) -> i64 {
-        let count = self.left_strings.len();
-        let mut total = 0_i64;
-        for i in (0..count).step_by(3) {
-            let base = &self.strings[i];
-            for b in base {
-                let mut found = 0;
-                for j in 1..3 {
-                    let other = &self.strings[i + j];
-                    if other.contains(b) {
-                        found += 1;
-                    }
-                }
-                if found == 2 {
-                    let c = *b;
-                    if c >= 'a' as u8 && c <= 'z' as u8 {
-                        let priority = c - 'a' as u8;
-                        total += priority as i64;
-                        total += 1;
-                    } else {
-                        let priority = c - 'A' as u8;
-                        total += priority as i64;
-                        total += 27;
-                    }
-                    break;
-                }
-            }
-        }
-        return total;
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::str_array_to_string_array;
-
-    #[test]
-    fn part1() {
-        let input: Vec<&str> = vec![
-            "vJrwpWtwJgWrhcsFMMfFFhFp",
-            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
-            "PmmdzqPrVvPwwTWBwg",
-            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
-            "ttgJtRGJQctTZtZT",
-            "CrZsJsPPZsGzwwsLwLmpwMDw",
-        ];
-        let lines = str_array_to_string_array(input);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
-        assert_eq!(day.sum_priorities(), 157);
-    }
-
-    #[test]
-    fn part2() {
-        let input: Vec<&str> = vec![
-            "vJrwpWtwJgWrhcsFMMfFFhFp",
-            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
-            "PmmdzqPrVvPwwTWBwg",
-            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
-            "ttgJtRGJQctTZtZT",
-            "CrZsJsPPZsGzwwsLwLmpwMDw",
-        ];
-        let lines = str_array_to_string_array(input);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
-        assert_eq!(day.sum_priorities_three(), 70);
-    }
-}
@@ -0,0 +1,272 @@
+use std::error;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+// Errors from loading, parsing, solving and checking the puzzles
+// Errors made while parsing or solving a day have day 0 until the runner sets it with in_day
+// Parse and input errors in a file other than the day's input (e.g. a rules file) name it
+#[derive(Debug)]
+pub enum Error {
+    Io {
+        path: String,
+        error: io::Error,
+    },
+    // line and column start at 1
+    Parse {
+        day: u32,
+        file: Option<String>,
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    // The input parsed but does not have the shape the solution needs
+    Input {
+        day: u32,
+        file: Option<String>,
+        message: String,
+    },
+    WrongAnswer {
+        day: u32,
+        part: usize,
+        answer: String,
+        expected: String,
+    },
+    Panic {
+        day: u32,
+        part: usize,
+        message: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+// io::Error is not Clone so the copy keeps its kind and message
+impl Clone for Error {
+    fn clone(&self) -> Error {
+        return match self {
+            Error::Io { path, error } => Error::Io {
+                path: path.clone(),
+                error: io::Error::new(error.kind(), error.to_string()),
+            },
+            Error::Parse {
+                day,
+                file,
+                line,
+                column,
+                message,
+            } => Error::Parse {
+                day: *day,
+                file: file.clone(),
+                line: *line,
+                column: *column,
+                message: message.clone(),
+            },
+            Error::Input { day, file, message } => Error::Input {
+                day: *day,
+                file: file.clone(),
+                message: message.clone(),
+            },
+            Error::WrongAnswer {
+                day,
+                part,
+                answer,
+                expected,
+            } => Error::WrongAnswer {
+                day: *day,
+                part: *part,
+                answer: answer.clone(),
+                expected: expected.clone(),
+            },
+            Error::Panic { day, part, message } => Error::Panic {
+                day: *day,
+                part: *part,
+                message: message.clone(),
+            },
+        };
+    }
+}
+
+impl Error {
+    pub fn io(path: &str, error: io::Error) -> Error {
+        return Error::Io {
+            path: path.to_string(),
+            error,
+        };
+    }
+
+    // line_index is the index of the line in the input, column starts at 1
+    pub fn parse(line_index: usize, column: usize, message: &str) -> Error {
+        return Error::Parse {
+            day: 0,
+            file: None,
+            line: line_index + 1,
+            column,
+            message: message.to_string(),
+        };
+    }
+
+    // A parse error for the whole of token, which must be a slice of line
+    pub fn parse_token(line_index: usize, line: &str, token: &str, message: &str) -> Error {
+        return Error::parse(line_index, column_of(line, token), message);
+    }
+
+    pub fn input(message: &str) -> Error {
+        return Error::Input {
+            day: 0,
+            file: None,
+            message: message.to_string(),
+        };
+    }
+
+    // The file a parse or input error is in, other errors are left as they are
+    pub fn in_file(mut self, path: &str) -> Error {
+        match &mut self {
+            Error::Parse { file, .. } | Error::Input { file, .. } => *file = Some(path.to_string()),
+            _ => {}
+        }
+        return self;
+    }
+
+    pub fn in_day(mut self, in_day: u32) -> Error {
+        match &mut self {
+            Error::Io { .. } => {}
+            Error::Parse { day, .. }
+            | Error::Input { day, .. }
+            | Error::WrongAnswer { day, .. }
+            | Error::Panic { day, .. } => *day = in_day,
+        }
+        return self;
+    }
+
+    pub fn day(&self) -> Option<u32> {
+        return match self {
+            Error::Io { .. } => None,
+            Error::Parse { day, .. }
+            | Error::Input { day, .. }
+            | Error::WrongAnswer { day, .. }
+            | Error::Panic { day, .. } => Some(*day),
+        };
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Error::Io { path, error } => write!(f, "Could not read '{path}' {error}"),
+            Error::Parse {
+                day,
+                file: None,
+                line,
+                column,
+                message,
+            } => write!(f, "Day{day:02} line {line} column {column}: {message}"),
+            Error::Parse {
+                day,
+                file: Some(file),
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Day{day:02} '{file}' line {line} column {column}: {message}"
+            ),
+            Error::Input {
+                day,
+                file: None,
+                message,
+            } => write!(f, "Day{day:02} unexpected input: {message}"),
+            Error::Input {
+                day,
+                file: Some(file),
+                message,
+            } => write!(f, "Day{day:02} unexpected input in '{file}': {message}"),
+            Error::WrongAnswer {
+                day,
+                part,
+                answer,
+                expected,
+            } => write!(f, "Day{day:02} Part{part} is wrong {answer} != {expected}"),
+            Error::Panic { day, part, message } => {
+                write!(f, "Day{day:02} Part{part} panicked: {message}")
+            }
+        };
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        return match self {
+            Error::Io { error, .. } => Some(error),
+            _ => None,
+        };
+    }
+}
+
+// The column of token within line, token must be a slice of line
+pub fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    if pos < start || pos > start + line.len() {
+        return 1;
+    }
+    return pos - start + 1;
+}
+
+// Parses token as a number, token must be a slice of line
+pub fn parse_number<T: FromStr>(line_index: usize, line: &str, token: &str) -> Result<T> {
+    return token.parse().map_err(|_| {
+        Error::parse_token(line_index, line, token, &format!("Not a number '{token}'"))
+    });
+}
+
+// Parses toks[index] as a number, toks must be slices of line
+pub fn parse_field<T: FromStr>(
+    line_index: usize,
+    line: &str,
+    toks: &[&str],
+    index: usize,
+) -> Result<T> {
+    return match toks.get(index) {
+        Some(token) => parse_number(line_index, line, token),
+        None => Err(Error::parse(
+            line_index,
+            line.len() + 1,
+            &format!("Missing field {}", index + 1),
+        )),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let line = "move 3 from x to 2";
+        let toks: Vec<&str> = line.split(' ').collect();
+        assert_eq!(parse_field::<usize>(4, line, &toks, 1).unwrap(), 3);
+
+        let err = parse_field::<usize>(4, line, &toks, 3)
+            .unwrap_err()
+            .in_day(5);
+        assert_eq!(err.to_string(), "Day05 line 5 column 13: Not a number 'x'");
+        assert_eq!(err.day(), Some(5));
+
+        let err = parse_field::<usize>(0, line, &toks, 6).unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 1 column 19: Missing field 7");
+
+        let err = Error::parse(2, 4, "Unknown shape")
+            .in_file("rules.txt")
+            .in_day(2);
+        assert_eq!(
+            err.to_string(),
+            "Day02 'rules.txt' line 3 column 4: Unknown shape"
+        );
+        let err = Error::input("No shapes").in_file("rules.txt");
+        assert_eq!(
+            err.to_string(),
+            "Day00 unexpected input in 'rules.txt': No shapes"
+        );
+    }
+}
@@ -0,0 +1,691 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::answers::{answers_file, Answers};
+use crate::error::Error;
+use crate::input::Input;
+use crate::runner::{quietly, run_part};
+use crate::solution::{Answer, Puzzle};
+use crate::util::file_to_vec;
+
+// Example inputs and their answers pulled out of a puzzle description
+// The description is the text in the /* */ block of a day's source, the puzzle page HTML
+// or a markdown copy of the puzzle page
+//
+// Example answers are never guessed from the text : the solution is run on each example
+// and its answer is only kept when the description states it, the emphasised code of a
+// puzzle page or the value a sentence of the source ends on
+
+#[derive(Debug, PartialEq)]
+enum Item {
+    Prose(String),
+    Block(Vec<String>),
+}
+
+#[derive(Debug, Default)]
+pub struct Description {
+    // The prose and the example or diagram blocks of each part in order
+    parts: [Vec<Item>; 2],
+    // Emphasised code in each part, the puzzle pages give the example answers as <code><em>
+    // None when the description has no markup and answers are found in the sentences
+    emphasised: [Option<Vec<String>>; 2],
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub lines: Vec<String>,
+    pub answers: Answers,
+}
+
+// What extract_examples did for a day
+#[derive(Debug, Default)]
+pub struct ExamplesReport {
+    // The description the examples are from
+    pub source: String,
+    pub found: usize,
+    // The example files saved and their answers
+    pub saved: Vec<(String, Answers)>,
+    pub answers_file: String,
+    // The parts of the answers file given the answer in the description
+    pub recorded: Vec<usize>,
+    // The parts where the description has another answer than the answers file
+    // (part, description, answers file)
+    pub differ: Vec<(usize, String, String)>,
+    pub errors: Vec<Error>,
+}
+
+const YOUR_ANSWER: &str = "Your puzzle answer was ";
+
+// Some solutions have constants for the real input and run for a very long time on
+// the examples (e.g. the 4000000 square of Day 15), those runs are abandoned
+const RUN_LIMIT: Duration = Duration::from_secs(5);
+
+pub fn source_file(puzzle: &Puzzle) -> String {
+    return format!("src/year{}/day{:02}.rs", puzzle.year, puzzle.day);
+}
+
+pub fn example_file(puzzle: &Puzzle, index: usize) -> String {
+    return format!(
+        "data/{}/day{:02}/example{index}.txt",
+        puzzle.year, puzzle.day
+    );
+}
+
+// The example files of a day in order : data/YYYY/dayNN/example1.txt, example2.txt, ...
+pub fn example_files(puzzle: &Puzzle) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut index = 1;
+    while Path::new(&example_file(puzzle, index)).exists() {
+        files.push(example_file(puzzle, index));
+        index += 1;
+    }
+    return files;
+}
+
+pub fn save_example(filename: &str, example: &Example) -> io::Result<()> {
+    fs::write(filename, example.lines.join("\n") + "\n")?;
+    return example.answers.save(&answers_file(filename));
+}
+
+// .html and .md files are puzzle pages, any other file is a day's source
+pub fn load_description(filename: &str) -> io::Result<Description> {
+    let lines = file_to_vec(filename)?;
+    if filename.ends_with(".html") || filename.ends_with(".htm") {
+        return Ok(Description::from_html(&lines.join("\n")));
+    }
+    if filename.ends_with(".md") {
+        return Ok(Description::from_markdown(&lines));
+    }
+    return Ok(Description::from_source(&lines));
+}
+
+// A line of prose starts like a sentence and ends like one
+fn is_prose(line: &str) -> bool {
+    let starts = line.starts_with(|c: char| c.is_ascii_uppercase() || c == '(');
+    let ends = line.ends_with(['.', ':', '?', '!', ')']);
+    return starts && ends && line.split_whitespace().count() >= 4;
+}
+
+// The prose before a puzzle input example, not the many diagrams of its steps
+// "For example:", "For example, suppose ... the following list:", "Here's a larger example:"
+fn is_example_lead_in(prose: &str) -> bool {
+    let prose = prose.to_lowercase();
+    if !prose.ends_with(':') || prose.contains("above") || prose.contains("same") {
+        return false;
+    }
+    if prose.starts_with("for example") {
+        return prose == "for example:"
+            || prose.contains("following")
+            || prose.contains("suppose")
+            || prose.contains("consider");
+    }
+    return prose.contains("larger example")
+        || prose.contains("complex example")
+        || prose.starts_with("consider this")
+        || prose.starts_with("consider the following");
+}
+
+fn part_header(line: &str) -> bool {
+    return line.contains("--- Part Two ---");
+}
+
+fn words(prose: &str) -> impl Iterator<Item = &str> {
+    return prose
+        .split_whitespace()
+        .map(|w| w.trim_matches(['.', ',', ';', ':', '!', '?', '(', ')', '"', '\'']))
+        .filter(|w| !w.is_empty());
+}
+
+// The sentences of prose without the remarks in brackets
+fn sentences(prose: &str) -> Vec<String> {
+    let mut text = String::new();
+    let mut depth = 0;
+    for c in prose.chars() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth > 0 => depth -= 1,
+            _ if depth == 0 => text.push(c),
+            _ => {}
+        }
+    }
+    return text
+        .split([';', '?', '!'])
+        .flat_map(|s| s.split(". "))
+        .map(|s| s.to_string())
+        .collect();
+}
+
+// A word that could be an answer : it has a digit or is capital letters like CMZ
+fn is_value(word: &str) -> bool {
+    return word.bytes().any(|b| b.is_ascii_digit())
+        || (word.len() > 1 && word.bytes().all(|b| b.is_ascii_uppercase()));
+}
+
+// The value a sentence ends on : "the sum of these is 157" states 157
+fn stated_value(sentence: &str) -> Option<&str> {
+    return words(sentence).filter(|w| is_value(w)).last();
+}
+
+fn unescape(text: &str) -> String {
+    return text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+}
+
+fn trim_blank_lines(lines: &[String]) -> Vec<String> {
+    let first = lines.iter().position(|l| !l.trim().is_empty());
+    let last = lines.iter().rposition(|l| !l.trim().is_empty());
+    return match (first, last) {
+        (Some(first), Some(last)) => lines[first..=last].to_vec(),
+        _ => Vec::new(),
+    };
+}
+
+impl Description {
+    // The text of the /* */ block in a day's source
+    // Blocks follow a line of prose ending with ':' and end at the next line of prose
+    pub fn from_source(source: &[String]) -> Description {
+        let mut description = Description::default();
+        let start = source.iter().position(|l| l.trim() == "/*");
+        let end = source.iter().position(|l| l.trim() == "*/");
+        let lines = match (start, end) {
+            (Some(start), Some(end)) if start < end => &source[start + 1..end],
+            _ => return description,
+        };
+        let mut part = 0;
+        let mut i = 0;
+        while i < lines.len() {
+            let line = lines[i].trim_end();
+            i += 1;
+            if part_header(line) {
+                part = 1;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            description.parts[part].push(Item::Prose(line.trim().to_string()));
+            if !line.ends_with(':') || !line.starts_with(|c: char| c.is_ascii_uppercase()) {
+                continue;
+            }
+            let mut block = Vec::new();
+            while i < lines.len() && !is_prose(&lines[i]) && !part_header(&lines[i]) {
+                block.push(lines[i].trim_end().to_string());
+                i += 1;
+            }
+            let block = trim_blank_lines(&block);
+            if !block.is_empty() {
+                description.parts[part].push(Item::Block(block));
+            }
+        }
+        return description;
+    }
+
+    // The puzzle page : parts are the <article> elements, blocks are <pre> and
+    // example answers are <em>
+    pub fn from_html(html: &str) -> Description {
+        let mut description = Description {
+            emphasised: [Some(Vec::new()), Some(Vec::new())],
+            ..Default::default()
+        };
+        let mut articles = 0;
+        let mut in_article = false;
+        let mut in_pre = false;
+        let mut emphasis: Option<String> = None;
+        let mut in_code = false;
+        let mut text = String::new();
+        let mut rest = html;
+        while !rest.is_empty() {
+            let (chunk, tag) = match rest.find('<') {
+                Some(0) => match rest.find('>') {
+                    Some(end) => {
+                        let tag = &rest[1..end];
+                        rest = &rest[end + 1..];
+                        ("", tag)
+                    }
+                    None => (std::mem::take(&mut rest), ""),
+                },
+                Some(start) => {
+                    let chunk = &rest[..start];
+                    rest = &rest[start..];
+                    (chunk, "")
+                }
+                None => (std::mem::take(&mut rest), ""),
+            };
+            if !chunk.is_empty() {
+                let chunk = unescape(chunk);
+                if let Some(emphasis) = emphasis.as_mut() {
+                    emphasis.push_str(&chunk);
+                }
+                text.push_str(&chunk);
+                continue;
+            }
+            let name = tag.split_whitespace().next().unwrap_or("").to_lowercase();
+            let part = (articles.max(1) - 1).min(1);
+            match name.as_str() {
+                "code" => in_code = true,
+                "/code" => in_code = false,
+                "em" if in_code => emphasis = Some(String::new()),
+                "/em" => {
+                    if let (Some(emphasised), Some(list)) =
+                        (emphasis.take(), description.emphasised[part].as_mut())
+                    {
+                        list.push(emphasised.trim().to_string());
+                    }
+                }
+                "pre" => {
+                    text.clear();
+                    in_pre = true;
+                }
+                "/pre" => {
+                    let lines: Vec<String> = text.lines().map(|l| l.to_string()).collect();
+                    let block = trim_blank_lines(&lines);
+                    if in_article && !block.is_empty() {
+                        description.parts[part].push(Item::Block(block));
+                    }
+                    text.clear();
+                    in_pre = false;
+                }
+                "article" | "/article" | "p" | "/p" | "li" | "/li" | "ul" | "/ul" | "h2"
+                | "/h2" => {
+                    if !in_pre {
+                        let prose = text.split_whitespace().collect::<Vec<&str>>().join(" ");
+                        // The answers are after each article
+                        if !prose.is_empty() && (in_article || prose.starts_with(YOUR_ANSWER)) {
+                            description.parts[part].push(Item::Prose(prose));
+                        }
+                        text.clear();
+                    }
+                    if name == "article" {
+                        articles += 1;
+                        in_article = articles <= 2;
+                    } else if name == "/article" {
+                        in_article = false;
+                    }
+                }
+                _ => {}
+            }
+        }
+        return description;
+    }
+
+    // A markdown copy of the puzzle page : blocks are ``` fences and example answers are
+    // emphasised code, `*7*`, `**7**` or **`7`**
+    pub fn from_markdown(lines: &[String]) -> Description {
+        let mut description = Description {
+            emphasised: [Some(Vec::new()), Some(Vec::new())],
+            ..Default::default()
+        };
+        let mut part = 0;
+        let mut block: Option<Vec<String>> = None;
+        for line in lines {
+            if line.trim_start().starts_with("```") {
+                match block.take() {
+                    Some(lines) => {
+                        let lines = trim_blank_lines(&lines);
+                        if !lines.is_empty() {
+                            description.parts[part].push(Item::Block(lines));
+                        }
+                    }
+                    None => block = Some(Vec::new()),
+                }
+                continue;
+            }
+            if let Some(lines) = block.as_mut() {
+                lines.push(line.trim_end().to_string());
+                continue;
+            }
+            if part_header(line) {
+                part = 1;
+            }
+            let line = line.replace("**", "*");
+            let segments: Vec<&str> = line.split('*').collect();
+            if let Some(list) = description.emphasised[part].as_mut() {
+                for s in (1..segments.len()).step_by(2) {
+                    let segment = segments[s].trim();
+                    let code_inside =
+                        segment.len() > 1 && segment.starts_with('`') && segment.ends_with('`');
+                    let code_outside = segments[s - 1].ends_with('`')
+                        && segments
+                            .get(s + 1)
+                            .is_some_and(|next| next.starts_with('`'));
+                    if code_inside || code_outside {
+                        list.push(segment.trim_matches('`').trim().to_string());
+                    }
+                }
+            }
+            let prose = segments.concat().replace('`', "");
+            let prose = prose.trim_start_matches(['#', '-', ' ']).trim();
+            if !prose.is_empty() {
+                description.parts[part].push(Item::Prose(prose.to_string()));
+            }
+        }
+        return description;
+    }
+
+    // "Your puzzle answer was 71502." in each part
+    pub fn answers(&self) -> Answers {
+        let mut answers = Answers::new();
+        for p in 0..2 {
+            for item in &self.parts[p] {
+                if let Item::Prose(prose) = item {
+                    if let Some(answer) = prose.strip_prefix(YOUR_ANSWER) {
+                        let answer = answer.trim_end_matches('.').to_string();
+                        answers.set(p + 1, &Answer::Text(answer));
+                    }
+                }
+            }
+        }
+        return answers;
+    }
+
+    // The blocks that look like puzzle inputs, with the part they are in (0 or 1)
+    // and the index of the block in that part
+    fn example_inputs(&self) -> Vec<(usize, usize, &Vec<String>)> {
+        let mut inputs = Vec::new();
+        for p in 0..2 {
+            for i in 1..self.parts[p].len() {
+                if let Item::Block(block) = &self.parts[p][i] {
+                    if self.is_input(p, i) {
+                        inputs.push((p, i, block));
+                    }
+                }
+            }
+        }
+        return inputs;
+    }
+
+    fn is_input(&self, part: usize, index: usize) -> bool {
+        let items = &self.parts[part];
+        return match (&items[index - 1], &items[index]) {
+            (Item::Prose(prose), Item::Block(_)) => is_example_lead_in(prose),
+            _ => false,
+        };
+    }
+
+    // Does the description of part state answer after item start and before the next
+    // example input, the answers of an example are given before the next one starts
+    // Without markup a sentence states the value it ends on, a block states a multi-line
+    // answer it is equal to, and a line of a block "<example>: ... 19" states 19 for a one
+    // line example
+    fn mentions(&self, part: usize, start: usize, example: &[String], answer: &str) -> bool {
+        if let Some(emphasised) = &self.emphasised[part] {
+            return emphasised.iter().any(|e| e == answer);
+        }
+        let items = &self.parts[part];
+        for i in start..items.len() {
+            if i > start && self.is_input(part, i) {
+                break;
+            }
+            let found = match &items[i] {
+                Item::Prose(prose) if prose.starts_with(YOUR_ANSWER) => false,
+                Item::Prose(prose) => sentences(prose)
+                    .iter()
+                    .any(|sentence| stated_value(sentence) == Some(answer)),
+                Item::Block(block) if answer.contains('\n') => block.join("\n") == answer,
+                Item::Block(block) => {
+                    example.len() == 1
+                        && block.iter().any(|line| {
+                            line.strip_prefix(example[0].as_str())
+                                .and_then(|rest| rest.strip_prefix(':'))
+                                .is_some_and(|rest| stated_value(rest) == Some(answer))
+                        })
+                }
+            };
+            if found {
+                return true;
+            }
+        }
+        return false;
+    }
+}
+
+// The answer of a part on an example, None if it fails or is over the time limit
+// A run over the limit is left running in the background until the program exits
+fn run_example(puzzle: &'static Puzzle, lines: &[String], part: usize) -> Option<Answer> {
+    let (sender, receiver) = mpsc::channel();
+    let input = Input::from_lines(lines);
+    thread::spawn(move || {
+        // Blocks that are not valid inputs make some solutions panic, the panics are expected
+        let _ = sender.send(quietly(|| run_part(puzzle, &input, part)).answer);
+    });
+    return match receiver.recv_timeout(RUN_LIMIT) {
+        Ok(Ok(Answer::None)) => None,
+        Ok(Ok(answer)) => Some(answer),
+        _ => None,
+    };
+}
+
+// Runs the solution on each example input in the description
+// Examples in part one are used for both parts, examples in part two only for part two
+pub fn find_examples(puzzle: &'static Puzzle, description: &Description) -> Vec<Example> {
+    let mut examples: Vec<Example> = Vec::new();
+    for (p, index, lines) in description.example_inputs() {
+        if examples.iter().any(|e| &e.lines == lines) {
+            continue;
+        }
+        let mut answers = Answers::new();
+        for part in (p + 1)..=2 {
+            // The answers of part two are after any part one example
+            let start = if part - 1 == p { index } else { 0 };
+            let Some(answer) = run_example(puzzle, lines, part) else {
+                continue;
+            };
+            if description.mentions(part - 1, start, lines, &answer.to_string()) {
+                answers.set(part, &answer);
+            }
+        }
+        if answers != Answers::new() {
+            examples.push(Example {
+                lines: lines.clone(),
+                answers,
+            });
+        }
+    }
+    return examples;
+}
+
+// Saves the examples of a day found in the description in from, or else the day's source, and
+// records the answers the description gives that are missing from the answers file
+pub fn extract_examples(puzzle: &'static Puzzle, from: Option<&str>) -> ExamplesReport {
+    let mut report = ExamplesReport {
+        source: from.map_or_else(|| source_file(puzzle), |from| from.to_string()),
+        answers_file: answers_file(puzzle.input_file),
+        ..ExamplesReport::default()
+    };
+    let description = match load_description(&report.source) {
+        Ok(description) => description,
+        Err(err) => {
+            report.errors.push(Error::io(&report.source, err));
+            return report;
+        }
+    };
+    let found = find_examples(puzzle, &description);
+    report.found = found.len();
+    for (i, example) in found.into_iter().enumerate() {
+        let filename = example_file(puzzle, i + 1);
+        match save_example(&filename, &example) {
+            Ok(()) => report.saved.push((filename, example.answers)),
+            Err(err) => report.errors.push(Error::io(&filename, err)),
+        }
+    }
+
+    let mut answers = match Answers::load(&report.answers_file) {
+        Ok(answers) => answers,
+        Err(err) => {
+            report.errors.push(Error::io(&report.answers_file, err));
+            return report;
+        }
+    };
+    let (added, differ) = answers.merge(&description.answers());
+    report.differ = differ;
+    if !added.is_empty() {
+        match answers.save(&report.answers_file) {
+            Ok(()) => report.recorded = added,
+            Err(err) => report.errors.push(Error::io(&report.answers_file, err)),
+        }
+    }
+    return report;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::answers::Verdict;
+    use crate::util::str_array_to_string_array;
+    use crate::year2022::PUZZLES;
+
+    #[test]
+    fn source() {
+        let source = str_array_to_string_array(vec![
+            "use crate::solution::{Answer, Puzzle, Solution};",
+            "/*",
+            "For example, suppose the Elves finish writing their items' Calories and end up with the following list:",
+            "",
+            "1000",
+            "",
+            "2000",
+            "3000",
+            "This list represents the Calories of the food carried by two Elves:",
+            "The second Elf carries 2000 and 3000 Calories.",
+            "In the example above, this is 5000 (carried by the second Elf).",
+            "",
+            "Your puzzle answer was 71502.",
+            "",
+            "--- Part Two ---",
+            "",
+            "The sum of the Calories carried by these three elves is 6000.",
+            "*/",
+        ]);
+        let description = Description::from_source(&source);
+        let inputs = description.example_inputs();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(inputs[0].0, 0);
+        assert_eq!(
+            inputs[0].2,
+            &str_array_to_string_array(vec!["1000", "", "2000", "3000"])
+        );
+        let example = inputs[0].2.clone();
+        assert!(description.mentions(0, inputs[0].1, &example, "5000"));
+        assert!(description.mentions(0, inputs[0].1, &example, "3000"));
+        // Only the value a sentence ends on is stated
+        assert!(!description.mentions(0, inputs[0].1, &example, "2000"));
+        assert!(!description.mentions(0, inputs[0].1, &example, "71502"));
+        assert!(description.mentions(1, 0, &example, "6000"));
+        assert_eq!(description.answers().get(1), Some("71502"));
+        assert_eq!(description.answers().get(2), None);
+
+        let examples = find_examples(PUZZLES[0], &description);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(examples[0].answers.get(1), Some("5000"));
+        // Less than three elves is an error
+        assert_eq!(examples[0].answers.get(2), None);
+
+        // The answers of one line examples can be in a list of them
+        let source = str_array_to_string_array(vec![
+            "/*",
+            "For example, suppose you receive the following datastream buffer:",
+            "",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb",
+            "",
+            "--- Part Two ---",
+            "Here are the first positions of start-of-message markers for all of the above examples:",
+            "",
+            "mjqjpqmgbljsphdztnvjfqwrcgsmlb: first marker after character 19",
+            "bvwbjplbgvbhsrlpgdmjqwftvncz: first marker after character 23",
+            "*/",
+        ]);
+        let description = Description::from_source(&source);
+        let example = vec!["mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string()];
+        assert!(description.mentions(1, 0, &example, "19"));
+        assert!(!description.mentions(1, 0, &example, "23"));
+        let examples = find_examples(PUZZLES[5], &description);
+        assert_eq!(examples[0].answers.get(2), Some("19"));
+    }
+
+    #[test]
+    fn html() {
+        let html = "<main>\n\
+            <article class=\"day-desc\"><h2>--- Day 6: Tuning Trouble ---</h2>\n\
+            <p>For example, suppose you receive the following datastream buffer:</p>\n\
+            <pre><code>mjqjp&lt;qmgbljsphdztnvjfqwrcgsmlb</code></pre>\n\
+            <p>Here, the first marker is after character <code><em>7</em></code>.</p>\n\
+            </article>\n\
+            <p>Your puzzle answer was <code>1093</code>.</p>\n\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2>\n\
+            <p>The first marker is after character <code><em>19</em></code> not <em>20</em>.</p>\n\
+            </article>\n\
+            <p>Your puzzle answer was <code>3534</code>.</p>\n\
+            </main>";
+        let description = Description::from_html(html);
+        let inputs = description.example_inputs();
+        assert_eq!(inputs.len(), 1);
+        assert_eq!(
+            inputs[0].2,
+            &vec!["mjqjp<qmgbljsphdztnvjfqwrcgsmlb".to_string()]
+        );
+        let example = inputs[0].2.clone();
+        assert!(description.mentions(0, 0, &example, "7"));
+        assert!(!description.mentions(0, 0, &example, "first"));
+        assert!(description.mentions(1, 0, &example, "19"));
+        assert!(!description.mentions(1, 0, &example, "20"));
+        assert_eq!(description.answers().get(1), Some("1093"));
+        assert_eq!(description.answers().get(2), Some("3534"));
+    }
+
+    #[test]
+    fn markdown() {
+        let markdown = str_array_to_string_array(vec![
+            "## --- Day 2: Rock Paper Scissors ---",
+            "For example, suppose you were given the following strategy guide:",
+            "```",
+            "A Y",
+            "B X",
+            "C Z",
+            "```",
+            "You would get a total score of `**15**` (8 + 1 + 6).",
+            "Your puzzle answer was `15691`.",
+            "## --- Part Two ---",
+            "You would get a total score of *`12`* not *11*.",
+        ]);
+        let description = Description::from_markdown(&markdown);
+        let examples = find_examples(PUZZLES[1], &description);
+        assert_eq!(examples.len(), 1);
+        assert_eq!(
+            examples[0].lines,
+            str_array_to_string_array(vec!["A Y", "B X", "C Z"])
+        );
+        assert_eq!(examples[0].answers.get(1), Some("15"));
+        assert_eq!(examples[0].answers.get(2), Some("12"));
+        assert_eq!(description.answers().get(1), Some("15691"));
+    }
+
+    // The examples saved by "aoc2022 examples" are test cases for every day
+    #[test]
+    fn saved_examples() {
+        for puzzle in PUZZLES {
+            for filename in example_files(puzzle) {
+                let input = Input::load(&filename).unwrap();
+                let answers = Answers::load(&answers_file(&filename)).unwrap();
+                for part in 1..=2 {
+                    if answers.get(part).is_none() {
+                        continue;
+                    }
+                    let answer = run_part(puzzle, &input, part).answer.unwrap();
+                    assert_eq!(
+                        answers.check(part, &answer),
+                        Verdict::Correct,
+                        "{filename} part{part}"
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::{Error, Result};
+
+// A width by height grid of cells stored row by row, indexed with grid[(x, y)]
+// x goes right and y goes down from (0, 0) at the top left
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+// Steps to the neighbours of a cell : up, right, down, left then the diagonals
+pub const DIRECTIONS4: [(i64, i64); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+pub const DIRECTIONS8: [(i64, i64); 8] = [
+    (0, -1),
+    (1, 0),
+    (0, 1),
+    (-1, 0),
+    (1, -1),
+    (1, 1),
+    (-1, 1),
+    (-1, -1),
+];
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, value: T) -> Grid<T> {
+        return Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        };
+    }
+}
+
+impl<T> Grid<T> {
+    // cell gives the value of each cell from its x, y
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut(usize, usize) -> T,
+    ) -> Grid<T> {
+        let mut cells = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                cells.push(cell(x, y));
+            }
+        }
+        return Grid {
+            width,
+            height,
+            cells,
+        };
+    }
+
+    // Parses a character map, every line is a row and they must all be the same length
+    // cell is given x, y and the character and returns None for a character that is not allowed
+    pub fn parse(
+        lines: &[impl AsRef<str>],
+        message: &str,
+        mut cell: impl FnMut(usize, usize, u8) -> Option<T>,
+    ) -> Result<Grid<T>> {
+        if lines.is_empty() || lines[0].as_ref().is_empty() {
+            return Err(Error::input("Empty grid"));
+        }
+        let width = lines[0].as_ref().len();
+        let mut cells = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let line = line.as_ref();
+            if line.len() != width {
+                return Err(Error::parse(
+                    y,
+                    line.len().min(width) + 1,
+                    &format!("Expected a row of {width} not {}", line.len()),
+                ));
+            }
+            let bytes = line.as_bytes();
+            for x in 0..width {
+                match cell(x, y, bytes[x]) {
+                    Some(value) => cells.push(value),
+                    None => return Err(Error::parse(y, x + 1, message)),
+                }
+            }
+        }
+        return Ok(Grid {
+            width,
+            height: lines.len(),
+            cells,
+        });
+    }
+
+    pub fn width(&self) -> usize {
+        return self.width;
+    }
+
+    pub fn height(&self) -> usize {
+        return self.height;
+    }
+
+    pub fn index(&self, x: usize, y: usize) -> usize {
+        assert!(
+            x < self.width && y < self.height,
+            "({x}, {y}) is outside the grid"
+        );
+        return y * self.width + x;
+    }
+
+    pub fn xy(&self, index: usize) -> (usize, usize) {
+        return (index % self.width, index / self.width);
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        return Some(&self.cells[y * self.width + x]);
+    }
+
+    // The cell a step of (dx, dy) away, None if it is outside the grid
+    pub fn step(&self, x: usize, y: usize, (dx, dy): (i64, i64)) -> Option<(usize, usize)> {
+        let new_x = x as i64 + dx;
+        let new_y = y as i64 + dy;
+        if new_x < 0 || new_y < 0 || new_x >= self.width as i64 || new_y >= self.height as i64 {
+            return None;
+        }
+        return Some((new_x as usize, new_y as usize));
+    }
+
+    // Coordinates outside the grid wrap round to the other side
+    pub fn get_wrapped(&self, x: i64, y: i64) -> &T {
+        let x = x.rem_euclid(self.width as i64) as usize;
+        let y = y.rem_euclid(self.height as i64) as usize;
+        return &self.cells[y * self.width + x];
+    }
+
+    pub fn neighbours4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return DIRECTIONS4
+            .iter()
+            .filter_map(move |&step| self.step(x, y, step));
+    }
+
+    pub fn neighbours8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        return DIRECTIONS8
+            .iter()
+            .filter_map(move |&step| self.step(x, y, step));
+    }
+
+    pub fn cells(&self) -> &[T] {
+        return &self.cells;
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        return &self.cells[y * self.width..(y + 1) * self.width];
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        return self.cells.chunks(self.width);
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        return self.cells[x..].iter().step_by(self.width);
+    }
+
+    // A grid of the same size with cell applied to every cell
+    pub fn map<U>(&self, cell: impl Fn(&T) -> U) -> Grid<U> {
+        return Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(cell).collect(),
+        };
+    }
+
+    // One line per row, cell gives the character for each cell
+    pub fn render(&self, cell: impl Fn(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.rows() {
+            text.extend(row.iter().map(&cell));
+            text.push('\n');
+        }
+        return text;
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        return &self.cells[Grid::index(self, x, y)];
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let index = Grid::index(self, x, y);
+        return &mut self.cells[index];
+    }
+}
+
+// Character maps print as they were read
+impl<T: Copy + Into<char>> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return write!(f, "{}", self.render(|&c| c.into()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn parse() {
+        let lines = str_array_to_string_array(vec!["30373", "25512", "65332"]);
+        let grid = Grid::parse(&lines, "Expected 0-9", |_, _, c| {
+            c.is_ascii_digit().then(|| c - b'0')
+        })
+        .unwrap();
+        assert_eq!((grid.width(), grid.height()), (5, 3));
+        assert_eq!(grid[(3, 0)], 7);
+        assert_eq!(grid.get(1, 2), Some(&5));
+        assert_eq!(grid.get(5, 0), None);
+        assert_eq!(grid.row(1), &[2, 5, 5, 1, 2]);
+        assert_eq!(grid.column(4).copied().collect::<Vec<u8>>(), vec![3, 2, 2]);
+        assert_eq!(grid.xy(grid.index(4, 2)), (4, 2));
+        let doubled = grid.map(|&h| h as usize * 2);
+        assert_eq!(doubled.row(0), &[6, 0, 6, 14, 6]);
+        let diagonal = Grid::from_fn(3, 2, |x, y| x == y);
+        assert_eq!(diagonal.cells(), &[true, false, false, false, true, false]);
+
+        let lines = str_array_to_string_array(vec!["303", "2x5"]);
+        let err = Grid::parse(&lines, "Expected 0-9", |_, _, c| {
+            c.is_ascii_digit().then_some(c)
+        })
+        .unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 2 column 2: Expected 0-9");
+        let lines = str_array_to_string_array(vec!["303", "25"]);
+        let err = Grid::parse(&lines, "", |_, _, c| Some(c)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day00 line 2 column 3: Expected a row of 3 not 2"
+        );
+    }
+
+    #[test]
+    fn neighbours() {
+        let mut grid = Grid::new(3, 2, b'.');
+        grid[(2, 1)] = b'#';
+        assert_eq!(grid.to_string(), "...\n..#\n");
+        assert_eq!(
+            grid.neighbours4(0, 0).collect::<Vec<(usize, usize)>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbours8(1, 1).count(), 5);
+        assert_eq!(grid.step(2, 1, (1, 0)), None);
+        assert_eq!(*grid.get_wrapped(-1, 3), b'#');
+        assert_eq!(
+            grid.render(|&c| if c == b'#' { 'X' } else { ' ' }),
+            "   \n  X\n"
+        );
+    }
+}
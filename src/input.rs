@@ -0,0 +1,85 @@
+use std::fs;
+use std::io;
+use std::io::Read;
+
+// A puzzle input read in one go and split into lines as they are needed
+// Lines end with "\n" or "\r\n" and a newline at the end of the input does not start
+// another line, so the same input saved on Windows or without a final newline parses the same
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Input {
+    text: String,
+}
+
+impl Input {
+    // Reads all of the input into memory before any of it is parsed, this does not stream
+    // Days that can solve a part a line at a time implement Solution::solve_stream instead
+    // Read errors and input that is not UTF-8 are returned instead of being dropped
+    pub fn read(mut reader: impl Read) -> io::Result<Input> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes)?;
+        let text = String::from_utf8(bytes).map_err(|err| {
+            let at = err.utf8_error().valid_up_to();
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Input is not UTF-8 at byte {at}"),
+            )
+        })?;
+        return Ok(Input { text });
+    }
+
+    pub fn load(filename: &str) -> io::Result<Input> {
+        return Input::read(fs::File::open(filename)?);
+    }
+
+    pub fn from_lines(lines: &[String]) -> Input {
+        let mut text = String::new();
+        for line in lines {
+            text += line;
+            text.push('\n');
+        }
+        return Input { text };
+    }
+
+    // The input as read including the line endings
+    pub fn bytes(&self) -> &[u8] {
+        return self.text.as_bytes();
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        return self.text.lines();
+    }
+
+    // For solutions that work on bytes
+    pub fn byte_lines(&self) -> impl Iterator<Item = &[u8]> {
+        return self.text.lines().map(|line| line.as_bytes());
+    }
+
+    pub fn to_lines(&self) -> Vec<String> {
+        return self.lines().map(|line| line.to_string()).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn lines() {
+        let expected = str_array_to_string_array(vec!["1", "", "2"]);
+        for text in ["1\n\n2", "1\n\n2\n", "1\r\n\r\n2\r\n"] {
+            let input = Input::read(text.as_bytes()).unwrap();
+            assert_eq!(input.to_lines(), expected);
+            assert_eq!(input.bytes(), text.as_bytes());
+        }
+        let input = Input::from_lines(&expected);
+        assert_eq!(input.bytes(), b"1\n\n2\n");
+        let byte_lines: Vec<&[u8]> = input.byte_lines().collect();
+        assert_eq!(byte_lines, vec![&b"1"[..], b"", b"2"]);
+        assert_eq!(Input::read(&b""[..]).unwrap().lines().count(), 0);
+
+        let err = Input::read(&b"1\n\xff\n"[..]).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(Input::load("data/missing/input.txt").is_err());
+    }
+}
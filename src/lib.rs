@@ -0,0 +1,19 @@
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod examples;
+pub mod grid;
+pub mod input;
+pub mod log;
+pub mod pool;
+pub mod render;
+pub mod report;
+pub mod runner;
+pub mod scaffold;
+pub mod scan;
+pub mod search;
+pub mod solution;
+pub mod util;
+pub mod visualize;
+pub mod year2022;
@@ -0,0 +1,110 @@
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+// How much debug output is written to stderr, each level includes the ones before it
+//   debug : the steps of a solution e.g. cycles found and new best routes
+//   trace : everything e.g. each search and each round of a simulation
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Off,
+    Debug,
+    Trace,
+}
+
+// The level can also be set in the environment : AOC_LOG=debug
+pub const ENV_VAR: &str = "AOC_LOG";
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Off as u8);
+
+impl Level {
+    pub fn parse(text: &str) -> Option<Level> {
+        return match text.to_ascii_lowercase().as_str() {
+            "off" | "" => Some(Level::Off),
+            "debug" => Some(Level::Debug),
+            "trace" => Some(Level::Trace),
+            _ => None,
+        };
+    }
+
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Level::Off => "off",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        };
+    }
+}
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+// A single load so a log point that is off costs next to nothing
+pub fn enabled(level: Level) -> bool {
+    return level != Level::Off && level as u8 <= LEVEL.load(Ordering::Relaxed);
+}
+
+// The level from AOC_LOG raised by the number of --verbose flags : 1 is debug, 2 is trace
+pub fn init(verbose: usize) -> Result<(), String> {
+    let from_env = match env::var(ENV_VAR) {
+        Ok(text) => match Level::parse(&text) {
+            Some(level) => level,
+            None => {
+                return Err(format!(
+                    "Invalid {ENV_VAR} '{text}' expected off, debug or trace"
+                ))
+            }
+        },
+        Err(_) => Level::Off,
+    };
+    let from_verbose = match verbose {
+        0 => Level::Off,
+        1 => Level::Debug,
+        _ => Level::Trace,
+    };
+    set_level(from_env.max(from_verbose));
+    return Ok(());
+}
+
+// [debug year2022::day17] Cycle 35 ...
+pub fn write(level: Level, module: &str, args: fmt::Arguments) {
+    let module = module.split_once("::").map_or(module, |(_, rest)| rest);
+    eprintln!("[{} {module}] {args}", level.name());
+}
+
+// The arguments are only formatted when the level is on
+#[macro_export]
+macro_rules! log_debug {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Debug) {
+            $crate::log::write($crate::log::Level::Debug, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[macro_export]
+macro_rules! log_trace {
+    ($($arg:tt)*) => {
+        if $crate::log::enabled($crate::log::Level::Trace) {
+            $crate::log::write($crate::log::Level::Trace, module_path!(), format_args!($($arg)*));
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels() {
+        assert_eq!(Level::parse("Debug"), Some(Level::Debug));
+        assert_eq!(Level::parse("trace"), Some(Level::Trace));
+        assert_eq!(Level::parse(""), Some(Level::Off));
+        assert_eq!(Level::parse("loud"), None);
+        assert!(Level::Debug < Level::Trace);
+        // Tests run in parallel so the level is left off
+        assert!(!enabled(Level::Off));
+        assert!(!enabled(Level::Trace));
+    }
+}
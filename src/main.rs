@@ -1,97 +1,442 @@
+use std::cell::RefCell;
 use std::env;
-use std::fs;
-use std::io;
-use std::io::BufRead;
-use std::io::BufReader;
-use std::time::Instant;
-
-mod day01;
-mod day02;
-mod day03;
-mod day04;
-mod day05;
-mod day06;
-mod day07;
-mod day08;
-mod day09;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day25;
-
-pub fn file_to_vec(filename: &str) -> io::Result<Vec<String>> {
-    let file_in = fs::File::open(filename)?;
-    let file_reader = BufReader::new(file_in);
-    Ok(file_reader.lines().filter_map(io::Result::ok).collect())
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::process;
+use std::rc::Rc;
+
+use aoc2022::bench::{bench_day, compare, load_baseline, save_baseline, to_table as bench_table};
+use aoc2022::cli::{parse_args, BenchOptions, Command, Options, RenderOptions, USAGE};
+use aoc2022::error::{Error, Result};
+use aoc2022::examples::extract_examples;
+use aoc2022::log;
+use aoc2022::pool::for_each_ordered;
+use aoc2022::render::FrameFiles;
+use aoc2022::report::{to_csv, to_json, to_matrix, to_table, Format, Status};
+use aoc2022::runner::{
+    finish_day, load_answers, load_day, panic_message, quietly, run_matrix, run_part_with,
+    stream_input, DayInput, DayRun, PartRun,
+};
+use aoc2022::scaffold::create_day;
+use aoc2022::solution::{Puzzle, Year};
+use aoc2022::visualize::play;
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("Error: {message}");
+    eprintln!();
+    eprint!("{USAGE}");
+    process::exit(2);
+}
+
+// Errors that do not come from a day's solution are prefixed with the day
+fn print_errors(puzzle: &Puzzle, errors: &[Error]) {
+    for error in errors {
+        match error.day() {
+            Some(_) => eprintln!("{error}"),
+            None => eprintln!("{}: {error}", puzzle.name()),
+        }
+    }
+}
+
+fn print_text(puzzle: &Puzzle, options: &Options, run: &DayRun) {
+    let name = puzzle.name();
+    println!("{name}: Start");
+    for r in &run.reports {
+        if r.status == Status::Error {
+            continue;
+        }
+        let part = r.part;
+        let result = &r.answer;
+        if result.is_multi_line() {
+            println!("{name}: Result{part}");
+            println!("{result}");
+        } else {
+            println!("{name}: Result{part} {result}");
+        }
+        if r.status == Status::Unknown && options.settings.is_empty() {
+            println!("{name}: Part{part} answer is unknown, use --record to save it");
+        }
+    }
+    print_errors(puzzle, &run.errors);
+    if let Some(filename) = &run.recorded {
+        println!("{name}: Recorded answers in '{filename}'");
+    }
+    println!("{name}: End");
+    let milli_seconds = run.elapsed.as_millis() as u64;
+    let seconds = (milli_seconds as f32) / 1000.0f32;
+    let minutes = seconds / 60.0f32;
+    println!("Elapsed {milli_seconds}ms {seconds}s {minutes}mins");
+}
+
+fn bench(puzzles: &[&Puzzle], options: &Options, bench_options: &BenchOptions) {
+    // Load the baseline first so a bad baseline fails before the slow part
+    let baseline = match &bench_options.baseline {
+        Some(filename) => match load_baseline(filename) {
+            Ok(baseline) => Some(baseline),
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let mut failed = false;
+    let mut results = Vec::new();
+    for &puzzle in puzzles {
+        let name = puzzle.name();
+        eprintln!(
+            "{name}: Bench {} runs after {} warm-up runs",
+            bench_options.runs, bench_options.warmup
+        );
+        match bench_day(puzzle, options, bench_options) {
+            Ok(day_results) => results.extend(day_results),
+            Err(err) => {
+                print_errors(puzzle, &[err]);
+                failed = true;
+            }
+        }
+    }
+
+    let comparisons = match &baseline {
+        Some(baseline) => compare(&results, baseline, bench_options.threshold),
+        None => Vec::new(),
+    };
+    print!("{}", bench_table(&results, &comparisons));
+    let regressions = comparisons
+        .iter()
+        .filter(|c| c.is_some_and(|c| c.regression))
+        .count();
+    if regressions > 0 {
+        eprintln!("{regressions} regressions against the baseline");
+        failed = true;
+    }
+
+    if let Some(filename) = &bench_options.save {
+        match save_baseline(filename, &results) {
+            Ok(()) => eprintln!("Saved baseline '{filename}'"),
+            Err(err) => {
+                eprintln!("Could not save baseline '{filename}' {err}");
+                failed = true;
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
 }
 
-pub fn str_array_to_string_array(strings: Vec<&str>) -> Vec<String> {
-    let mut lines = Vec::new() as Vec<String>;
-    for s in strings {
-        lines.push(s.to_string());
+// Saves the examples of each day and fills in the answers the description gives
+// that are missing from the answers file
+fn examples(puzzles: &[&'static Puzzle], from: Option<&str>) {
+    let mut failed = false;
+    for &puzzle in puzzles {
+        let name = puzzle.name();
+        let report = extract_examples(puzzle, from);
+        if report.found == 0 && report.errors.is_empty() {
+            println!(
+                "{name}: No examples with answers found in '{}'",
+                report.source
+            );
+        }
+        for (filename, answers) in &report.saved {
+            let mut summary = String::new();
+            for part in 1..=2 {
+                match answers.get(part) {
+                    Some(answer) if answer.contains('\n') => {
+                        summary += &format!(" part{part}: {} lines", answer.lines().count())
+                    }
+                    Some(answer) => summary += &format!(" part{part}: {answer}"),
+                    None => {}
+                }
+            }
+            println!("{name}: Saved '{filename}'{summary}");
+        }
+        for (part, answer, recorded) in &report.differ {
+            eprintln!(
+                "{name}: Part{part} answer in '{}' {answer} != {recorded} in '{}'",
+                report.source, report.answers_file
+            );
+        }
+        if !report.recorded.is_empty() {
+            println!("{name}: Recorded answers in '{}'", report.answers_file);
+        }
+        if !report.errors.is_empty() {
+            print_errors(puzzle, &report.errors);
+            failed = true;
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+// Runs the selected parts of each day saving an image of every step of the simulation
+fn render(puzzles: &[&Puzzle], options: &Options, render_options: &RenderOptions) {
+    let mut failed = false;
+    for &puzzle in puzzles {
+        let name = puzzle.name();
+        let input = match load_day(puzzle, options) {
+            Ok(input) => input,
+            Err(err) => {
+                print_errors(puzzle, &[err]);
+                failed = true;
+                continue;
+            }
+        };
+        for part in 1..=2 {
+            if !options.selects_part(part) {
+                continue;
+            }
+            let dir = Path::new(&render_options.out)
+                .join(puzzle.year.to_string())
+                .join(format!("day{:02}", puzzle.day))
+                .join(format!("part{part}"));
+            let files = Rc::new(RefCell::new(FrameFiles::new(
+                &dir,
+                render_options.image,
+                render_options.scale,
+                render_options.every,
+            )));
+            let mut solution = (puzzle.instance)(part == 1);
+            if let Err(err) = solution.parse(&input.input) {
+                print_errors(puzzle, &[err.in_day(puzzle.day)]);
+                failed = true;
+                break;
+            }
+            let frames = Rc::clone(&files);
+            if !solution.set_frames(Box::new(move |image| frames.borrow_mut().add(image))) {
+                eprintln!("{name}: Has nothing to render");
+                failed = true;
+                break;
+            }
+            let answer = if part == 1 {
+                solution.part1()
+            } else {
+                solution.part2()
+            };
+            let answer = match answer {
+                Ok(answer) => answer,
+                Err(err) => {
+                    print_errors(puzzle, &[err.in_day(puzzle.day)]);
+                    failed = true;
+                    continue;
+                }
+            };
+            let dir = dir.to_string_lossy();
+            let saved = files.borrow_mut().finish();
+            match saved {
+                Ok(count) => {
+                    println!("{name}: Result{part} {answer} saved {count} images in '{dir}'")
+                }
+                Err(err) => {
+                    print_errors(puzzle, &[Error::io(&dir, err)]);
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        process::exit(1);
     }
-    return lines;
 }
 
-fn run_day(requested_day: i32, this_day: i32, extra_days: bool, function: fn()) {
-    if (requested_day == -1)
-        || (requested_day == this_day)
-        || (extra_days && this_day >= requested_day)
-    {
-        let now = Instant::now();
-        function();
-        let milli_seconds = now.elapsed().as_millis() as u64;
-        let seconds = (milli_seconds as f32) / 1000.0f32;
-        let minutes = seconds / 60.0f32;
-        println!("Elapsed {milli_seconds}ms {seconds}s {minutes}mins");
+// Steps through the simulation of a day in the terminal
+fn visualize(puzzle: &'static Puzzle, options: &Options, fps: usize) {
+    let input = match load_day(puzzle, options) {
+        Ok(input) => input,
+        Err(err) => {
+            print_errors(puzzle, &[err]);
+            process::exit(1);
+        }
+    };
+    // A panic would be printed over the screen, a panic drawing the screen is reported once
+    // the terminal has been put back and a panic in the simulation is reported as its answer
+    let part = options.part.unwrap_or(1);
+    let played = panic::catch_unwind(AssertUnwindSafe(|| {
+        quietly(|| play(puzzle, input.input, part, fps))
+    }));
+    let played = played.unwrap_or_else(|payload| {
+        Err(Error::Panic {
+            day: puzzle.day,
+            part,
+            message: panic_message(&payload),
+        })
+    });
+    match played {
+        Ok(Some(answer)) => println!("{}: Result{part} {answer}", puzzle.name()),
+        Ok(None) => {}
+        Err(err) => {
+            print_errors(puzzle, &[err]);
+            process::exit(1);
+        }
     }
 }
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-
-    let mut day: i32 = -1;
-    let mut extra_days: bool = false;
-    if args.len() == 2 {
-        let arg1: &String = &args[1];
-        extra_days = arg1.ends_with('+');
-        day = arg1.trim_end_matches('+').parse().expect("Not a number");
-    }
-    run_day(day, 1, extra_days, day01::run);
-    run_day(day, 2, extra_days, day02::run);
-    run_day(day, 3, extra_days, day03::run);
-    run_day(day, 4, extra_days, day04::run);
-    run_day(day, 5, extra_days, day05::run);
-    run_day(day, 6, extra_days, day06::run);
-    run_day(day, 7, extra_days, day07::run);
-    run_day(day, 8, extra_days, day08::run);
-    run_day(day, 9, extra_days, day09::run);
-    run_day(day, 10, extra_days, day10::run);
-    run_day(day, 11, extra_days, day11::run);
-    run_day(day, 12, extra_days, day12::run);
-    run_day(day, 13, extra_days, day13::run);
-    run_day(day, 14, extra_days, day14::run);
-    run_day(day, 15, extra_days, day15::run);
-    run_day(day, 16, extra_days, day16::run);
-    run_day(day, 17, extra_days, day17::run);
-    run_day(day, 18, extra_days, day18::run);
-    run_day(day, 19, extra_days, day19::run);
-    run_day(day, 20, extra_days, day20::run);
-    run_day(day, 21, extra_days, day21::run);
-    run_day(day, 22, extra_days, day22::run);
-    run_day(day, 23, extra_days, day23::run);
-    run_day(day, 24, extra_days, day24::run);
-    run_day(day, 25, extra_days, day25::run);
+    let args: Vec<String> = env::args().skip(1).collect();
+    let options = match parse_args(&args) {
+        Ok(options) => options,
+        Err(message) => usage_error(&message),
+    };
+    if options.help {
+        print!("{USAGE}");
+        return;
+    }
+    if let Err(message) = log::init(options.verbose) {
+        usage_error(&message);
+    }
+
+    // The new day is not one of the puzzles yet and it can be in a new year
+    if let Command::New(day) = options.command {
+        let year = options.year.unwrap_or(Year::latest().year);
+        match create_day(year, day) {
+            Ok(files) => {
+                for file in files {
+                    println!("Made {file}");
+                }
+            }
+            Err(err) => {
+                eprintln!("{err}");
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    let year = match options.year {
+        Some(year) => match Year::find(year) {
+            Some(year) => year,
+            None => usage_error(&format!("There are no solutions for {year}")),
+        },
+        None => Year::latest(),
+    };
+    let puzzles: Vec<&Puzzle> = year
+        .puzzles
+        .iter()
+        .copied()
+        .filter(|puzzle| options.selects_day(puzzle.day))
+        .collect();
+    if puzzles.is_empty() {
+        usage_error("No puzzles match the requested days");
+    }
+    if let Command::Examples { from: Some(_) } = &options.command {
+        if puzzles.len() != 1 {
+            usage_error("--from can only be used when running a single day");
+        }
+    }
+    if options.input.is_some() && puzzles.len() != 1 {
+        usage_error("--input can only be used when running a single day");
+    }
+    if !options.settings.is_empty() && puzzles.len() != 1 {
+        usage_error("--set can only be used when running a single day");
+    }
+    if options.record && options.input_is_stdin() {
+        usage_error("--record can not be used when reading the input from stdin");
+    }
+
+    match &options.command {
+        Command::Run => run(&puzzles, &options),
+        Command::Bench(bench_options) => bench(&puzzles, &options, bench_options),
+        Command::Matrix => matrix(&puzzles, &options),
+        Command::Examples { from } => examples(&puzzles, from.as_deref()),
+        Command::Render(render_options) => render(&puzzles, &options, render_options),
+        Command::Visualize { fps } => visualize(puzzles[0], &options, *fps),
+        Command::New(_) => unreachable!("new is handled before the puzzles are selected"),
+    }
+}
+
+// Runs each day against every input in its data directory and shows which pass
+fn matrix(puzzles: &[&Puzzle], options: &Options) {
+    let matrix = run_matrix(puzzles, options);
+    for (name, error) in &matrix.errors {
+        eprintln!("{name}: {error}");
+    }
+    print!("{}", to_matrix(&matrix.names, &matrix.rows));
+    if !matrix.ok() {
+        process::exit(1);
+    }
+}
+
+// Days and their parts are run on up to options.jobs threads
+// Each day is reported in day order as soon as all of its parts have finished
+// With --stream only the answers are loaded and each part reads the input itself
+fn run(puzzles: &[&Puzzle], options: &Options) {
+    let inputs: Vec<Result<DayInput>> = puzzles
+        .iter()
+        .map(|puzzle| {
+            if options.stream {
+                return load_answers(puzzle, options);
+            }
+            return load_day(puzzle, options);
+        })
+        .collect();
+    let mut tasks = Vec::new();
+    let mut remaining = vec![0; puzzles.len()];
+    for d in 0..puzzles.len() {
+        if inputs[d].is_err() {
+            continue;
+        }
+        for part in 1..=2 {
+            if options.selects_part(part) {
+                tasks.push((d, part));
+                remaining[d] += 1;
+            }
+        }
+    }
+
+    let mut failed = false;
+    let mut reports = Vec::new();
+    let mut day_parts: Vec<Vec<PartRun>> = puzzles.iter().map(|_| Vec::new()).collect();
+    let mut next_day = 0;
+    let mut finish_days = |day_parts: &mut Vec<Vec<PartRun>>, remaining: &[usize]| {
+        while next_day < puzzles.len() && remaining[next_day] == 0 {
+            let puzzle = puzzles[next_day];
+            let parts = std::mem::take(&mut day_parts[next_day]);
+            let run = finish_day(puzzle, options, &inputs[next_day], parts);
+            if options.format == Format::Text {
+                print_text(puzzle, options, &run);
+            } else {
+                print_errors(puzzle, &run.errors);
+            }
+            if !run.ok() {
+                failed = true;
+            }
+            reports.extend(run.reports);
+            next_day += 1;
+        }
+    };
+
+    for_each_ordered(
+        options.jobs,
+        tasks.clone(),
+        |(d, part)| {
+            if options.stream {
+                return stream_input(puzzles[d], options, part);
+            }
+            let input = match &inputs[d] {
+                Ok(input) => &input.input,
+                Err(_) => unreachable!("Tasks are only made for loaded days"),
+            };
+            return run_part_with(puzzles[d], &options.settings, input, part);
+        },
+        |i, part_run| {
+            let d = tasks[i].0;
+            day_parts[d].push(part_run);
+            remaining[d] -= 1;
+            finish_days(&mut day_parts, &remaining);
+        },
+    );
+    // Days without any parts to run
+    finish_days(&mut day_parts, &remaining);
+
+    match options.format {
+        Format::Text => {}
+        Format::Json => print!("{}", to_json(&reports)),
+        Format::Csv => print!("{}", to_csv(&reports)),
+        Format::Table => print!("{}", to_table(&reports)),
+    }
+    if failed {
+        process::exit(1);
+    }
 }
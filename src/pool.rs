@@ -0,0 +1,81 @@
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+// Runs work on each item using up to jobs threads
+// output is called on the calling thread with each result in the order of the items
+// as soon as that result and all the results before it are ready
+pub fn for_each_ordered<T, R, W, O>(jobs: usize, items: Vec<T>, work: W, mut output: O)
+where
+    T: Send,
+    R: Send,
+    W: Fn(T) -> R + Sync,
+    O: FnMut(usize, R),
+{
+    let count = items.len();
+    if jobs <= 1 || count <= 1 {
+        for (i, item) in items.into_iter().enumerate() {
+            output(i, work(item));
+        }
+        return;
+    }
+
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(count) {
+            let sender = sender.clone();
+            let queue = &queue;
+            let work = &work;
+            scope.spawn(move || loop {
+                let next = queue.lock().expect("Work queue poisoned").next();
+                let Some((i, item)) = next else {
+                    break;
+                };
+                if sender.send((i, work(item))).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending: Vec<Option<R>> = (0..count).map(|_| None).collect();
+        let mut next = 0;
+        for (i, result) in receiver {
+            pending[i] = Some(result);
+            while next < count {
+                match pending[next].take() {
+                    Some(result) => output(next, result),
+                    None => break,
+                }
+                next += 1;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn ordered() {
+        for jobs in [1, 4] {
+            let items: Vec<u64> = (0..20).collect();
+            let mut results = Vec::new();
+            for_each_ordered(
+                jobs,
+                items,
+                |i| {
+                    // Make the early items finish last
+                    thread::sleep(Duration::from_millis(20 - i));
+                    return i * i;
+                },
+                |i, result| results.push((i, result)),
+            );
+            let expected: Vec<(usize, u64)> = (0..20).map(|i| (i as usize, i * i)).collect();
+            assert_eq!(results, expected);
+        }
+    }
+}
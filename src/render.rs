@@ -0,0 +1,353 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::grid::Grid;
+
+// Red, green, blue
+pub type Rgb = [u8; 3];
+
+// An image is a grid of pixels with (0, 0) at the top left
+pub type Image = Grid<Rgb>;
+
+// Simulations that can be rendered call this with an image of each step
+pub type Frames = Box<dyn FnMut(&Image)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        return match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        };
+    }
+
+    pub fn encode(&self, image: &Image) -> Vec<u8> {
+        return match self {
+            ImageFormat::Ppm => to_ppm(image),
+            ImageFormat::Png => to_png(image),
+        };
+    }
+}
+
+// Each cell becomes a scale by scale square so small grids are big enough to see
+pub fn scale(image: &Image, scale: usize) -> Image {
+    return Grid::from_fn(image.width() * scale, image.height() * scale, |x, y| {
+        image[(x / scale, y / scale)]
+    });
+}
+
+// Binary PPM (P6)
+pub fn to_ppm(image: &Image) -> Vec<u8> {
+    let mut bytes = format!("P6\n{} {}\n255\n", image.width(), image.height()).into_bytes();
+    for pixel in image.cells() {
+        bytes.extend_from_slice(pixel);
+    }
+    return bytes;
+}
+
+// 8 bit RGB PNG with every row unfiltered
+pub fn to_png(image: &Image) -> Vec<u8> {
+    let mut raw = Vec::with_capacity((image.width() * 3 + 1) * image.height());
+    for row in image.rows() {
+        raw.push(0);
+        for pixel in row {
+            raw.extend_from_slice(pixel);
+        }
+    }
+    let mut header = Vec::new();
+    header.extend_from_slice(&(image.width() as u32).to_be_bytes());
+    header.extend_from_slice(&(image.height() as u32).to_be_bytes());
+    // Bit depth 8, colour type 2 (RGB), deflate, no filtering method, no interlace
+    header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+    let mut png = b"\x89PNG\r\n\x1a\n".to_vec();
+    png_chunk(&mut png, b"IHDR", &header);
+    png_chunk(&mut png, b"IDAT", &zlib(&raw));
+    png_chunk(&mut png, b"IEND", &[]);
+    return png;
+}
+
+fn png_chunk(png: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    png.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(data);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = u32::MAX;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    return !crc;
+}
+
+pub fn adler32(bytes: &[u8]) -> u32 {
+    let mut a: u32 = 1;
+    let mut b: u32 = 0;
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    return (b << 16) | a;
+}
+
+// Writes bits from the lowest bit of each byte up as deflate needs
+struct BitWriter {
+    bytes: Vec<u8>,
+    bits: u64,
+    count: usize,
+}
+
+impl BitWriter {
+    fn write(&mut self, value: u32, count: usize) {
+        self.bits |= (value as u64) << self.count;
+        self.count += count;
+        while self.count >= 8 {
+            self.bytes.push(self.bits as u8);
+            self.bits >>= 8;
+            self.count -= 8;
+        }
+    }
+
+    // Huffman codes go highest bit first
+    fn write_code(&mut self, code: u32, count: usize) {
+        let reversed = code.reverse_bits() >> (32 - count);
+        self.write(reversed, count);
+    }
+
+    fn finish(mut self) -> Vec<u8> {
+        if self.count > 0 {
+            self.bytes.push(self.bits as u8);
+        }
+        return self.bytes;
+    }
+}
+
+const LENGTH_BASES: [usize; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [usize; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASES: [usize; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DISTANCE_EXTRA: [usize; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+const WINDOW: usize = 32768;
+const MIN_MATCH: usize = 3;
+const MAX_MATCH: usize = 258;
+
+// The fixed Huffman code of a literal, end of block or length symbol
+fn write_symbol(out: &mut BitWriter, symbol: usize) {
+    let symbol = symbol as u32;
+    match symbol {
+        0..=143 => out.write_code(0x30 + symbol, 8),
+        144..=255 => out.write_code(0x190 + symbol - 144, 9),
+        256..=279 => out.write_code(symbol - 256, 7),
+        _ => out.write_code(0xc0 + symbol - 280, 8),
+    }
+}
+
+// A length or distance as its code, the value of the extra bits and the number of extra bits
+fn range_code(value: usize, bases: &[usize], extra: &[usize]) -> (usize, u32, usize) {
+    let mut code = bases.len() - 1;
+    while bases[code] > value {
+        code -= 1;
+    }
+    return (code, (value - bases[code]) as u32, extra[code]);
+}
+
+// A single deflate block with the fixed Huffman codes
+// Repeats are found by remembering where each 3 bytes were last seen, which suits images
+// with runs of the same colour
+pub fn deflate(data: &[u8]) -> Vec<u8> {
+    let mut out = BitWriter {
+        bytes: Vec::new(),
+        bits: 0,
+        count: 0,
+    };
+    // Final block, fixed codes
+    out.write(1, 1);
+    out.write(1, 2);
+    let mut last_seen: Vec<usize> = vec![usize::MAX; 1 << 16];
+    let hash = |i: usize| -> usize {
+        let key = (data[i] as usize) << 16 | (data[i + 1] as usize) << 8 | data[i + 2] as usize;
+        return (key.wrapping_mul(2654435761) >> 8) & 0xffff;
+    };
+    let mut i = 0;
+    while i < data.len() {
+        let mut length = 0;
+        let mut distance = 0;
+        if i + MIN_MATCH <= data.len() {
+            let h = hash(i);
+            let candidate = last_seen[h];
+            last_seen[h] = i;
+            if candidate != usize::MAX && i - candidate <= WINDOW {
+                let max = MAX_MATCH.min(data.len() - i);
+                while length < max && data[candidate + length] == data[i + length] {
+                    length += 1;
+                }
+                distance = i - candidate;
+            }
+        }
+        if length < MIN_MATCH {
+            write_symbol(&mut out, data[i] as usize);
+            i += 1;
+            continue;
+        }
+        let (code, offset, extra) = range_code(length, &LENGTH_BASES, &LENGTH_EXTRA);
+        write_symbol(&mut out, 257 + code);
+        out.write(offset, extra);
+        let (code, offset, extra) = range_code(distance, &DISTANCE_BASES, &DISTANCE_EXTRA);
+        out.write_code(code as u32, 5);
+        out.write(offset, extra);
+        // Remember the positions inside the match for later repeats
+        for j in i + 1..(i + length).min(data.len().saturating_sub(MIN_MATCH - 1)) {
+            last_seen[hash(j)] = j;
+        }
+        i += length;
+    }
+    // End of block
+    write_symbol(&mut out, 256);
+    return out.finish();
+}
+
+// A zlib stream of the deflated data
+pub fn zlib(data: &[u8]) -> Vec<u8> {
+    // Deflate with a 32K window, no dictionary, check bits so the header is a multiple of 31
+    let mut bytes = vec![0x78, 0x01];
+    bytes.extend(deflate(data));
+    bytes.extend_from_slice(&adler32(data).to_be_bytes());
+    return bytes;
+}
+
+// Saves numbered images in dir, frame_000001.png, frame_000002.png ...
+// Only every nth frame is saved, and the last frame is always saved by finish so the end
+// state is kept. A frame that can not be saved stops the saving and finish returns the error
+pub struct FrameFiles {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    every: usize,
+    count: usize,
+    saved: usize,
+    unsaved: Option<Image>,
+    error: Option<io::Error>,
+}
+
+impl FrameFiles {
+    // The directory is made when the first frame is saved
+    pub fn new(dir: &Path, format: ImageFormat, scale: usize, every: usize) -> FrameFiles {
+        return FrameFiles {
+            dir: dir.to_path_buf(),
+            format,
+            scale: scale.max(1),
+            every: every.max(1),
+            count: 0,
+            saved: 0,
+            unsaved: None,
+            error: None,
+        };
+    }
+
+    pub fn add(&mut self, image: &Image) {
+        self.count += 1;
+        if self.error.is_some() {
+            return;
+        }
+        if (self.count - 1) % self.every != 0 {
+            self.unsaved = Some(image.clone());
+            return;
+        }
+        self.unsaved = None;
+        if let Err(err) = self.save(image) {
+            self.error = Some(err);
+        }
+    }
+
+    fn save(&mut self, image: &Image) -> io::Result<()> {
+        if self.saved == 0 {
+            fs::create_dir_all(&self.dir)?;
+        }
+        let filename = format!("frame_{:06}.{}", self.count, self.format.extension());
+        fs::write(
+            self.dir.join(filename),
+            self.format.encode(&scale(image, self.scale)),
+        )?;
+        self.saved += 1;
+        return Ok(());
+    }
+
+    // Returns the number of frames saved
+    pub fn finish(&mut self) -> io::Result<usize> {
+        if let Some(image) = self.unsaved.take() {
+            if self.error.is_none() {
+                if let Err(err) = self.save(&image) {
+                    self.error = Some(err);
+                }
+            }
+        }
+        return match self.error.take() {
+            Some(err) => Err(err),
+            None => Ok(self.saved),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn images() {
+        let mut image = Grid::new(2, 1, [0, 0, 0]);
+        image[(1, 0)] = [255, 128, 1];
+        assert_eq!(to_ppm(&image), b"P6\n2 1\n255\n\0\0\0\xff\x80\x01");
+        let big = scale(&image, 3);
+        assert_eq!((big.width(), big.height()), (6, 3));
+        assert_eq!(big[(3, 2)], [255, 128, 1]);
+
+        let png = to_png(&big);
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[8..16], b"\0\0\0\x0dIHDR");
+        assert_eq!(&png[16..29], &[0, 0, 0, 6, 0, 0, 0, 3, 8, 2, 0, 0, 0]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+
+    #[test]
+    fn compress() {
+        // The fixed code block of an empty input
+        assert_eq!(deflate(&[]), vec![0x03, 0x00]);
+        // "a" then a repeat of length 9 at distance 1 : literal, length 257 + 6, distance 0
+        assert_eq!(deflate(&[b'a'; 10]), vec![0x4b, 0x84, 0x03, 0x00]);
+        let data: Vec<u8> = (0..10000).map(|i| (i % 7 * i % 13) as u8).collect();
+        let stream = zlib(&data);
+        assert!(stream.len() < data.len() / 4);
+        assert_eq!(&stream[stream.len() - 4..], &adler32(&data).to_be_bytes());
+    }
+}
@@ -0,0 +1,310 @@
+use std::time::Duration;
+
+use crate::solution::Answer;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json,
+    Csv,
+    Table,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    Correct,
+    Wrong,
+    Unknown,
+    Recorded,
+    // The part did not produce an answer, the error is reported by the runner
+    Error,
+}
+
+impl Status {
+    pub fn name(&self) -> &'static str {
+        return match self {
+            Status::Correct => "correct",
+            Status::Wrong => "wrong",
+            Status::Unknown => "unknown",
+            Status::Recorded => "recorded",
+            Status::Error => "error",
+        };
+    }
+
+    // Short form for the cells of the input matrix
+    pub fn symbol(&self) -> &'static str {
+        return match self {
+            Status::Correct => "ok",
+            Status::Wrong => "FAIL",
+            Status::Unknown => "?",
+            Status::Recorded => "rec",
+            Status::Error => "ERR",
+        };
+    }
+}
+
+// The result of running one part of a day
+#[derive(Clone, Debug)]
+pub struct PartReport {
+    pub day: u32,
+    pub part: usize,
+    pub answer: Answer,
+    pub status: Status,
+    pub expected: Option<String>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    return json;
+}
+
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n', '\r']) {
+        return format!("\"{}\"", text.replace('"', "\"\""));
+    }
+    return text.to_string();
+}
+
+pub fn format_ms(time: Duration) -> String {
+    return format!("{:.3}ms", time.as_secs_f64() * 1000.0);
+}
+
+pub fn to_json(reports: &[PartReport]) -> String {
+    let mut lines = Vec::new();
+    for r in reports {
+        let answer = match &r.answer {
+            Answer::None => "null".to_string(),
+            Answer::Number(number) => number.to_string(),
+            answer => json_string(&answer.to_string()),
+        };
+        let expected = match &r.expected {
+            Some(expected) => json_string(expected),
+            None => "null".to_string(),
+        };
+        lines.push(format!(
+            "  {{\"day\": {}, \"part\": {}, \"answer\": {answer}, \"status\": \"{}\", \"expected\": {expected}, \"parse_us\": {}, \"solve_us\": {}}}",
+            r.day,
+            r.part,
+            r.status.name(),
+            r.parse_time.as_micros(),
+            r.solve_time.as_micros()
+        ));
+    }
+    if lines.is_empty() {
+        return "[]\n".to_string();
+    }
+    return format!("[\n{}\n]\n", lines.join(",\n"));
+}
+
+pub fn to_csv(reports: &[PartReport]) -> String {
+    let mut csv = String::from("day,part,answer,status,expected,parse_us,solve_us\n");
+    for r in reports {
+        let expected = r.expected.as_deref().unwrap_or("");
+        csv += &format!(
+            "{},{},{},{},{},{},{}\n",
+            r.day,
+            r.part,
+            csv_field(&r.answer.to_string()),
+            r.status.name(),
+            csv_field(expected),
+            r.parse_time.as_micros(),
+            r.solve_time.as_micros()
+        );
+    }
+    return csv;
+}
+
+// Draws a table with a header row, right_aligned gives the alignment of each column
+pub fn draw_table(header: &[&str], rows: &[Vec<String>], right_aligned: &[bool]) -> String {
+    let columns = header.len();
+    let mut widths: Vec<usize> = header.iter().map(|h| h.len()).collect();
+    for row in rows {
+        for c in 0..columns {
+            widths[c] = widths[c].max(row[c].len());
+        }
+    }
+    let format_row = |row: &[&str]| -> String {
+        let mut cells = Vec::new();
+        for c in 0..columns {
+            if right_aligned[c] {
+                cells.push(format!("{:>w$}", row[c], w = widths[c]));
+            } else {
+                cells.push(format!("{:<w$}", row[c], w = widths[c]));
+            }
+        }
+        return format!("| {} |\n", cells.join(" | "));
+    };
+    let separator = widths
+        .iter()
+        .map(|w| "-".repeat(w + 2))
+        .collect::<Vec<String>>()
+        .join("+");
+
+    let mut table = format!("+{separator}+\n");
+    table += &format_row(header);
+    table += &format!("+{separator}+\n");
+    for row in rows {
+        let cells: Vec<&str> = row.iter().map(|s| s.as_str()).collect();
+        table += &format_row(&cells);
+    }
+    table += &format!("+{separator}+\n");
+    return table;
+}
+
+// Multi-line answers continue on the following rows
+pub fn to_table(reports: &[PartReport]) -> String {
+    let header = ["Day", "Part", "Answer", "Status", "Parse", "Solve"];
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    for r in reports {
+        let answer = r.answer.to_string();
+        let mut answer_lines = answer.lines();
+        rows.push(vec![
+            format!("{:02}", r.day),
+            r.part.to_string(),
+            answer_lines.next().unwrap_or("").to_string(),
+            r.status.name().to_string(),
+            format_ms(r.parse_time),
+            format_ms(r.solve_time),
+        ]);
+        for line in answer_lines {
+            let mut row = vec![String::new(); header.len()];
+            row[2] = line.to_string();
+            rows.push(row);
+        }
+    }
+    // Timings are right aligned
+    return draw_table(&header, &rows, &[false, false, false, false, true, true]);
+}
+
+// One row per day and one column per input name
+// A cell has the status of each part run against the input, None when the day does not
+// have the input
+pub fn to_matrix(inputs: &[String], rows: &[(u32, Vec<Option<Vec<Status>>>)]) -> String {
+    let mut header = vec!["Day"];
+    for input in inputs {
+        header.push(input);
+    }
+    let mut table_rows: Vec<Vec<String>> = Vec::new();
+    for (day, cells) in rows {
+        let mut row = vec![format!("{day:02}")];
+        for cell in cells {
+            row.push(match cell {
+                Some(statuses) => statuses
+                    .iter()
+                    .map(|s| s.symbol())
+                    .collect::<Vec<&str>>()
+                    .join(" "),
+                None => "-".to_string(),
+            });
+        }
+        table_rows.push(row);
+    }
+    let mut table = draw_table(&header, &table_rows, &vec![false; header.len()]);
+    table += "ok correct, FAIL wrong, ? no expected answer, ERR error, - no input\n";
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reports() -> Vec<PartReport> {
+        return vec![
+            PartReport {
+                day: 1,
+                part: 1,
+                answer: Answer::Number(24000),
+                status: Status::Correct,
+                expected: Some("24000".to_string()),
+                parse_time: Duration::from_micros(12),
+                solve_time: Duration::from_micros(3),
+            },
+            PartReport {
+                day: 10,
+                part: 2,
+                answer: Answer::Text("#.\n.\"#".to_string()),
+                status: Status::Unknown,
+                expected: None,
+                parse_time: Duration::from_micros(1500),
+                solve_time: Duration::from_micros(20),
+            },
+        ];
+    }
+
+    #[test]
+    fn json() {
+        let expected = concat!(
+            "[\n",
+            "  {\"day\": 1, \"part\": 1, \"answer\": 24000, \"status\": \"correct\", \"expected\": \"24000\", \"parse_us\": 12, \"solve_us\": 3},\n",
+            "  {\"day\": 10, \"part\": 2, \"answer\": \"#.\\n.\\\"#\", \"status\": \"unknown\", \"expected\": null, \"parse_us\": 1500, \"solve_us\": 20}\n",
+            "]\n"
+        );
+        assert_eq!(to_json(&reports()), expected);
+        assert_eq!(to_json(&[]), "[]\n");
+    }
+
+    #[test]
+    fn csv() {
+        let expected = concat!(
+            "day,part,answer,status,expected,parse_us,solve_us\n",
+            "1,1,24000,correct,24000,12,3\n",
+            "10,2,\"#.\n.\"\"#\",unknown,,1500,20\n"
+        );
+        assert_eq!(to_csv(&reports()), expected);
+    }
+
+    #[test]
+    fn table() {
+        let expected = concat!(
+            "+-----+------+--------+---------+---------+---------+\n",
+            "| Day | Part | Answer | Status  |   Parse |   Solve |\n",
+            "+-----+------+--------+---------+---------+---------+\n",
+            "| 01  | 1    | 24000  | correct | 0.012ms | 0.003ms |\n",
+            "| 10  | 2    | #.     | unknown | 1.500ms | 0.020ms |\n",
+            "|     |      | .\"#    |         |         |         |\n",
+            "+-----+------+--------+---------+---------+---------+\n",
+        );
+        assert_eq!(to_table(&reports()), expected);
+    }
+
+    #[test]
+    fn matrix() {
+        let inputs = vec!["input".to_string(), "example1".to_string()];
+        let rows = vec![
+            (
+                1,
+                vec![
+                    Some(vec![Status::Correct, Status::Correct]),
+                    Some(vec![Status::Correct, Status::Wrong]),
+                ],
+            ),
+            (25, vec![Some(vec![Status::Unknown]), None]),
+            (3, vec![Some(vec![Status::Error]), Some(Vec::new())]),
+        ];
+        let expected = concat!(
+            "+-----+-------+----------+\n",
+            "| Day | input | example1 |\n",
+            "+-----+-------+----------+\n",
+            "| 01  | ok ok | ok FAIL  |\n",
+            "| 25  | ?     | -        |\n",
+            "| 03  | ERR   |          |\n",
+            "+-----+-------+----------+\n",
+            "ok correct, FAIL wrong, ? no expected answer, ERR error, - no input\n",
+        );
+        assert_eq!(to_matrix(&inputs, &rows), expected);
+    }
+}
@@ -0,0 +1,513 @@
+use std::any::Any;
+use std::cell::Cell;
+use std::fs;
+use std::io::{self, BufRead, BufReader};
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use crate::answers::{answers_file, Answers, Verdict};
+use crate::cli::Options;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::pool::for_each_ordered;
+use crate::report::{PartReport, Status};
+use crate::solution::{Answer, Puzzle, Solution};
+
+// A day's input and recorded answers, loaded before any of its parts are run
+pub struct DayInput {
+    pub input: Input,
+    // None when the input is read from stdin
+    pub answers_file: Option<String>,
+    pub answers: Answers,
+    pub load_time: Duration,
+}
+
+// The answer and timings of one part of a day
+pub struct PartRun {
+    pub part: usize,
+    pub answer: Result<Answer>,
+    pub parse_time: Duration,
+    pub solve_time: Duration,
+}
+
+// The outcome of running the selected parts of a day
+pub struct DayRun {
+    pub reports: Vec<PartReport>,
+    // Problems loading the input, parsing, solving, checking and saving the answers
+    pub errors: Vec<Error>,
+    pub recorded: Option<String>,
+    // Time spent loading the input and on the parse and solve of each part
+    pub elapsed: Duration,
+}
+
+impl DayRun {
+    pub fn ok(&self) -> bool {
+        return self.errors.is_empty();
+    }
+
+    // Both parts parse the same input so a parse error is only reported once
+    fn push_error(&mut self, error: Error) {
+        let message = error.to_string();
+        if !self.errors.iter().any(|e| e.to_string() == message) {
+            self.errors.push(error);
+        }
+    }
+}
+
+// A day can have several named inputs next to its input file, each with its own answers
+//   data/2022/day01/input.txt      the default input named "input" with answers.txt
+//   data/2022/day01/alice.txt      the input named "alice" with alice.answers.txt
+//   data/2022/day01/example1.txt   the input named "example1" with example1.answers.txt
+pub const DEFAULT_INPUT: &str = "input";
+
+pub fn named_input_file(puzzle: &Puzzle, name: &str) -> String {
+    let path = Path::new(puzzle.input_file).with_file_name(format!("{name}.txt"));
+    return path.to_string_lossy().to_string();
+}
+
+// The names of the inputs of a day, the default input first then in name order
+pub fn input_names(puzzle: &Puzzle) -> io::Result<Vec<String>> {
+    let dir = Path::new(puzzle.input_file)
+        .parent()
+        .unwrap_or(Path::new("."));
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+    let mut names = Vec::new();
+    for entry in entries {
+        let file_name = entry?.file_name().to_string_lossy().to_string();
+        if let Some(name) = file_name.strip_suffix(".txt") {
+            if name != "answers" && !name.ends_with(".answers") {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names.sort_by_key(|name| (name != DEFAULT_INPUT, name.clone()));
+    return Ok(names);
+}
+
+pub fn input_file(puzzle: &Puzzle, options: &Options) -> String {
+    if let Some(input) = &options.input {
+        return input.clone();
+    }
+    return named_input_file(puzzle, options.name.as_deref().unwrap_or(DEFAULT_INPUT));
+}
+
+pub fn load_input(puzzle: &Puzzle, options: &Options) -> io::Result<Input> {
+    if options.input_is_stdin() {
+        return Input::read(io::stdin().lock());
+    }
+    return Input::load(&input_file(puzzle, options));
+}
+
+// Loads an input file and its answers
+pub fn load_file(filename: &str) -> Result<DayInput> {
+    let now = Instant::now();
+    let input = Input::load(filename).map_err(|err| Error::io(filename, err))?;
+    let answers_filename = answers_file(filename);
+    let answers =
+        Answers::load(&answers_filename).map_err(|err| Error::io(&answers_filename, err))?;
+    return Ok(DayInput {
+        input,
+        answers_file: Some(answers_filename),
+        answers,
+        load_time: now.elapsed(),
+    });
+}
+
+pub fn load_day(puzzle: &Puzzle, options: &Options) -> Result<DayInput> {
+    if !options.input_is_stdin() {
+        return load_file(&input_file(puzzle, options));
+    }
+    // Input read from stdin does not have an answers file
+    let now = Instant::now();
+    let input = Input::read(io::stdin().lock()).map_err(|err| Error::io("-", err))?;
+    return Ok(DayInput {
+        input,
+        answers_file: None,
+        answers: Answers::new(),
+        load_time: now.elapsed(),
+    });
+}
+
+// The answers of a day whose input is streamed, the input itself is not loaded
+pub fn load_answers(puzzle: &Puzzle, options: &Options) -> Result<DayInput> {
+    let now = Instant::now();
+    let mut day_input = DayInput {
+        input: Input::default(),
+        answers_file: None,
+        answers: Answers::new(),
+        load_time: Duration::ZERO,
+    };
+    // Input read from stdin does not have an answers file
+    if !options.input_is_stdin() {
+        let filename = answers_file(&input_file(puzzle, options));
+        day_input.answers = Answers::load(&filename).map_err(|err| Error::io(&filename, err))?;
+        day_input.answers_file = Some(filename);
+    }
+    day_input.load_time = now.elapsed();
+    return Ok(day_input);
+}
+
+thread_local! {
+    // Panics on this thread are not printed
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+// Puts the quiet setting of the thread back when dropped, also when f panics
+struct Quiet(bool);
+
+impl Drop for Quiet {
+    fn drop(&mut self) {
+        QUIET.with(|quiet| quiet.set(self.0));
+    }
+}
+
+// Runs f without printing its panics, for panics that are caught and reported in another way
+// Panics on other threads are printed by the hook as usual
+pub fn quietly<T>(f: impl FnOnce() -> T) -> T {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.with(|quiet| quiet.get()) {
+                hook(info);
+            }
+        }));
+    });
+    let _quiet = Quiet(QUIET.with(|quiet| quiet.replace(true)));
+    return f();
+}
+
+pub fn panic_message(payload: &Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return message.to_string();
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return "Unknown panic".to_string();
+}
+
+// A new instance of the day for a part with the day options set, name=value from --set
+fn new_solution(
+    puzzle: &Puzzle,
+    settings: &[(String, String)],
+    part1: bool,
+) -> Result<Box<dyn Solution>> {
+    let mut solution = (puzzle.instance)(part1);
+    for (name, value) in settings {
+        if !solution.set_option(name, value)? {
+            return Err(Error::input(&format!("There is no option '{name}'")));
+        }
+    }
+    return Ok(solution);
+}
+
+pub fn run_part(puzzle: &Puzzle, input: &Input, part: usize) -> PartRun {
+    return run_part_with(puzzle, &[], input, part);
+}
+
+// A new instance is made for each part because some parts modify the parsed state
+// A panic in the solution is caught and returned as an error so the other days still run
+pub fn run_part_with(
+    puzzle: &Puzzle,
+    settings: &[(String, String)],
+    input: &Input,
+    part: usize,
+) -> PartRun {
+    let part1 = part == 1;
+    let mut parse_time = Duration::ZERO;
+    let mut solve_time = Duration::ZERO;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> Result<Answer> {
+        let parse_start = Instant::now();
+        let mut solution = new_solution(puzzle, settings, part1)?;
+        let parsed = solution.parse(input);
+        parse_time = parse_start.elapsed();
+        parsed?;
+        let solve_start = Instant::now();
+        let answer = if part1 {
+            solution.part1()
+        } else {
+            solution.part2()
+        };
+        solve_time = solve_start.elapsed();
+        return answer;
+    }));
+    return PartRun {
+        part,
+        answer: part_answer(puzzle, part, result),
+        parse_time,
+        solve_time,
+    };
+}
+
+// Runs a part of a day that reads its input a line at a time from reader instead of loading it
+// name is the input file for the errors, nothing is parsed first so it is all solve time
+pub fn stream_part(
+    puzzle: &Puzzle,
+    settings: &[(String, String)],
+    name: &str,
+    reader: &mut dyn BufRead,
+    part: usize,
+) -> PartRun {
+    let mut solve_time = Duration::ZERO;
+    let result = panic::catch_unwind(AssertUnwindSafe(|| -> Result<Answer> {
+        let solve_start = Instant::now();
+        let mut solution = new_solution(puzzle, settings, part == 1)?;
+        let answer = solution.solve_stream(name, reader);
+        solve_time = solve_start.elapsed();
+        return match answer {
+            Some(answer) => answer,
+            None => Err(Error::input(
+                "Can not read the input a line at a time, run without --stream",
+            )),
+        };
+    }));
+    return PartRun {
+        part,
+        answer: part_answer(puzzle, part, result),
+        parse_time: Duration::ZERO,
+        solve_time,
+    };
+}
+
+// Streams the input of a day to a part, the input file is opened again for each part
+pub fn stream_input(puzzle: &Puzzle, options: &Options, part: usize) -> PartRun {
+    if options.input_is_stdin() {
+        let stdin = &mut io::stdin().lock();
+        return stream_part(puzzle, &options.settings, "-", stdin, part);
+    }
+    let filename = input_file(puzzle, options);
+    return match fs::File::open(&filename) {
+        Ok(file) => {
+            let reader = &mut BufReader::new(file);
+            stream_part(puzzle, &options.settings, &filename, reader, part)
+        }
+        Err(err) => PartRun {
+            part,
+            answer: Err(Error::io(&filename, err)),
+            parse_time: Duration::ZERO,
+            solve_time: Duration::ZERO,
+        },
+    };
+}
+
+// The day is set on the errors of the solution and a panic is turned into an error
+fn part_answer(
+    puzzle: &Puzzle,
+    part: usize,
+    result: std::thread::Result<Result<Answer>>,
+) -> Result<Answer> {
+    return match result {
+        Ok(answer) => answer.map_err(|err| err.in_day(puzzle.day)),
+        Err(payload) => Err(Error::Panic {
+            day: puzzle.day,
+            part,
+            message: panic_message(&payload),
+        }),
+    };
+}
+
+// Checks the answers of the parts against the recorded answers
+// or records them when options.record is set
+pub fn finish_day(
+    puzzle: &Puzzle,
+    options: &Options,
+    input: &Result<DayInput>,
+    parts: Vec<PartRun>,
+) -> DayRun {
+    let mut run = DayRun {
+        reports: Vec::new(),
+        errors: Vec::new(),
+        recorded: None,
+        elapsed: Duration::ZERO,
+    };
+    let input = match input {
+        Ok(input) => input,
+        Err(err) => {
+            run.errors.push(err.clone());
+            return run;
+        }
+    };
+    run.elapsed = input.load_time;
+    let mut answers = input.answers.clone();
+    for p in parts {
+        run.elapsed += p.parse_time + p.solve_time;
+        let expected = answers.get(p.part).map(|e| e.to_string());
+        let (answer, status) = match p.answer {
+            Ok(Answer::None) => continue,
+            // Answers with day options set are not the answers of the puzzle
+            Ok(answer) if !options.settings.is_empty() => (answer, Status::Unknown),
+            Ok(answer) => {
+                let status = if options.record {
+                    answers.set(p.part, &answer);
+                    Status::Recorded
+                } else {
+                    match answers.check(p.part, &answer) {
+                        Verdict::Correct => Status::Correct,
+                        Verdict::Wrong(expected) => {
+                            run.push_error(Error::WrongAnswer {
+                                day: puzzle.day,
+                                part: p.part,
+                                answer: answer.to_string(),
+                                expected,
+                            });
+                            Status::Wrong
+                        }
+                        Verdict::Unknown => Status::Unknown,
+                    }
+                };
+                (answer, status)
+            }
+            Err(err) => {
+                run.push_error(err);
+                (Answer::None, Status::Error)
+            }
+        };
+        run.reports.push(PartReport {
+            day: puzzle.day,
+            part: p.part,
+            answer,
+            status,
+            expected,
+            parse_time: p.parse_time,
+            solve_time: p.solve_time,
+        });
+    }
+    if options.record {
+        if let Some(filename) = &input.answers_file {
+            match answers.save(filename) {
+                Ok(()) => run.recorded = Some(filename.clone()),
+                Err(err) => run.push_error(Error::io(filename, err)),
+            }
+        }
+    }
+    return run;
+}
+
+// The statuses of every day against every one of its inputs, see run_matrix
+pub struct MatrixRun {
+    // The input names of the columns, the default input first
+    pub names: Vec<String>,
+    // A cell per input name, None when the day has no input with that name
+    pub rows: Vec<(u32, Vec<Option<Vec<Status>>>)>,
+    // Each error with the input file or day it belongs to
+    pub errors: Vec<(String, Error)>,
+}
+
+impl MatrixRun {
+    pub fn ok(&self) -> bool {
+        return self.errors.is_empty();
+    }
+}
+
+// Runs the selected parts of the days against each of their inputs on up to options.jobs threads
+pub fn run_matrix(puzzles: &[&Puzzle], options: &Options) -> MatrixRun {
+    let mut matrix = MatrixRun {
+        names: Vec::new(),
+        rows: Vec::new(),
+        errors: Vec::new(),
+    };
+    let mut runs: Vec<(usize, String, Result<DayInput>)> = Vec::new();
+    for (d, &puzzle) in puzzles.iter().enumerate() {
+        let day_names = match input_names(puzzle) {
+            Ok(day_names) => day_names,
+            Err(err) => {
+                matrix
+                    .errors
+                    .push((puzzle.name(), Error::io(puzzle.input_file, err)));
+                continue;
+            }
+        };
+        for name in day_names {
+            if !matrix.names.contains(&name) {
+                matrix.names.push(name.clone());
+            }
+            let input = load_file(&named_input_file(puzzle, &name));
+            runs.push((d, name, input));
+        }
+    }
+    // The default input first then the other names in order
+    matrix
+        .names
+        .sort_by_key(|name| (name != DEFAULT_INPUT, name.clone()));
+
+    let mut tasks = Vec::new();
+    for (r, (_, _, input)) in runs.iter().enumerate() {
+        if input.is_err() {
+            continue;
+        }
+        for part in 1..=2 {
+            if options.selects_part(part) {
+                tasks.push((r, part));
+            }
+        }
+    }
+    let mut run_parts: Vec<Vec<PartRun>> = runs.iter().map(|_| Vec::new()).collect();
+    for_each_ordered(
+        options.jobs,
+        tasks.clone(),
+        |(r, part)| {
+            let (d, _, input) = &runs[r];
+            let input = match input {
+                Ok(input) => &input.input,
+                Err(_) => unreachable!("Tasks are only made for loaded inputs"),
+            };
+            return run_part(puzzles[*d], input, part);
+        },
+        |i, part_run| run_parts[tasks[i].0].push(part_run),
+    );
+
+    let columns = matrix.names.len();
+    matrix.rows = puzzles
+        .iter()
+        .map(|puzzle| (puzzle.day, vec![None; columns]))
+        .collect();
+    for (r, (d, name, input)) in runs.iter().enumerate() {
+        let puzzle = puzzles[*d];
+        let run = finish_day(puzzle, options, input, std::mem::take(&mut run_parts[r]));
+        for error in run.errors {
+            matrix.errors.push((named_input_file(puzzle, name), error));
+        }
+        let mut statuses: Vec<Status> = run.reports.iter().map(|r| r.status).collect();
+        if input.is_err() {
+            statuses.push(Status::Error);
+        }
+        let column = matrix.names.iter().position(|n| n == name).unwrap_or(0);
+        matrix.rows[*d].1[column] = Some(statuses);
+    }
+    return matrix;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::parse_args;
+    use crate::util::str_array_to_string_array;
+    use crate::year2022::day01;
+
+    #[test]
+    fn quiet_panics() {
+        let result = panic::catch_unwind(|| quietly(|| panic!("Not printed")));
+        assert_eq!(panic_message(&result.unwrap_err()), "Not printed");
+        // The thread is not quiet after the panic
+        assert!(!QUIET.with(|quiet| quiet.get()));
+        assert_eq!(quietly(|| quietly(|| 1) + 1), 2);
+        assert!(!QUIET.with(|quiet| quiet.get()));
+    }
+
+    #[test]
+    fn matrix() {
+        let options = parse_args(&str_array_to_string_array(vec!["matrix", "-j", "2"])).unwrap();
+        let matrix = run_matrix(&[&day01::PUZZLE], &options);
+        assert!(matrix.ok());
+        assert_eq!(matrix.names, vec!["input", "example1"]);
+        let correct = Some(vec![Status::Correct, Status::Correct]);
+        assert_eq!(matrix.rows, vec![(1, vec![correct.clone(), correct])]);
+    }
+}
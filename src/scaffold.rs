@@ -0,0 +1,216 @@
+use std::fs;
+use std::path::Path;
+
+// The template for a new day : __YEAR__ is the year, __DAY__ the day number
+// and __NN__ the two digit day
+static TEMPLATE: &str = include_str!("../templates/day.rs");
+
+pub fn module_name(day: u32) -> String {
+    return format!("day{day:02}");
+}
+
+pub fn render(year: u32, day: u32) -> String {
+    return TEMPLATE
+        .replace("__YEAR__", &year.to_string())
+        .replace("__DAY__", &day.to_string())
+        .replace("__NN__", &format!("{day:02}"));
+}
+
+// The src/yearYYYY/mod.rs of a new year with its first day
+pub fn render_year(year: u32, day: u32) -> String {
+    let module = module_name(day);
+    return format!(
+        "use crate::solution::{{Puzzle, Year}};
+
+pub mod {module};
+
+pub static YEAR: Year = Year {{
+    year: {year},
+    puzzles: &PUZZLES,
+}};
+
+// Every day in the calendar, in day order
+pub static PUZZLES: [&Puzzle; 1] = [&{module}::PUZZLE];
+"
+    );
+}
+
+// Inserts line into the run of lines starting with prefix, keeping them sorted
+fn insert_sorted(text: &str, prefix: &str, line: &str) -> Result<String, String> {
+    let lines: Vec<&str> = text.lines().collect();
+    let Some(first) = lines.iter().position(|l| l.starts_with(prefix)) else {
+        return Err(format!("No lines starting with '{prefix}'"));
+    };
+    let mut last = first;
+    while last + 1 < lines.len() && lines[last + 1].starts_with(prefix) {
+        last += 1;
+    }
+    if lines[first..=last].contains(&line) {
+        return Err(format!("'{line}' is already there"));
+    }
+    let mut at = first;
+    while at <= last && lines[at] < line {
+        at += 1;
+    }
+    let mut result: Vec<&str> = lines[..at].to_vec();
+    result.push(line);
+    result.extend(&lines[at..]);
+    return Ok(result.join("\n") + "\n");
+}
+
+// Adds entry to the array that starts with header e.g. "pub static PUZZLES: [&Puzzle; "
+// The entries are kept sorted and the array is written on one line if it was on one line
+fn add_to_array(text: &str, header: &str, entry: &str) -> Result<String, String> {
+    let missing = || format!("No '{header}' array");
+    let start = text.find(header).ok_or_else(missing)?;
+    let open = start + text[start..].find("= [").ok_or_else(missing)? + 3;
+    let close = open + text[open..].find("];").ok_or_else(missing)?;
+    let mut entries: Vec<&str> = text[open..close]
+        .split(',')
+        .map(|e| e.trim())
+        .filter(|e| !e.is_empty())
+        .collect();
+    if entries.contains(&entry) {
+        return Err(format!("'{entry}' is already there"));
+    }
+    entries.push(entry);
+    entries.sort();
+    let list = if text[open..close].contains('\n') {
+        let lines: Vec<String> = entries.iter().map(|e| format!("    {e},\n")).collect();
+        format!("\n{}", lines.concat())
+    } else {
+        entries.join(", ")
+    };
+    return Ok(format!(
+        "{}{header}{}] = [{list}{}",
+        &text[..start],
+        entries.len(),
+        &text[close..]
+    ));
+}
+
+// Adds "pub mod dayNN;" and the PUZZLES entry to the mod.rs of a year
+pub fn register_day(year_rs: &str, day: u32) -> Result<String, String> {
+    let module = module_name(day);
+    let text = insert_sorted(year_rs, "pub mod day", &format!("pub mod {module};"))?;
+    return add_to_array(
+        &text,
+        "pub static PUZZLES: [&Puzzle; ",
+        &format!("&{module}::PUZZLE"),
+    );
+}
+
+// Adds "pub mod yearYYYY;" to lib.rs
+pub fn register_year_module(lib_rs: &str, year: u32) -> Result<String, String> {
+    return insert_sorted(lib_rs, "pub mod ", &format!("pub mod year{year};"));
+}
+
+// Adds the import and the YEARS entry to solution.rs
+pub fn register_year(solution_rs: &str, year: u32) -> Result<String, String> {
+    let text = insert_sorted(
+        solution_rs,
+        "use crate::year",
+        &format!("use crate::year{year};"),
+    )?;
+    return add_to_array(
+        &text,
+        "pub static YEARS: [&Year; ",
+        &format!("&year{year}::YEAR"),
+    );
+}
+
+// Writes src/yearYYYY/dayNN.rs, makes data/YYYY/dayNN and registers the day in its year
+// A new year is registered in lib.rs and solution.rs
+// Returns the files made or changed, nothing is changed if the day already exists
+pub fn create_day(year: u32, day: u32) -> Result<Vec<String>, String> {
+    let module = module_name(day);
+    let year_dir = format!("src/year{year}");
+    let source = format!("{year_dir}/{module}.rs");
+    if Path::new(&source).exists() {
+        return Err(format!("'{source}' already exists"));
+    }
+    let year_rs = format!("{year_dir}/mod.rs");
+    let mut changes: Vec<(String, String)> = Vec::new();
+    if Path::new(&year_rs).exists() {
+        changes.push((year_rs.clone(), register_day(&read(&year_rs)?, day)?));
+    } else {
+        changes.push((year_rs.clone(), render_year(year, day)));
+        let lib_rs = register_year_module(&read("src/lib.rs")?, year)?;
+        changes.push(("src/lib.rs".to_string(), lib_rs));
+        let solution_rs = register_year(&read("src/solution.rs")?, year)?;
+        changes.push(("src/solution.rs".to_string(), solution_rs));
+    }
+
+    let data = format!("data/{year}/{module}");
+    for dir in [&year_dir, &data] {
+        fs::create_dir_all(dir).map_err(|err| format!("Could not make '{dir}' {err}"))?;
+    }
+    write(&source, &render(year, day))?;
+    let mut files = vec![source, data + "/"];
+    for (filename, text) in changes {
+        write(&filename, &text)?;
+        files.push(filename);
+    }
+    return Ok(files);
+}
+
+fn read(filename: &str) -> Result<String, String> {
+    return fs::read_to_string(filename)
+        .map_err(|err| format!("Could not read '{filename}' {err}"));
+}
+
+fn write(filename: &str, text: &str) -> Result<(), String> {
+    return fs::write(filename, text).map_err(|err| format!("Could not write '{filename}' {err}"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn template() {
+        let text = render(2023, 7);
+        assert!(text.contains("static INPUT_FILE: &str = \"data/2023/day07/input.txt\";"));
+        assert!(text.contains("    year: 2023,\n    day: 7,\n"));
+        assert!(!text.contains("__"));
+    }
+
+    #[test]
+    fn register() {
+        let year_rs = render_year(2023, 3);
+        assert!(year_rs.contains("pub static PUZZLES: [&Puzzle; 1] = [&day03::PUZZLE];"));
+        let year_rs = register_day(&year_rs, 1).unwrap();
+        assert!(year_rs.contains("pub mod day01;\npub mod day03;\n"));
+        assert!(year_rs
+            .contains("pub static PUZZLES: [&Puzzle; 2] = [&day01::PUZZLE, &day03::PUZZLE];"));
+        assert!(register_day(&year_rs, 3).is_err());
+
+        let year_rs = "pub mod day01;\n\
+            \n\
+            pub static PUZZLES: [&Puzzle; 1] = [\n    &day01::PUZZLE,\n];\n";
+        assert_eq!(
+            register_day(year_rs, 2),
+            Ok("pub mod day01;\npub mod day02;\n\
+                \n\
+                pub static PUZZLES: [&Puzzle; 2] = [\n    &day01::PUZZLE,\n    &day02::PUZZLE,\n];\n"
+                .to_string())
+        );
+
+        let lib_rs = "pub mod cli;\npub mod util;\npub mod year2022;\n";
+        assert_eq!(
+            register_year_module(lib_rs, 2023),
+            Ok("pub mod cli;\npub mod util;\npub mod year2022;\npub mod year2023;\n".to_string())
+        );
+
+        let solution_rs = "use crate::year2022;\n\
+            \n\
+            pub static YEARS: [&Year; 1] = [&year2022::YEAR];\n";
+        assert_eq!(
+            register_year(solution_rs, 2021),
+            Ok("use crate::year2021;\nuse crate::year2022;\n\
+                \n\
+                pub static YEARS: [&Year; 2] = [&year2021::YEAR, &year2022::YEAR];\n"
+                .to_string())
+        );
+    }
+}
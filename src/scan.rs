@@ -0,0 +1,230 @@
+use std::str::FromStr;
+
+use crate::error::{parse_number, Error, Result};
+
+// scanf style matching of an input line against a template where {} is a field
+//   scan(i, line, "Sensor at x={}, y={}: closest beacon is at x={}, y={}")
+// The text between fields has to match, where spaces match at least as many spaces
+// A field runs to the next text of the template or to the end of the line, fields can not be
+// empty and must be separated by text
+#[derive(Debug)]
+pub struct Fields<'a> {
+    line_index: usize,
+    line: &'a str,
+    values: Vec<&'a str>,
+}
+
+impl<'a> Fields<'a> {
+    pub fn str(&self, index: usize) -> &'a str {
+        return self.values[index];
+    }
+
+    pub fn number<T: FromStr>(&self, index: usize) -> Result<T> {
+        return parse_number(self.line_index, self.line, self.values[index]);
+    }
+
+    // Every number in the field e.g. the "79, 98" in "Starting items: 79, 98"
+    pub fn numbers<T: FromStr>(&self, index: usize) -> Result<Vec<T>> {
+        return numbers_in(self.line_index, self.line, self.values[index]);
+    }
+}
+
+pub fn scan<'a>(line_index: usize, line: &'a str, template: &str) -> Result<Fields<'a>> {
+    let texts: Vec<&str> = template.split("{}").collect();
+    let mut fields = Fields {
+        line_index,
+        line,
+        values: Vec::new(),
+    };
+    let expected = |at: usize, text: &str| -> Error {
+        return Error::parse(line_index, at + 1, &format!("Expected '{text}'"));
+    };
+    // The leading text matches from the start of the line
+    let mut pos = match match_at(line, 0, texts[0]) {
+        Ok(end) => end,
+        Err((at, t)) => return Err(expected(at, &texts[0][t..])),
+    };
+    for t in 1..texts.len() {
+        let text = texts[t];
+        if pos >= line.len() {
+            return Err(Error::parse(line_index, pos + 1, "Missing field"));
+        }
+        // The field has at least one character
+        let mut end = pos + line[pos..].chars().next().map_or(1, |c| c.len_utf8());
+        let mut text_end = line.len();
+        if text.is_empty() {
+            end = line.len();
+        } else {
+            loop {
+                if end >= line.len() {
+                    return Err(expected(line.len(), text));
+                }
+                if let Ok(match_end) = match_at(line, end, text) {
+                    text_end = match_end;
+                    break;
+                }
+                end += 1;
+                while !line.is_char_boundary(end) {
+                    end += 1;
+                }
+            }
+        }
+        fields.values.push(&line[pos..end]);
+        pos = text_end;
+    }
+    if pos < line.len() {
+        return Err(Error::parse(
+            line_index,
+            pos + 1,
+            &format!("Unexpected '{}'", &line[pos..]),
+        ));
+    }
+    return Ok(fields);
+}
+
+// Matches text at pos in line, a run of spaces in text matches at least as many spaces
+// Returns the end of the match in line, or where line and text stopped matching
+fn match_at(line: &str, pos: usize, text: &str) -> std::result::Result<usize, (usize, usize)> {
+    let line = line.as_bytes();
+    let text = text.as_bytes();
+    let mut at = pos;
+    for t in 0..text.len() {
+        if at >= line.len() || line[at] != text[t] {
+            return Err((at, t));
+        }
+        at += 1;
+        let run_end = text[t] == b' ' && text.get(t + 1) != Some(&b' ');
+        if run_end {
+            while at < line.len() && line[at] == b' ' {
+                at += 1;
+            }
+        }
+    }
+    return Ok(at);
+}
+
+// The integers in text, a '-' is a sign when it is not straight after a digit
+// so "x=-2" is -2 but "2-4" is 2 and 4
+fn number_tokens(text: &str) -> Vec<&str> {
+    let bytes = text.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        let signed = bytes[i] == b'-'
+            && i + 1 < bytes.len()
+            && bytes[i + 1].is_ascii_digit()
+            && (i == 0 || !bytes[i - 1].is_ascii_digit());
+        if !signed && !bytes[i].is_ascii_digit() {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        i += 1;
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        tokens.push(&text[start..i]);
+    }
+    return tokens;
+}
+
+// text must be a slice of line
+fn numbers_in<T: FromStr>(line_index: usize, line: &str, text: &str) -> Result<Vec<T>> {
+    let mut values = Vec::new();
+    for token in number_tokens(text) {
+        values.push(parse_number(line_index, line, token)?);
+    }
+    return Ok(values);
+}
+
+// Every integer in a line, ignoring the text around them
+pub fn numbers<T: FromStr>(line_index: usize, line: &str) -> Result<Vec<T>> {
+    return numbers_in(line_index, line, line);
+}
+
+// Splits the lines into blocks separated by blank lines
+// Each block comes with the index of its first line so errors can give the line
+pub fn blocks<S: AsRef<str>>(lines: &[S]) -> Vec<(usize, &[S])> {
+    let mut blocks = Vec::new();
+    let mut start = 0;
+    for i in 0..=lines.len() {
+        if i == lines.len() || lines[i].as_ref().is_empty() {
+            if i > start {
+                blocks.push((start, &lines[start..i]));
+            }
+            start = i + 1;
+        }
+    }
+    return blocks;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn template() {
+        let template = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+        let line = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15";
+        let fields = scan(0, line, template).unwrap();
+        assert_eq!(fields.number::<i32>(0).unwrap(), 2);
+        assert_eq!(fields.number::<i32>(2).unwrap(), -2);
+        assert_eq!(fields.str(3), "15");
+
+        let errors = [
+            ("Sensor at y=2", "column 11: Expected 'x='"),
+            (
+                "Sensor at x=2, y=18",
+                "column 20: Expected ': closest beacon is at x='",
+            ),
+            ("Sensor at x=", "column 13: Missing field"),
+            (
+                "Sensor at x=2, y=18: closest beacon is at x=-2, y=a",
+                "column 51: Not a number 'a'",
+            ),
+        ];
+        for (line, message) in errors {
+            let fields = scan(3, line, template);
+            let err = match fields {
+                Ok(fields) => fields.number::<i32>(3).map(|_| ()).unwrap_err(),
+                Err(err) => err,
+            };
+            assert!(err.to_string().starts_with("Day00 line 4 "));
+            assert!(err.to_string().contains(message), "{err}");
+        }
+
+        let err = scan(0, "move 1 from 2 to 3 now", "move {} from {} to {}!").unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 1 column 23: Expected '!'");
+        let err = scan(0, "a: b c", "{}: {}d").unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 1 column 7: Expected 'd'");
+        let err = scan(0, "a: b c", "{}: {} ").unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 1 column 6: Unexpected 'c'");
+        let fields = scan(0, "a:  b   c", "{}: {} {}").unwrap();
+        assert_eq!(
+            (fields.str(0), fields.str(1), fields.str(2)),
+            ("a", "b", "c")
+        );
+        let fields = scan(0, "  Starting items: 79, 98", "  Starting items: {}").unwrap();
+        assert_eq!(fields.numbers::<u64>(0).unwrap(), vec![79, 98]);
+    }
+
+    #[test]
+    fn integers() {
+        assert_eq!(numbers::<i32>(0, "x=-2, y=15").unwrap(), vec![-2, 15]);
+        assert_eq!(numbers::<i32>(0, "2-4,6-8").unwrap(), vec![2, 4, 6, 8]);
+        assert_eq!(numbers::<i32>(0, "- -1-").unwrap(), vec![-1]);
+        assert!(numbers::<i32>(0, "no numbers").unwrap().is_empty());
+        let err = numbers::<u8>(0, "a 300").unwrap_err();
+        assert_eq!(err.to_string(), "Day00 line 1 column 3: Not a number '300'");
+    }
+
+    #[test]
+    fn split_blocks() {
+        let lines = str_array_to_string_array(vec!["", "1", "2", "", "", "3", ""]);
+        let blocks = blocks(&lines);
+        assert_eq!(blocks.len(), 2);
+        assert_eq!(blocks[0], (1, &lines[1..3]));
+        assert_eq!(blocks[1], (5, &lines[5..6]));
+    }
+}
@@ -0,0 +1,199 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+// The cheapest way found from a start to a goal
+// states runs from the start to the goal, both included
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<S> {
+    pub cost: usize,
+    pub states: Vec<S>,
+}
+
+const NO_PARENT: usize = usize::MAX;
+
+// Every state reached with the index of the state it was reached from, so paths can be rebuilt
+// without copying the route so far into every entry
+struct Visits<S> {
+    states: Vec<S>,
+    parents: Vec<usize>,
+}
+
+impl<S: Clone> Visits<S> {
+    fn new() -> Visits<S> {
+        return Visits {
+            states: Vec::new(),
+            parents: Vec::new(),
+        };
+    }
+
+    fn add(&mut self, state: S, parent: usize) -> usize {
+        self.states.push(state);
+        self.parents.push(parent);
+        return self.states.len() - 1;
+    }
+
+    fn path(&self, cost: usize, index: usize) -> Path<S> {
+        let mut states = Vec::new();
+        let mut index = index;
+        while index != NO_PARENT {
+            states.push(self.states[index].clone());
+            index = self.parents[index];
+        }
+        states.reverse();
+        return Path { cost, states };
+    }
+}
+
+// Breadth first search where every step costs 1
+// The search starts from all the starts at once and stops at the first state that is_goal
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visits = Visits::new();
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back((visits.add(start, NO_PARENT), 0));
+        }
+    }
+    while !queue.is_empty() {
+        let (index, cost) = queue.pop_front().unwrap();
+        if is_goal(&visits.states[index]) {
+            crate::log_trace!("Cost {cost} after reaching {} states", visits.states.len());
+            return Some(visits.path(cost, index));
+        }
+        for next in neighbours(&visits.states[index]) {
+            if seen.insert(next.clone()) {
+                queue.push_back((visits.add(next, index), cost + 1));
+            }
+        }
+    }
+    return None;
+}
+
+// A* search where neighbours gives each next state with the cost of the step to it
+// heuristic must never be more than the real cost to a goal or a longer path can be returned
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> usize,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    let mut visits = Visits::new();
+    let mut best_costs: HashMap<S, usize> = HashMap::new();
+    // Lowest estimate first, the index breaks ties so states do not need to be ordered
+    let mut heap = BinaryHeap::new();
+    for start in starts {
+        if best_costs.contains_key(&start) {
+            continue;
+        }
+        best_costs.insert(start.clone(), 0);
+        let estimate = heuristic(&start);
+        heap.push(Reverse((estimate, 0, visits.add(start, NO_PARENT))));
+    }
+    while !heap.is_empty() {
+        let Reverse((_, cost, index)) = heap.pop().unwrap();
+        let state = &visits.states[index];
+        // A cheaper way here was found after this entry was added
+        if best_costs[state] < cost {
+            continue;
+        }
+        if is_goal(state) {
+            crate::log_trace!("Cost {cost} after reaching {} states", visits.states.len());
+            return Some(visits.path(cost, index));
+        }
+        for (next, step_cost) in neighbours(state) {
+            let next_cost = cost + step_cost;
+            if let Some(&best_cost) = best_costs.get(&next) {
+                if best_cost <= next_cost {
+                    continue;
+                }
+            }
+            best_costs.insert(next.clone(), next_cost);
+            let estimate = next_cost + heuristic(&next);
+            heap.push(Reverse((estimate, next_cost, visits.add(next, index))));
+        }
+    }
+    return None;
+}
+
+// A* without a heuristic
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    neighbours: impl FnMut(&S) -> I,
+    is_goal: impl FnMut(&S) -> bool,
+) -> Option<Path<S>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = (S, usize)>,
+{
+    return astar(starts, neighbours, |_| 0, is_goal);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn breadth_first() {
+        let lines = str_array_to_string_array(vec!["S..#", ".#.#", "...E"]);
+        let grid = Grid::parse(&lines, "", |_, _, c| Some(c)).unwrap();
+        let open = |&(x, y): &(usize, usize)| {
+            grid.neighbours4(x, y)
+                .filter(|&xy| grid[xy] != b'#')
+                .collect::<Vec<(usize, usize)>>()
+        };
+        let path = bfs([(0, 0)], open, |&xy| grid[xy] == b'E').unwrap();
+        assert_eq!(path.cost, 5);
+        assert_eq!(path.states.len(), 6);
+        assert_eq!(path.states[0], (0, 0));
+        assert_eq!(path.states[5], (3, 2));
+
+        // Any start will do and the nearest one wins
+        let path = bfs([(0, 0), (2, 1)], open, |&xy| grid[xy] == b'E').unwrap();
+        assert_eq!(path.states, vec![(2, 1), (2, 2), (3, 2)]);
+
+        assert_eq!(bfs([0], |&n| [n + 1], |&n| n == 10).unwrap().cost, 10);
+        assert_eq!(bfs([0], |&n| [(n + 2) % 10], |&n| n == 5), None);
+    }
+
+    #[test]
+    fn weighted() {
+        // a -> b -> c -> d is cheaper than the fewer steps of a -> d
+        let links = HashMap::from([
+            ('a', vec![('b', 1), ('c', 4), ('d', 7)]),
+            ('b', vec![('c', 1), ('d', 5)]),
+            ('c', vec![('d', 1)]),
+            ('d', vec![]),
+        ]);
+        let path = dijkstra(['a'], |n| links[n].clone(), |&n| n == 'd').unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.states, vec!['a', 'b', 'c', 'd']);
+
+        // Steps along a line to 20 where a jump of 3 costs 2
+        // so the cost is at least 2 / 3 of the distance left
+        let neighbours = |&n: &i64| vec![(n + 1, 1), (n - 1, 1), (n + 3, 2)];
+        let heuristic = |&n: &i64| (20 - n).unsigned_abs() as usize * 2 / 3;
+        let path = astar([1], neighbours, heuristic, |&n| n == 20).unwrap();
+        assert_eq!(path.cost, 13);
+        assert_eq!(path.states.len(), 8);
+        assert_eq!(
+            dijkstra([1], neighbours, |&n| n == 20).unwrap().cost,
+            path.cost
+        );
+    }
+}
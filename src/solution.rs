@@ -0,0 +1,128 @@
+use std::fmt;
+use std::io::BufRead;
+
+use crate::error::Result;
+use crate::input::Input;
+use crate::render::Frames;
+
+use crate::year2022;
+
+// A calendar of puzzles
+pub struct Year {
+    pub year: u32,
+    // In day order
+    pub puzzles: &'static [&'static Puzzle],
+}
+
+// Every year with solutions, in year order
+pub static YEARS: [&Year; 1] = [&year2022::YEAR];
+
+impl Year {
+    pub fn find(year: u32) -> Option<&'static Year> {
+        return YEARS.iter().copied().find(|y| y.year == year);
+    }
+
+    pub fn latest() -> &'static Year {
+        return YEARS[YEARS.len() - 1];
+    }
+}
+
+// The result of solving one part of a puzzle
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    // The puzzle does not have this part (Day 25 only has one part)
+    None,
+    Number(i64),
+    // Multi-line answers (e.g. Day 10 screen output) use '\n' between lines
+    Text(String),
+}
+
+impl Answer {
+    pub fn is_multi_line(&self) -> bool {
+        return match self {
+            Answer::Text(text) => text.contains('\n'),
+            _ => false,
+        };
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        return match self {
+            Answer::None => Ok(()),
+            Answer::Number(number) => write!(f, "{number}"),
+            Answer::Text(text) => write!(f, "{text}"),
+        };
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(value: i64) -> Answer {
+        return Answer::Number(value);
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        return Answer::Number(value as i64);
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        return Answer::Number(value as i64);
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        return Answer::Text(value);
+    }
+}
+
+impl From<Vec<String>> for Answer {
+    fn from(lines: Vec<String>) -> Answer {
+        return Answer::Text(lines.join("\n"));
+    }
+}
+
+// A day's solver : parse the puzzle input then solve each part
+// A new instance is made for each part because some parts modify the parsed state
+// Bad input is reported as an Error instead of a panic
+pub trait Solution {
+    // The lines are borrowed from the input, anything kept from them is the day's to copy
+    fn parse(&mut self, input: &Input) -> Result<()>;
+    fn part1(&mut self) -> Result<Answer>;
+    fn part2(&mut self) -> Result<Answer>;
+    // Days with options set them from --set name=value before the input is parsed
+    // Returns false when the day has no option called name
+    fn set_option(&mut self, _name: &str, _value: &str) -> Result<bool> {
+        return Ok(false);
+    }
+    // Days that can solve a part reading the input a line at a time, keeping only what the part
+    // needs, so an input too big to load still runs, name is the input file for the errors
+    // Returns None when the day has to load the whole input
+    fn solve_stream(&mut self, _name: &str, _reader: &mut dyn BufRead) -> Option<Result<Answer>> {
+        return None;
+    }
+    // Simulations that can be rendered keep frames and give it an image of each step as they run
+    // Returns false when the day has nothing to render
+    fn set_frames(&mut self, _frames: Frames) -> bool {
+        return false;
+    }
+}
+
+// Registry entry for a day
+// The expected answers are recorded in the answers file next to the input file
+pub struct Puzzle {
+    pub year: u32,
+    pub day: u32,
+    pub input_file: &'static str,
+    pub instance: fn(part1: bool) -> Box<dyn Solution>,
+}
+
+impl Puzzle {
+    pub fn name(&self) -> String {
+        return format!("Day{:02}", self.day);
+    }
+}
@@ -0,0 +1,16 @@
+use std::io;
+
+use crate::input::Input;
+
+// The lines are split the same way as puzzle inputs
+pub fn file_to_vec(filename: &str) -> io::Result<Vec<String>> {
+    return Ok(Input::load(filename)?.to_lines());
+}
+
+pub fn str_array_to_string_array(strings: Vec<&str>) -> Vec<String> {
+    let mut lines = Vec::new() as Vec<String>;
+    for s in strings {
+        lines.push(s.to_string());
+    }
+    return lines;
+}
@@ -0,0 +1,373 @@
+use std::io::{self, IsTerminal, Read, Write};
+use std::panic::{self, AssertUnwindSafe};
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::render::{Image, Rgb};
+use crate::runner::{panic_message, quietly};
+use crate::solution::{Answer, Puzzle};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Key {
+    PlayPause,
+    Step,
+    Faster,
+    Slower,
+    Up,
+    Down,
+    Left,
+    Right,
+    Quit,
+}
+
+// Keys from the bytes a terminal in raw mode sends, the arrows are ESC [ A to ESC [ D
+pub fn parse_keys(bytes: &[u8]) -> Vec<Key> {
+    let mut keys = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == 0x1b && i + 2 < bytes.len() && bytes[i + 1] == b'[' {
+            let arrow = match bytes[i + 2] {
+                b'A' => Some(Key::Up),
+                b'B' => Some(Key::Down),
+                b'C' => Some(Key::Right),
+                b'D' => Some(Key::Left),
+                _ => None,
+            };
+            keys.extend(arrow);
+            i += 3;
+            continue;
+        }
+        let key = match bytes[i] {
+            b' ' | b'p' => Some(Key::PlayPause),
+            b'n' | b'.' => Some(Key::Step),
+            b'+' | b'=' => Some(Key::Faster),
+            b'-' | b'_' => Some(Key::Slower),
+            b'k' => Some(Key::Up),
+            b'j' => Some(Key::Down),
+            b'h' => Some(Key::Left),
+            b'l' => Some(Key::Right),
+            // q, escape or ctrl-c
+            b'q' | 0x1b | 0x03 => Some(Key::Quit),
+            _ => None,
+        };
+        keys.extend(key);
+        i += 1;
+    }
+    return keys;
+}
+
+// The part of the image on the screen, each line of text shows two rows of pixels
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct View {
+    pub x: usize,
+    pub y: usize,
+    pub columns: usize,
+    pub lines: usize,
+}
+
+impl View {
+    // Scrolls by a few cells for an arrow key keeping as much of the image on the screen
+    // as will fit
+    pub fn scroll(&mut self, key: Key, image: &Image) {
+        match key {
+            Key::Up => self.y = self.y.saturating_sub(4),
+            Key::Down => self.y += 4,
+            Key::Left => self.x = self.x.saturating_sub(4),
+            Key::Right => self.x += 4,
+            _ => {}
+        }
+        self.x = self.x.min(image.width().saturating_sub(self.columns));
+        self.y = self.y.min(image.height().saturating_sub(self.lines * 2));
+    }
+}
+
+fn colour_codes(text: &mut String, top: Rgb, bottom: Rgb) {
+    *text += &format!(
+        "\x1b[38;2;{};{};{}m\x1b[48;2;{};{};{}m",
+        top[0], top[1], top[2], bottom[0], bottom[1], bottom[2]
+    );
+}
+
+// The view of the image as lines of upper half blocks with the top pixel as the foreground
+// colour and the pixel below as the background colour, lines end with "\r\n" for raw mode
+pub fn to_ansi(image: &Image, view: &View) -> String {
+    let mut text = String::new();
+    let right = (view.x + view.columns).min(image.width());
+    for line in 0..view.lines {
+        let y = view.y + line * 2;
+        if y >= image.height() {
+            break;
+        }
+        let mut colours = None;
+        for x in view.x..right {
+            let top = image[(x, y)];
+            let bottom = image.get(x, y + 1).copied().unwrap_or([0, 0, 0]);
+            if colours != Some((top, bottom)) {
+                colour_codes(&mut text, top, bottom);
+                colours = Some((top, bottom));
+            }
+            text.push('▀');
+        }
+        text += "\x1b[0m\x1b[K\r\n";
+    }
+    return text;
+}
+
+fn stty(args: &[&str]) -> io::Result<String> {
+    let output = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!("stty {} failed", args.join(" "))));
+    }
+    return Ok(String::from_utf8_lossy(&output.stdout).trim().to_string());
+}
+
+// Columns and lines, 80 by 24 when the size is not known
+fn terminal_size() -> (usize, usize) {
+    let size = stty(&["size"]).unwrap_or_default();
+    return match size.split_once(' ') {
+        Some((lines, columns)) => (
+            columns.parse().unwrap_or(80).max(1),
+            lines.parse().unwrap_or(24).max(2),
+        ),
+        None => (80, 24),
+    };
+}
+
+// Keys are read a byte at a time without echo and the screen is switched to the alternate
+// screen, dropping it puts the terminal back as it was
+struct RawTerminal {
+    saved: String,
+}
+
+impl RawTerminal {
+    fn enable() -> io::Result<RawTerminal> {
+        let saved = stty(&["-g"])?;
+        stty(&["raw", "-echo"])?;
+        let mut stdout = io::stdout();
+        write!(stdout, "\x1b[?1049h\x1b[?25l")?;
+        stdout.flush()?;
+        return Ok(RawTerminal { saved });
+    }
+}
+
+impl Drop for RawTerminal {
+    fn drop(&mut self) {
+        let _ = stty(&[&self.saved]);
+        let mut stdout = io::stdout();
+        let _ = write!(stdout, "\x1b[?25h\x1b[?1049l");
+        let _ = stdout.flush();
+    }
+}
+
+// What the simulation thread sends to the player
+enum Event {
+    Frame(Image),
+    Done(Result<Answer>),
+}
+
+// Runs the part on its own thread, it waits for the player to take each frame before making
+// the next one
+fn simulate(puzzle: &'static Puzzle, input: Input, part: usize) -> Receiver<Event> {
+    let (sender, receiver) = mpsc::sync_channel(1);
+    thread::spawn(move || {
+        let frames = sender.clone();
+        // A panic is sent as the answer instead of being printed over the screen
+        let result = quietly(|| {
+            panic::catch_unwind(AssertUnwindSafe(|| -> Result<Answer> {
+                let mut solution = (puzzle.instance)(part == 1);
+                solution.parse(&input)?;
+                let watched = solution.set_frames(Box::new(move |image| {
+                    // The player has gone when this fails and the part runs to the end unwatched
+                    let _ = frames.send(Event::Frame(image.clone()));
+                }));
+                if !watched {
+                    return Err(Error::input("Has nothing to visualize"));
+                }
+                return if part == 1 {
+                    solution.part1()
+                } else {
+                    solution.part2()
+                };
+            }))
+        });
+        let answer = match result {
+            Ok(answer) => answer.map_err(|err| err.in_day(puzzle.day)),
+            Err(payload) => Err(Error::Panic {
+                day: puzzle.day,
+                part,
+                message: panic_message(&payload),
+            }),
+        };
+        let _ = sender.send(Event::Done(answer));
+    });
+    return receiver;
+}
+
+fn read_keys() -> Receiver<Key> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut stdin = io::stdin();
+        let mut buffer = [0; 64];
+        loop {
+            let count = match stdin.read(&mut buffer) {
+                Ok(0) | Err(_) => return,
+                Ok(count) => count,
+            };
+            for key in parse_keys(&buffer[..count]) {
+                if sender.send(key).is_err() {
+                    return;
+                }
+            }
+        }
+    });
+    return receiver;
+}
+
+const HELP: &str = "space play/pause  n step  +/- speed  arrows scroll  q quit";
+
+// Steps through the frames of a part in the terminal at fps frames a second
+// Returns the answer, or None when the player quits before the part has finished
+pub fn play(
+    puzzle: &'static Puzzle,
+    input: Input,
+    part: usize,
+    fps: usize,
+) -> Result<Option<Answer>> {
+    let terminal_error = |err| Error::io("terminal", err);
+    if !io::stdin().is_terminal() || !io::stdout().is_terminal() {
+        return Err(terminal_error(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "visualize needs a terminal",
+        )));
+    }
+    let events = simulate(puzzle, input, part);
+    // Parse errors are reported before the screen is taken over
+    let mut image = match events.recv() {
+        Ok(Event::Frame(image)) => image,
+        Ok(Event::Done(answer)) => return answer.map(Some),
+        Err(_) => unreachable!("The simulation always sends when it is done"),
+    };
+    let terminal = RawTerminal::enable().map_err(terminal_error)?;
+    let keys = read_keys();
+    let (columns, lines) = terminal_size();
+    let mut view = View {
+        x: 0,
+        y: 0,
+        columns,
+        lines: lines - 1,
+    };
+    let mut fps = fps;
+    let mut step = 1;
+    let mut playing = true;
+    let mut done: Option<Result<Answer>> = None;
+    let mut stdout = io::stdout();
+    loop {
+        let state = match &done {
+            Some(Ok(answer)) => format!("done, answer {answer}"),
+            Some(Err(err)) => format!("failed, {err}"),
+            None if playing => "playing".to_string(),
+            None => "paused".to_string(),
+        };
+        let mut status = format!(
+            "{} part {part} step {step} {fps} fps {state} | {HELP}",
+            puzzle.name()
+        );
+        status.truncate(columns);
+        let screen = format!(
+            "\x1b[H{}\x1b[J\x1b[7m{status}\x1b[0m",
+            to_ansi(&image, &view)
+        );
+        stdout
+            .write_all(screen.as_bytes())
+            .and_then(|_| stdout.flush())
+            .map_err(terminal_error)?;
+
+        let wait = if playing && done.is_none() {
+            Duration::from_millis(1000 / fps as u64)
+        } else {
+            Duration::from_secs(3600)
+        };
+        let mut advance = false;
+        match keys.recv_timeout(wait) {
+            Ok(Key::Quit) => break,
+            Ok(Key::PlayPause) => playing = !playing,
+            Ok(Key::Step) => {
+                playing = false;
+                advance = true;
+            }
+            Ok(Key::Faster) => fps = (fps * 2).min(1000),
+            Ok(Key::Slower) => fps = (fps / 2).max(1),
+            Ok(key) => view.scroll(key, &image),
+            Err(RecvTimeoutError::Timeout) => advance = playing,
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+        if advance && done.is_none() {
+            match events.recv() {
+                Ok(Event::Frame(next)) => {
+                    image = next;
+                    step += 1;
+                }
+                Ok(Event::Done(answer)) => done = Some(answer),
+                Err(_) => unreachable!("The simulation always sends when it is done"),
+            }
+        }
+    }
+    drop(terminal);
+    return done.transpose();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::Grid;
+
+    #[test]
+    fn keys() {
+        assert_eq!(
+            parse_keys(b" n+-\x1b[A\x1b[Dxq"),
+            vec![
+                Key::PlayPause,
+                Key::Step,
+                Key::Faster,
+                Key::Slower,
+                Key::Up,
+                Key::Left,
+                Key::Quit
+            ]
+        );
+        assert_eq!(parse_keys(b"\x1b"), vec![Key::Quit]);
+        assert_eq!(parse_keys(b"\x1b[Z"), vec![]);
+    }
+
+    #[test]
+    fn view() {
+        let mut image = Grid::new(3, 3, [0, 0, 0]);
+        image[(0, 1)] = [255, 0, 0];
+        let mut view = View {
+            x: 0,
+            y: 0,
+            columns: 2,
+            lines: 5,
+        };
+        assert_eq!(
+            to_ansi(&image, &view),
+            "\x1b[38;2;0;0;0m\x1b[48;2;255;0;0m▀\x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀\x1b[0m\x1b[K\r\n\
+             \x1b[38;2;0;0;0m\x1b[48;2;0;0;0m▀▀\x1b[0m\x1b[K\r\n"
+        );
+        // Scrolling stops at the right and bottom edges of the image
+        view.scroll(Key::Right, &image);
+        assert_eq!((view.x, view.y), (1, 0));
+        view.lines = 1;
+        view.scroll(Key::Down, &image);
+        assert_eq!((view.x, view.y), (1, 1));
+        view.scroll(Key::Up, &image);
+        assert_eq!((view.x, view.y), (1, 0));
+    }
+}
@@ -0,0 +1,508 @@
+use crate::error::{parse_number, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::i64;
+use std::io::BufRead;
+
+/*
+
+--- Day 1: Calorie Counting ---
+
+Santa's reindeer typically eat regular reindeer food, but they need a lot of magical energy to deliver presents on Christmas.
+For that, their favorite snack is a special type of star fruit that only grows deep in the jungle.
+The Elves have brought you on their annual expedition to the grove where the fruit grows.
+
+To supply enough magical energy, the expedition needs to retrieve a minimum of fifty stars by December 25th.
+Although the Elves assure you that the grove has plenty of fruit, you decide to grab any fruit you see along the way, just in case.
+
+Collect stars by solving puzzles.
+Two puzzles will be made available on each day in the Advent calendar; the second puzzle is unlocked when you complete the first.
+Each puzzle grants one star. Good luck!
+
+The jungle must be too overgrown and difficult to navigate in vehicles or access from the air; the Elves' expedition traditionally goes on foot.
+As your boats approach land, the Elves begin taking inventory of their supplies.
+One important consideration is food - in particular, the number of Calories each Elf is carrying (your puzzle input).
+
+The Elves take turns writing down the number of Calories contained by the various meals, snacks, rations, etc. that they've brought with them, one item per line.
+Each Elf separates their own inventory from the previous Elf's inventory (if any) by a blank line.
+
+For example, suppose the Elves finish writing their items' Calories and end up with the following list:
+
+1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+
+This list represents the Calories of the food carried by five Elves:
+
+The first Elf is carrying food with 1000, 2000, and 3000 Calories, a total of 6000 Calories.
+The second Elf is carrying one food item with 4000 Calories.
+The third Elf is carrying food with 5000 and 6000 Calories, a total of 11000 Calories.
+The fourth Elf is carrying food with 7000, 8000, and 9000 Calories, a total of 24000 Calories.
+The fifth Elf is carrying one food item with 10000 Calories.
+
+In case the Elves get hungry and need extra snacks, they need to know which Elf to ask: they'd like to know how many Calories are being carried by the Elf carrying the most Calories.
+In the example above, this is 24000 (carried by the fourth Elf).
+
+Find the Elf carrying the most Calories. How many total Calories is that Elf carrying?
+
+Your puzzle answer was 71502.
+
+--- Part Two ---
+
+By the time you calculate the answer to the Elves' question, they've already realized that the Elf carrying the most Calories of food might eventually run out of snacks.
+
+To avoid this unacceptable situation, the Elves would instead like to know the total Calories carried by the top three Elves carrying the most Calories.
+That way, even if one of those Elves runs out of snacks, they still have two backups.
+
+In the example above, the top three Elves are the fourth Elf (with 24000 Calories), then the third Elf (with 11000 Calories), then the fifth Elf (with 10000 Calories).
+The sum of the Calories carried by these three elves is 45000.
+
+Find the top three Elves carrying the most Calories.
+How many Calories are those Elves carrying in total?
+
+*/
+
+static INPUT_FILE: &str = "data/2022/day01/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 1,
+    input_file: INPUT_FILE,
+    instance: |part1| Box::new(Day::instance(part1)),
+};
+
+// The items of one elf, position is where the elf is in the input starting from 0
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Elf {
+    pub position: usize,
+    pub items: Vec<i64>,
+    pub total: i64,
+}
+
+// The elves with the totals from 'from' to 'to' included
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Bucket {
+    pub from: i64,
+    pub to: i64,
+    pub count: usize,
+}
+
+// Every elf in input order with the elves ordered by total as well
+#[derive(Clone, Debug, Default)]
+pub struct Inventory {
+    elves: Vec<Elf>,
+    // Indexes of elves, most calories first, elves with the same total stay in input order
+    by_total: Vec<usize>,
+}
+
+impl Inventory {
+    pub fn new() -> Inventory {
+        return Inventory::default();
+    }
+
+    // The items of each elf in input order, the elves are sorted by total once they are all in
+    pub fn from_items(elf_items: Vec<Vec<i64>>) -> Inventory {
+        let mut inventory = Inventory::new();
+        for (position, items) in elf_items.into_iter().enumerate() {
+            let total = items.iter().sum();
+            inventory.elves.push(Elf {
+                position,
+                items,
+                total,
+            });
+        }
+        inventory.by_total = (0..inventory.elves.len()).collect();
+        // A stable sort keeps elves with the same total in input order
+        let elves = &inventory.elves;
+        inventory.by_total.sort_by_key(|&e| Reverse(elves[e].total));
+        return inventory;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.elves.len();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.elves.is_empty();
+    }
+
+    pub fn elves(&self) -> &[Elf] {
+        return &self.elves;
+    }
+
+    // The n elves with the most calories, most first, all of them when there are fewer than n
+    pub fn top(&self, n: usize) -> Vec<&Elf> {
+        let n = n.min(self.by_total.len());
+        return self.by_total[..n].iter().map(|&e| &self.elves[e]).collect();
+    }
+
+    pub fn top_total(&self, n: usize) -> i64 {
+        return self.top(n).iter().map(|elf| elf.total).sum();
+    }
+
+    // The number of items of each elf in input order
+    pub fn item_counts(&self) -> Vec<usize> {
+        return self.elves.iter().map(|elf| elf.items.len()).collect();
+    }
+
+    // The nearest rank percentile of the totals, percent from 0 to 100
+    // None when there are no elves
+    pub fn percentile(&self, percent: usize) -> Option<i64> {
+        if self.is_empty() {
+            return None;
+        }
+        let count = self.by_total.len();
+        let rank = (percent.min(100) * count).div_ceil(100).max(1);
+        // by_total is in descending order
+        return Some(self.elves[self.by_total[count - rank]].total);
+    }
+
+    pub fn median(&self) -> Option<i64> {
+        return self.percentile(50);
+    }
+
+    // The totals split into ranges of the same width from the least to the most, the last range
+    // ends at the most and there are fewer than bucket_count when there are fewer totals
+    pub fn histogram(&self, bucket_count: usize) -> Vec<Bucket> {
+        if self.is_empty() || bucket_count == 0 {
+            return Vec::new();
+        }
+        let most = self.elves[self.by_total[0]].total;
+        let least = self.elves[self.by_total[self.by_total.len() - 1]].total;
+        let span = most - least + 1;
+        // Rounded up so bucket_count buckets reach the most
+        let width = (span + bucket_count as i64 - 1) / bucket_count as i64;
+        let mut buckets = Vec::new();
+        for b in 0..(span + width - 1) / width {
+            buckets.push(Bucket {
+                from: least + b * width,
+                to: (least + (b + 1) * width - 1).min(most),
+                count: 0,
+            });
+        }
+        for elf in &self.elves {
+            buckets[((elf.total - least) / width) as usize].count += 1;
+        }
+        return buckets;
+    }
+
+    // A few lines about the elves for the debug output
+    pub fn summary(&self) -> String {
+        let counts = self.item_counts();
+        let mut text = format!(
+            "{} elves {} items median {} p90 {}",
+            self.len(),
+            counts.iter().sum::<usize>(),
+            self.median().unwrap_or(0),
+            self.percentile(90).unwrap_or(0)
+        );
+        for bucket in self.histogram(5) {
+            text += &format!(
+                "\n{:>6} - {:>6} : {}",
+                bucket.from,
+                bucket.to,
+                "#".repeat(bucket.count)
+            );
+        }
+        return text;
+    }
+}
+
+// The k largest totals seen so far, the smallest of them is at the top of the heap so each
+// new total is compared with it and replaces it when bigger
+pub struct TopTotals {
+    k: usize,
+    heap: BinaryHeap<Reverse<i64>>,
+}
+
+impl TopTotals {
+    pub fn new(k: usize) -> TopTotals {
+        return TopTotals {
+            k,
+            heap: BinaryHeap::with_capacity(k + 1),
+        };
+    }
+
+    pub fn add(&mut self, total: i64) {
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(total));
+        } else if let Some(&Reverse(least)) = self.heap.peek() {
+            if total > least {
+                self.heap.pop();
+                self.heap.push(Reverse(total));
+            }
+        }
+    }
+
+    // Most first
+    pub fn totals(self) -> Vec<i64> {
+        return self
+            .heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(total)| total)
+            .collect();
+    }
+}
+
+// The k largest elf totals of an inventory read a line at a time, so only the current line and
+// the k totals are kept however big the input is, name is the input file for the errors
+// Elves are separated by blank lines the same as in Day::parse
+pub fn stream_top_totals(mut reader: impl BufRead, name: &str, k: usize) -> Result<Vec<i64>> {
+    let mut top = TopTotals::new(k);
+    let mut line = String::new();
+    let mut line_index = 0;
+    let mut total: Option<i64> = None;
+    loop {
+        line.clear();
+        let count = reader
+            .read_line(&mut line)
+            .map_err(|err| Error::io(name, err))?;
+        if count == 0 {
+            break;
+        }
+        let text = line.trim_end_matches(['\n', '\r']);
+        if !text.is_empty() {
+            let value: i64 =
+                parse_number(line_index, text, text).map_err(|err| err.in_file(name))?;
+            total = Some(total.unwrap_or(0) + value);
+        } else if let Some(elf_total) = total.take() {
+            top.add(elf_total);
+        }
+        line_index += 1;
+    }
+    if let Some(elf_total) = total {
+        top.add(elf_total);
+    }
+    return Ok(top.totals());
+}
+
+pub struct Day {
+    part1: bool,
+    inventory: Inventory,
+}
+
+impl Day {
+    pub fn instance(part1: bool) -> Day {
+        Day {
+            part1: part1,
+            inventory: Inventory::new(),
+        }
+    }
+
+    pub fn most_calories(&self) -> i64 {
+        return self.inventory.top_total(1);
+    }
+
+    pub fn most_calories_top3(&self) -> i64 {
+        return self.inventory.top_total(3);
+    }
+
+    // The same answers as part1 and part2 without keeping the inventory
+    fn stream(&self, name: &str, reader: &mut dyn BufRead) -> Result<Answer> {
+        let count = if self.part1 { 1 } else { 3 };
+        let top = stream_top_totals(reader, name, count)?;
+        if top.len() < count {
+            let message = if self.part1 {
+                "No elves"
+            } else {
+                "Less than three elves"
+            };
+            return Err(Error::input(message).in_file(name));
+        }
+        return Ok(top.iter().sum::<i64>().into());
+    }
+}
+
+impl Solution for Day {
+    // Blank lines end an elf, a run of blank lines does not make elves without items
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let mut elf_items: Vec<Vec<i64>> = Vec::new();
+        let mut items: Vec<i64> = Vec::new();
+        for (i, line) in input.lines().enumerate() {
+            if !line.is_empty() {
+                let value: i64 = parse_number(i, line, line)?;
+                items.push(value);
+            } else if !items.is_empty() {
+                elf_items.push(items);
+                items = Vec::new();
+            }
+        }
+        if !items.is_empty() {
+            elf_items.push(items);
+        }
+        self.inventory = Inventory::from_items(elf_items);
+        crate::log_debug!("{}", self.inventory.summary());
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        if self.inventory.is_empty() {
+            return Err(Error::input("No elves"));
+        }
+        return Ok(self.most_calories().into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        if self.inventory.len() < 3 {
+            return Err(Error::input("Less than three elves"));
+        }
+        return Ok(self.most_calories_top3().into());
+    }
+
+    fn solve_stream(&mut self, name: &str, reader: &mut dyn BufRead) -> Option<Result<Answer>> {
+        return Some(self.stream(name, reader));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::stream_part;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn most_calories() {
+        let input: Vec<&str> = vec![
+            "1000", "2000", "3000", "", "4000", "", "5000", "6000", "", "7000", "8000", "9000", "",
+            "10000",
+        ];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.most_calories(), 24000);
+    }
+
+    #[test]
+    fn most_calories_top3() {
+        let input: Vec<&str> = vec![
+            "1000", "2000", "3000", "", "4000", "", "5000", "6000", "", "7000", "8000", "9000", "",
+            "10000",
+        ];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.most_calories_top3(), 45000);
+    }
+
+    #[test]
+    fn inventory() {
+        let input: Vec<&str> = vec![
+            "1000", "2000", "3000", "", "4000", "", "", "5000", "6000", "", "7000", "8000", "9000",
+            "", "10000", "",
+        ];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        let inventory = &day.inventory;
+        assert_eq!(inventory.len(), 5);
+        let top = inventory.top(2);
+        assert_eq!(top[0].position, 3);
+        assert_eq!(top[0].items, vec![7000, 8000, 9000]);
+        assert_eq!(top[1].position, 2);
+        assert_eq!(inventory.top(10).len(), 5);
+        assert_eq!(inventory.top_total(5), 55000);
+        assert_eq!(inventory.item_counts(), vec![3, 1, 2, 3, 1]);
+        assert_eq!(inventory.median(), Some(10000));
+        assert_eq!(inventory.percentile(0), Some(4000));
+        assert_eq!(inventory.percentile(100), Some(24000));
+        assert_eq!(
+            inventory.histogram(2),
+            vec![
+                Bucket {
+                    from: 4000,
+                    to: 14000,
+                    count: 4
+                },
+                Bucket {
+                    from: 14001,
+                    to: 24000,
+                    count: 1
+                }
+            ]
+        );
+        let ranges: Vec<(i64, i64)> = inventory
+            .histogram(3)
+            .iter()
+            .map(|bucket| (bucket.from, bucket.to))
+            .collect();
+        assert_eq!(ranges, vec![(4000, 10666), (10667, 17333), (17334, 24000)]);
+        // Two totals only fill two buckets
+        let inventory = Inventory::from_items(vec![vec![1], vec![2]]);
+        assert_eq!(inventory.histogram(5).len(), 2);
+        assert_eq!(Inventory::new().median(), None);
+        assert_eq!(Inventory::new().top_total(3), 0);
+        // Elves with the same total stay in input order
+        let inventory = Inventory::from_items(vec![vec![1], vec![3], vec![2, 1]]);
+        let positions: Vec<usize> = inventory.top(3).iter().map(|elf| elf.position).collect();
+        assert_eq!(positions, vec![1, 2, 0]);
+    }
+
+    #[test]
+    fn streaming() {
+        let text = "1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n9000\n\n10000";
+        let top = stream_top_totals(text.as_bytes(), "example1.txt", 3).unwrap();
+        assert_eq!(top, vec![24000, 11000, 10000]);
+        let crlf = text.replace('\n', "\r\n") + "\r\n\r\n";
+        assert_eq!(
+            stream_top_totals(crlf.as_bytes(), "example1.txt", 1).unwrap(),
+            vec![24000]
+        );
+        assert_eq!(
+            stream_top_totals(text.as_bytes(), "example1.txt", 10).unwrap(),
+            vec![24000, 11000, 10000, 6000, 4000]
+        );
+
+        // Errors are reported by the runner with the day and the input file
+        let run = stream_part(&PUZZLE, &[], "calories.txt", &mut "1\n\nx2\n".as_bytes(), 1);
+        assert_eq!(
+            run.answer.unwrap_err().to_string(),
+            "Day01 'calories.txt' line 3 column 1: Not a number 'x2'"
+        );
+        let run = stream_part(&PUZZLE, &[], "calories.txt", &mut "1\n\n2\n".as_bytes(), 2);
+        assert_eq!(
+            run.answer.unwrap_err().to_string(),
+            "Day01 unexpected input in 'calories.txt': Less than three elves"
+        );
+    }
+
+    #[test]
+    fn streaming_matches_parse() {
+        // Elves with the same totals and runs of blank lines
+        let mut lines: Vec<String> = Vec::new();
+        for e in 0..500 {
+            for i in 0..(e % 4 + 1) {
+                lines.push(((e * 7919 + i * 104729) % 5000 + 1).to_string());
+            }
+            for _ in 0..(e % 3 + 1) {
+                lines.push(String::new());
+            }
+        }
+        let text = lines.join("\n");
+        for part1 in [true, false] {
+            let mut day = Day::instance(part1);
+            day.parse(&Input::from_lines(&lines)).unwrap();
+            let expected = if part1 {
+                day.most_calories()
+            } else {
+                day.most_calories_top3()
+            };
+            let part = if part1 { 1 } else { 2 };
+            let run = stream_part(&PUZZLE, &[], "elves.txt", &mut text.as_bytes(), part);
+            assert_eq!(run.answer.unwrap(), Answer::Number(expected));
+        }
+    }
+}
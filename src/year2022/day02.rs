@@ -0,0 +1,922 @@
+use crate::error::{parse_number, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
+use crate::util::file_to_vec;
+use std::cmp::Reverse;
+use std::str::FromStr;
+
+/*
+
+--- Day 2: Rock Paper Scissors ---
+
+The Elves begin to set up camp on the beach.
+To decide whose tent gets to be closest to the snack storage, a giant Rock Paper Scissors tournament is already in progress.
+
+Rock Paper Scissors is a game between two players.
+Each game contains many rounds; in each round, the players each simultaneously choose one of Rock, Paper, or Scissors using a hand shape.
+Then, a winner for that round is selected: Rock defeats Scissors, Scissors defeats Paper, and Paper defeats Rock.
+If both players choose the same shape, the round instead ends in a draw.
+
+Appreciative of your help yesterday, one Elf gives you an encrypted strategy guide (your puzzle input) that they say will be sure to help you win.
+"The first column is what your opponent is going to play: A for Rock, B for Paper, and C for Scissors.
+The second column--" Suddenly, the Elf is called away to help with someone's tent.
+
+The second column, you reason, must be what you should play in response: X for Rock, Y for Paper, and Z for Scissors.
+Winning every time would be suspicious, so the responses must have been carefully chosen.
+
+The winner of the whole tournament is the player with the highest score.
+Your total score is the sum of your scores for each round.
+The score for a single round is the score for the shape you selected (1 for Rock, 2 for Paper, and 3 for Scissors) plus the score for the outcome of the round (0 if you lost, 3 if the round was a draw, and 6 if you won).
+
+Since you can't be sure if the Elf is trying to help you or trick you, you should calculate the score you would get if you were to follow the strategy guide.
+
+For example, suppose you were given the following strategy guide:
+
+A Y
+B X
+C Z
+This strategy guide predicts and recommends the following:
+
+In the first round, your opponent will choose Rock (A), and you should choose Paper (Y).
+This ends in a win for you with a score of 8 (2 because you chose Paper + 6 because you won).
+In the second round, your opponent will choose Paper (B), and you should choose Rock (X).
+This ends in a loss for you with a score of 1 (1 + 0).
+The third round is a draw with both players choosing Scissors, giving you a score of 3 + 3 = 6.
+In this example, if you were to follow the strategy guide, you would get a total score of 15 (8 + 1 + 6).
+
+What would your total score be if everything goes exactly according to your strategy guide?
+
+Your puzzle answer was 15691.
+
+--- Part Two ---
+
+The Elf finishes helping with the tent and sneaks back over to you.
+"Anyway, the second column says how the round needs to end: X means you need to lose, Y means you need to end the round in a draw, and Z means you need to win. Good luck!"
+
+The total score is still calculated in the same way, but now you need to figure out what shape to choose so the round ends as indicated. The example above now goes like this:
+
+In the first round, your opponent will choose Rock (A), and you need the round to end in a draw (Y), so you also choose Rock. This gives you a score of 1 + 3 = 4.
+In the second round, your opponent will choose Paper (B), and you choose Rock so you lose (X) with a score of 1 + 0 = 1.
+In the third round, you will defeat your opponent's Scissors with Rock for a score of 1 + 6 = 7.
+Now that you're correctly decrypting the ultra top secret strategy guide, you would get a total score of 12.
+
+Following the Elf's instructions for the second column, what would your total score be if everything goes exactly according to your strategy guide?
+
+*/
+
+static INPUT_FILE: &str = "data/2022/day02/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 2,
+    input_file: INPUT_FILE,
+    instance: |part1| Box::new(Day::instance(part1)),
+};
+
+// The rules of a game played as in the strategy guide, in a rules file
+//
+//   # comment
+//   shape <name> <left code> <right code> <score>
+//   outcome lose|draw|win <code> <score>
+//   <name> beats <name> ...
+//
+// The left code is the other player's shape and in part 1 the right code is the shape to play
+// In part 2 the right code is the outcome wanted, when more than one shape gives it the one
+// that scores the most is played
+// Shapes that do not beat each other draw
+pub const ROCK_PAPER_SCISSORS: &str = "\
+shape Rock A X 1
+shape Paper B Y 2
+shape Scissors C Z 3
+outcome lose X 0
+outcome draw Y 3
+outcome win Z 6
+Rock beats Scissors
+Paper beats Rock
+Scissors beats Paper
+";
+
+pub const ROCK_PAPER_SCISSORS_LIZARD_SPOCK: &str = "\
+shape Rock A V 1
+shape Paper B W 2
+shape Scissors C X 3
+shape Lizard D Y 4
+shape Spock E Z 5
+outcome lose X 0
+outcome draw Y 3
+outcome win Z 6
+Rock beats Scissors Lizard
+Paper beats Rock Spock
+Scissors beats Paper Lizard
+Lizard beats Spock Paper
+Spock beats Scissors Rock
+";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Shape {
+    pub name: String,
+    pub left_code: u8,
+    pub right_code: u8,
+    pub score: i64,
+}
+
+const OUTCOME_NAMES: [&str; 3] = ["lose", "draw", "win"];
+const LOSE: usize = 0;
+const DRAW: usize = 1;
+const WIN: usize = 2;
+
+#[derive(Clone, Debug)]
+pub struct Rules {
+    shapes: Vec<Shape>,
+    // beats[a][b] when shape a defeats shape b
+    beats: Vec<Vec<bool>>,
+    // Lose, draw then win
+    outcome_codes: [u8; 3],
+    outcome_scores: [i64; 3],
+    // The score of right for each left and right shape
+    round_scores: Vec<Vec<i64>>,
+    // The shape right plays for each left shape and outcome
+    right_for_outcome: Vec<[usize; 3]>,
+}
+
+fn find_code(codes: impl Iterator<Item = u8>, code: u8) -> Option<usize> {
+    return codes.enumerate().find(|&(_, c)| c == code).map(|(i, _)| i);
+}
+
+// "A, B or C"
+fn list_codes(codes: impl Iterator<Item = u8>) -> String {
+    let codes: Vec<String> = codes.map(|c| (c as char).to_string()).collect();
+    let last = codes.len() - 1;
+    if last == 0 {
+        return codes[0].clone();
+    }
+    return format!("{} or {}", codes[..last].join(", "), codes[last]);
+}
+
+fn parse_code(line_index: usize, line: &str, token: &str) -> Result<u8> {
+    if token.len() != 1 || !token.as_bytes()[0].is_ascii_graphic() {
+        return Err(Error::parse_token(
+            line_index,
+            line,
+            token,
+            &format!("Expected a single character code not '{token}'"),
+        ));
+    }
+    return Ok(token.as_bytes()[0]);
+}
+
+impl Rules {
+    pub fn parse(lines: &[String]) -> Result<Rules> {
+        let mut shapes: Vec<Shape> = Vec::new();
+        let mut outcomes: [Option<(u8, i64)>; 3] = [None; 3];
+        let mut beats: Vec<(usize, &str, &str)> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            let toks: Vec<&str> = line.split_whitespace().collect();
+            if toks.is_empty() || toks[0].starts_with('#') {
+                continue;
+            }
+            if toks[0] == "shape" {
+                if toks.len() != 5 {
+                    return Err(Error::parse(
+                        i,
+                        1,
+                        "Expected shape <name> <left code> <right code> <score>",
+                    ));
+                }
+                if shapes.iter().any(|shape| shape.name == toks[1]) {
+                    let message = format!("Shape {} is already defined", toks[1]);
+                    return Err(Error::parse_token(i, line, toks[1], &message));
+                }
+                let left_code = parse_code(i, line, toks[2])?;
+                let right_code = parse_code(i, line, toks[3])?;
+                if find_code(shapes.iter().map(|s| s.left_code), left_code).is_some() {
+                    let message = format!("Left code {} is already used", toks[2]);
+                    return Err(Error::parse_token(i, line, toks[2], &message));
+                }
+                if find_code(shapes.iter().map(|s| s.right_code), right_code).is_some() {
+                    let message = format!("Right code {} is already used", toks[3]);
+                    return Err(Error::parse_token(i, line, toks[3], &message));
+                }
+                shapes.push(Shape {
+                    name: toks[1].to_string(),
+                    left_code,
+                    right_code,
+                    score: parse_number(i, line, toks[4])?,
+                });
+            } else if toks[0] == "outcome" {
+                if toks.len() != 4 {
+                    return Err(Error::parse(
+                        i,
+                        1,
+                        "Expected outcome lose|draw|win <code> <score>",
+                    ));
+                }
+                let Some(outcome) = OUTCOME_NAMES.iter().position(|&name| name == toks[1]) else {
+                    return Err(Error::parse_token(
+                        i,
+                        line,
+                        toks[1],
+                        "Expected lose, draw or win",
+                    ));
+                };
+                let code = parse_code(i, line, toks[2])?;
+                if find_code(outcomes.iter().flatten().map(|&(c, _)| c), code).is_some() {
+                    let message = format!("Outcome code {} is already used", toks[2]);
+                    return Err(Error::parse_token(i, line, toks[2], &message));
+                }
+                outcomes[outcome] = Some((code, parse_number(i, line, toks[3])?));
+            } else if toks.len() >= 3 && toks[1] == "beats" {
+                for t in 2..toks.len() {
+                    beats.push((i, toks[0], toks[t]));
+                }
+            } else {
+                return Err(Error::parse_token(
+                    i,
+                    line,
+                    toks[0],
+                    "Expected shape, outcome or <name> beats <name>",
+                ));
+            }
+        }
+
+        if shapes.len() < 2 {
+            return Err(Error::input("Expected at least two shapes"));
+        }
+        let mut outcome_codes = [0; 3];
+        let mut outcome_scores = [0; 3];
+        for o in 0..3 {
+            let Some((code, score)) = outcomes[o] else {
+                return Err(Error::input(&format!(
+                    "Missing outcome {}",
+                    OUTCOME_NAMES[o]
+                )));
+            };
+            outcome_codes[o] = code;
+            outcome_scores[o] = score;
+        }
+        let count_shapes = shapes.len();
+        let mut beats_table = vec![vec![false; count_shapes]; count_shapes];
+        for (i, winner, loser) in beats {
+            let line = &lines[i];
+            let mut index = [0; 2];
+            for (n, name) in [winner, loser].iter().enumerate() {
+                match shapes.iter().position(|shape| shape.name == *name) {
+                    Some(shape) => index[n] = shape,
+                    None => {
+                        let message = format!("Unknown shape {name}");
+                        return Err(Error::parse_token(i, line, name, &message));
+                    }
+                }
+            }
+            let [w, l] = index;
+            if w == l {
+                let message = format!("{winner} can not beat itself");
+                return Err(Error::parse_token(i, line, loser, &message));
+            }
+            if beats_table[l][w] {
+                let message = format!("{loser} already beats {winner}");
+                return Err(Error::parse_token(i, line, loser, &message));
+            }
+            beats_table[w][l] = true;
+        }
+        return Rules::new(shapes, beats_table, outcome_codes, outcome_scores);
+    }
+
+    // Fills in the score and shape tables
+    fn new(
+        shapes: Vec<Shape>,
+        beats: Vec<Vec<bool>>,
+        outcome_codes: [u8; 3],
+        outcome_scores: [i64; 3],
+    ) -> Result<Rules> {
+        let mut rules = Rules {
+            shapes,
+            beats,
+            outcome_codes,
+            outcome_scores,
+            round_scores: Vec::new(),
+            right_for_outcome: Vec::new(),
+        };
+        let count_shapes = rules.shapes.len();
+        for l in 0..count_shapes {
+            let mut scores = Vec::new();
+            let mut best: [Option<usize>; 3] = [None; 3];
+            for r in 0..count_shapes {
+                let score = rules.compute_score(l, r);
+                scores.push(score);
+                let outcome = rules.outcome(l, r);
+                if best[outcome].is_none_or(|b| score > scores[b]) {
+                    best[outcome] = Some(r);
+                }
+            }
+            let mut right = [0; 3];
+            for o in 0..3 {
+                match best[o] {
+                    Some(r) => right[o] = r,
+                    None => {
+                        return Err(Error::input(&format!(
+                            "No shape can {} against {}",
+                            OUTCOME_NAMES[o], rules.shapes[l].name
+                        )))
+                    }
+                }
+            }
+            rules.round_scores.push(scores);
+            rules.right_for_outcome.push(right);
+        }
+        return Ok(rules);
+    }
+
+    // The errors name the file
+    pub fn load(filename: &str) -> Result<Rules> {
+        let lines = file_to_vec(filename).map_err(|err| Error::io(filename, err))?;
+        return Rules::parse(&lines).map_err(|err| err.in_file(filename));
+    }
+
+    // The rules of the puzzle
+    pub fn puzzle() -> Rules {
+        return Rules::preset(ROCK_PAPER_SCISSORS);
+    }
+
+    pub fn rock_paper_scissors_lizard_spock() -> Rules {
+        return Rules::preset(ROCK_PAPER_SCISSORS_LIZARD_SPOCK);
+    }
+
+    fn preset(text: &str) -> Rules {
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        return Rules::parse(&lines).expect("Presets are valid");
+    }
+
+    // A game of an odd number of shapes where each shape beats the half of the others before it
+    // going round, with 3 shapes it is rock paper scissors
+    // Shapes are coded with the same letter A, B, C ... on both sides and score 1, 2, 3 ...
+    pub fn cyclic(count_shapes: usize) -> Result<Rules> {
+        if count_shapes < 3 || count_shapes > 25 || count_shapes % 2 == 0 {
+            return Err(Error::input(&format!(
+                "Expected an odd number of shapes from 3 to 25 not {count_shapes}"
+            )));
+        }
+        let mut shapes = Vec::new();
+        let mut beats = vec![vec![false; count_shapes]; count_shapes];
+        for s in 0..count_shapes {
+            let code = b'A' + s as u8;
+            shapes.push(Shape {
+                name: format!("Shape{}", s + 1),
+                left_code: code,
+                right_code: code,
+                score: s as i64 + 1,
+            });
+            for d in 1..=count_shapes / 2 {
+                beats[s][(s + count_shapes - d) % count_shapes] = true;
+            }
+        }
+        return Rules::new(shapes, beats, [b'X', b'Y', b'Z'], [0, 3, 6]);
+    }
+
+    pub fn shapes(&self) -> &[Shape] {
+        return &self.shapes;
+    }
+
+    // The outcome for right
+    fn outcome(&self, left: usize, right: usize) -> usize {
+        if self.beats[right][left] {
+            return WIN;
+        }
+        if self.beats[left][right] {
+            return LOSE;
+        }
+        return DRAW;
+    }
+
+    fn compute_score(&self, left: usize, right: usize) -> i64 {
+        return self.shapes[right].score + self.outcome_scores[self.outcome(left, right)];
+    }
+}
+
+// The shapes to play against each left play and the score they make
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Plan {
+    pub score: i64,
+    pub right_plays: Vec<usize>,
+    pub wins: usize,
+}
+
+// The totals of a guide played over many trials
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: i64,
+    pub max: i64,
+}
+
+// xorshift64* so simulations with the same seed play the same games
+struct Random {
+    state: u64,
+}
+
+impl Random {
+    fn new(seed: u64) -> Random {
+        // The state must never be 0
+        return Random {
+            state: seed ^ 0x9e37_79b9_7f4a_7c15 | 1,
+        };
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        return self.state.wrapping_mul(0x2545_f491_4f6c_dd1d);
+    }
+
+    // From 0 up to but not including 1
+    fn next_f64(&mut self) -> f64 {
+        return (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+    }
+
+    // An index picked with the chance of each index in proportion to its weight
+    fn pick(&mut self, weights: &[f64], total: f64) -> usize {
+        let mut value = self.next_f64() * total;
+        for i in 0..weights.len() {
+            if value < weights[i] {
+                return i;
+            }
+            value -= weights[i];
+        }
+        return weights.len() - 1;
+    }
+}
+
+pub struct Day {
+    part1: bool,
+    rules: Rules,
+    left_plays: Vec<usize>,
+    right_plays: Vec<usize>,
+    // Set by the options, the parts answer the best plan winning no more than max_wins rounds
+    // or the score against an opponent playing with the weights instead of the guide's score
+    max_wins: Option<usize>,
+    opponent: Option<Vec<f64>>,
+    // The opponent is simulated over trials games instead of giving the expected score
+    trials: Option<usize>,
+    seed: Option<u64>,
+}
+
+impl Day {
+    pub fn instance(part1: bool) -> Day {
+        return Day::with_rules(part1, Rules::puzzle());
+    }
+
+    pub fn with_rules(part1: bool, rules: Rules) -> Day {
+        Day {
+            part1: part1,
+            rules: rules,
+            left_plays: Vec::new(),
+            right_plays: Vec::new(),
+            max_wins: None,
+            opponent: None,
+            trials: None,
+            seed: None,
+        }
+    }
+
+    pub fn score(&self) -> i64 {
+        let count_rounds = self.left_plays.len();
+        let mut total: i64 = 0;
+        for i in 0..count_rounds {
+            let l = self.left_plays[i];
+            let r = self.right_plays[i];
+            if self.part1 {
+                total += self.rules.round_scores[l][r];
+            } else {
+                let new_r = self.rules.right_for_outcome[l][r];
+                total += self.rules.round_scores[l][new_r];
+            }
+        }
+        return total;
+    }
+
+    // The shape right plays in round i of the guide when left plays left
+    fn right_play(&self, i: usize, left: usize) -> usize {
+        if self.part1 {
+            return self.right_plays[i];
+        }
+        return self.rules.right_for_outcome[left][self.right_plays[i]];
+    }
+
+    // The best score against the left plays of the guide winning no more than max_wins rounds
+    // Every round starts with the best shape that does not win, then the rounds where winning
+    // adds the most are won, ties go to the earlier round
+    pub fn best_plan(&self, max_wins: usize) -> Plan {
+        let count_shapes = self.rules.shapes.len();
+        let mut plan = Plan {
+            score: 0,
+            right_plays: Vec::new(),
+            wins: 0,
+        };
+        let mut gains: Vec<(i64, usize, usize)> = Vec::new();
+        for i in 0..self.left_plays.len() {
+            let l = self.left_plays[i];
+            let scores = &self.rules.round_scores[l];
+            let mut best_other = usize::MAX;
+            let mut best_win = usize::MAX;
+            for r in 0..count_shapes {
+                let best = if self.rules.outcome(l, r) == WIN {
+                    &mut best_win
+                } else {
+                    &mut best_other
+                };
+                if *best == usize::MAX || scores[r] > scores[*best] {
+                    *best = r;
+                }
+            }
+            // The rules always have a shape that draws
+            plan.score += scores[best_other];
+            plan.right_plays.push(best_other);
+            let gain = scores[best_win] - scores[best_other];
+            if gain > 0 {
+                gains.push((gain, i, best_win));
+            }
+        }
+        gains.sort_by_key(|&(gain, i, _)| (Reverse(gain), i));
+        for &(gain, i, win) in gains.iter().take(max_wins) {
+            plan.score += gain;
+            plan.right_plays[i] = win;
+            plan.wins += 1;
+        }
+        return plan;
+    }
+
+    // The total of weights, there must be one for each shape, 0 or more and not all 0
+    fn check_weights(&self, weights: &[f64]) -> Result<f64> {
+        if weights.len() != self.rules.shapes.len() {
+            return Err(Error::input(&format!(
+                "Expected {} weights not {}",
+                self.rules.shapes.len(),
+                weights.len()
+            )));
+        }
+        let total_weight: f64 = weights.iter().sum();
+        let valid = weights.iter().all(|&w| w >= 0.0) && total_weight > 0.0;
+        if !valid || !total_weight.is_finite() {
+            return Err(Error::input(
+                "Expected weights of 0 or more that are not all 0",
+            ));
+        }
+        return Ok(total_weight);
+    }
+
+    // The expected score of the guide when left plays each shape with a chance in proportion
+    // to weights instead of the left plays of the guide
+    pub fn expected_score(&self, weights: &[f64]) -> Result<f64> {
+        let total_weight = self.check_weights(weights)?;
+        let mut expected = 0.0;
+        for i in 0..self.right_plays.len() {
+            for l in 0..weights.len() {
+                let r = self.right_play(i, l);
+                let score = self.rules.round_scores[l][r] as f64;
+                expected += score * weights[l] / total_weight;
+            }
+        }
+        return Ok(expected);
+    }
+
+    // Plays the guide count_trials times against a left that picks shapes with a chance in
+    // proportion to weights, one weight for each shape
+    pub fn simulate(&self, weights: &[f64], count_trials: usize, seed: u64) -> Result<Estimate> {
+        let total_weight = self.check_weights(weights)?;
+        if count_trials == 0 {
+            return Err(Error::input("Expected at least one trial"));
+        }
+        let mut random = Random::new(seed);
+        let mut sum = 0.0;
+        let mut sum_squares = 0.0;
+        let mut min = i64::MAX;
+        let mut max = i64::MIN;
+        for _ in 0..count_trials {
+            let mut total = 0;
+            for i in 0..self.right_plays.len() {
+                let l = random.pick(weights, total_weight);
+                total += self.rules.round_scores[l][self.right_play(i, l)];
+            }
+            sum += total as f64;
+            sum_squares += (total as f64) * (total as f64);
+            min = min.min(total);
+            max = max.max(total);
+        }
+        let mean = sum / count_trials as f64;
+        let variance = (sum_squares / count_trials as f64 - mean * mean).max(0.0);
+        return Ok(Estimate {
+            mean,
+            std_dev: variance.sqrt(),
+            min,
+            max,
+        });
+    }
+
+    // The answer of both parts, what it is depends on the options
+    fn answer(&self) -> Result<Answer> {
+        let Some(weights) = &self.opponent else {
+            if self.trials.is_some() || self.seed.is_some() {
+                return Err(Error::input("trials and seed need an opponent"));
+            }
+            return match self.max_wins {
+                Some(max_wins) => Ok(self.best_plan(max_wins).score.into()),
+                None => Ok(self.score().into()),
+            };
+        };
+        if self.max_wins.is_some() {
+            return Err(Error::input("max-wins can not be used with an opponent"));
+        }
+        let Some(count_trials) = self.trials else {
+            if self.seed.is_some() {
+                return Err(Error::input("seed needs trials"));
+            }
+            return Ok(format!("{:.3}", self.expected_score(weights)?).into());
+        };
+        let estimate = self.simulate(weights, count_trials, self.seed.unwrap_or(1))?;
+        return Ok(format!(
+            "mean {:.3} std dev {:.3} min {} max {}",
+            estimate.mean, estimate.std_dev, estimate.min, estimate.max
+        )
+        .into());
+    }
+}
+
+fn parse_option<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    return value
+        .parse()
+        .map_err(|_| Error::input(&format!("Invalid {name} '{value}'")));
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let left_codes = || self.rules.shapes.iter().map(|shape| shape.left_code);
+        let right_codes: Vec<u8> = if self.part1 {
+            self.rules
+                .shapes
+                .iter()
+                .map(|shape| shape.right_code)
+                .collect()
+        } else {
+            self.rules.outcome_codes.to_vec()
+        };
+        for (i, line) in input.lines().enumerate() {
+            // A Y
+            let bytes = line.trim().as_bytes();
+            if bytes.len() != 3 || bytes[1] != ' ' as u8 {
+                return Err(Error::parse(i, 1, "Expected two plays"));
+            }
+            let Some(left) = find_code(left_codes(), bytes[0]) else {
+                let message = format!("Expected {}", list_codes(left_codes()));
+                return Err(Error::parse(i, 1, &message));
+            };
+            let Some(right) = find_code(right_codes.iter().copied(), bytes[2]) else {
+                let message = format!("Expected {}", list_codes(right_codes.iter().copied()));
+                return Err(Error::parse(i, 3, &message));
+            };
+            self.left_plays.push(left);
+            self.right_plays.push(right);
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return self.answer();
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return self.answer();
+    }
+
+    // rules=PATH plays the game in a rules file instead of rock paper scissors
+    // max-wins=K gives the best plan that wins no more than K rounds
+    // opponent=W,W,W gives the expected score against an opponent that plays each shape with a
+    // chance in proportion to its weight, with trials=N (and seed=S) it is simulated N times
+    fn set_option(&mut self, name: &str, value: &str) -> Result<bool> {
+        match name {
+            "rules" => self.rules = Rules::load(value)?,
+            "max-wins" => self.max_wins = Some(parse_option(name, value)?),
+            "opponent" => {
+                let mut weights = Vec::new();
+                for weight in value.split(',') {
+                    weights.push(parse_option(name, weight)?);
+                }
+                self.opponent = Some(weights);
+            }
+            "trials" => self.trials = Some(parse_option(name, value)?),
+            "seed" => self.seed = Some(parse_option(name, value)?),
+            _ => return Ok(false),
+        }
+        return Ok(true);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::Input;
+    use crate::runner::run_part_with;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn part1() {
+        let input: Vec<&str> = vec!["A Y", "B X", "C Z"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.score(), 15);
+    }
+
+    #[test]
+    fn part2() {
+        let input: Vec<&str> = vec!["A Y", "B X", "C Z"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(false);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.score(), 12);
+    }
+
+    #[test]
+    fn rules() {
+        // Rock v Lizard, Rock v Paper, Spock v Scissors
+        let lines = str_array_to_string_array(vec!["A Y", "A W", "E X"]);
+        let mut day = Day::with_rules(true, Rules::rock_paper_scissors_lizard_spock());
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.score(), 4 + 8 + 3);
+        // Spock wins against Rock for more than Paper, Lizard loses for more than Scissors
+        let lines = str_array_to_string_array(vec!["A Z", "A X", "C Y"]);
+        let mut day = Day::with_rules(false, Rules::rock_paper_scissors_lizard_spock());
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.score(), 11 + 4 + 6);
+        let err = day
+            .parse(&Input::from_lines(&str_array_to_string_array(vec!["F X"])))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day00 line 1 column 1: Expected A, B, C, D or E"
+        );
+
+        // The cyclic game of 3 is the puzzle with other codes
+        let lines = str_array_to_string_array(vec!["A B", "B A", "C C"]);
+        let mut day = Day::with_rules(true, Rules::cyclic(3).unwrap());
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.score(), 15);
+        let rules = Rules::cyclic(7).unwrap();
+        assert_eq!(rules.shapes().len(), 7);
+        for l in 0..7 {
+            let wins = (0..7).filter(|&r| rules.outcome(l, r) == WIN).count();
+            assert_eq!(wins, 3);
+        }
+        assert!(Rules::cyclic(4).is_err());
+
+        // Rules::load names the file and the runner sets the day
+        let text = ROCK_PAPER_SCISSORS.replace("Scissors beats Paper", "Paper beats Scissors");
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        let err = Rules::parse(&lines)
+            .unwrap_err()
+            .in_file("rules.txt")
+            .in_day(2);
+        assert_eq!(
+            err.to_string(),
+            "Day02 unexpected input in 'rules.txt': No shape can win against Paper"
+        );
+        let text = ROCK_PAPER_SCISSORS.replace("Paper beats Rock", "Paper beats Stone");
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        let err = Rules::parse(&lines)
+            .unwrap_err()
+            .in_file("rules.txt")
+            .in_day(2);
+        assert_eq!(
+            err.to_string(),
+            "Day02 'rules.txt' line 8 column 13: Unknown shape Stone"
+        );
+    }
+
+    #[test]
+    fn rules_file() {
+        let filename = "data/2022/day02/rock_paper_scissors_lizard_spock.rules";
+        let rules = Rules::load(filename).unwrap();
+        assert_eq!(
+            rules.shapes(),
+            Rules::rock_paper_scissors_lizard_spock().shapes()
+        );
+        // The same rounds as the rules test
+        let input = Input::from_lines(&str_array_to_string_array(vec!["A Y", "A W", "E X"]));
+        let settings = vec![("rules".to_string(), filename.to_string())];
+        let run = run_part_with(&PUZZLE, &settings, &input, 1);
+        assert_eq!(run.answer.unwrap(), Answer::Number(4 + 8 + 3));
+
+        let settings = vec![("rules".to_string(), "missing.rules".to_string())];
+        let run = run_part_with(&PUZZLE, &settings, &input, 1);
+        assert!(run
+            .answer
+            .unwrap_err()
+            .to_string()
+            .starts_with("Could not read 'missing.rules'"));
+        let settings = vec![("rule".to_string(), filename.to_string())];
+        let run = run_part_with(&PUZZLE, &settings, &input, 1);
+        assert_eq!(
+            run.answer.unwrap_err().to_string(),
+            "Day02 unexpected input: There is no option 'rule'"
+        );
+    }
+
+    #[test]
+    fn optimise() {
+        let input: Vec<&str> = vec!["A Y", "B X", "C Z"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        // Paper, Scissors and Rock win every round
+        let plan = day.best_plan(3);
+        assert_eq!((plan.score, plan.wins), (24, 3));
+        assert_eq!(plan.right_plays, vec![1, 2, 0]);
+        assert_eq!(day.best_plan(10), plan);
+        // Draws everywhere
+        assert_eq!(day.best_plan(0).score, 15);
+        // Winning the first two rounds adds 4 each and the last only 1
+        let plan = day.best_plan(1);
+        assert_eq!((plan.score, plan.wins), (19, 1));
+        assert_eq!(plan.right_plays, vec![1, 1, 2]);
+    }
+
+    #[test]
+    fn simulate() {
+        let input: Vec<&str> = vec!["A Y", "B X", "C Z"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance(true);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        // Always Rock : Paper wins, Rock draws and Scissors loses
+        let only_rock = [1.0, 0.0, 0.0];
+        let estimate = day.simulate(&only_rock, 10, 1).unwrap();
+        assert_eq!((estimate.min, estimate.max), (15, 15));
+        assert_eq!(estimate.std_dev, 0.0);
+        assert_eq!(day.expected_score(&only_rock).unwrap(), 15.0);
+
+        // Each shape in turn gives 1 + 2 + 3 for the shapes and 9 for the outcomes
+        let even = [1.0, 1.0, 1.0];
+        assert_eq!(day.expected_score(&even).unwrap(), 15.0);
+        let estimate = day.simulate(&even, 2000, 7).unwrap();
+        assert!((estimate.mean - 15.0).abs() < 0.5);
+        assert!(estimate.min >= 6 && estimate.max <= 24);
+        assert_eq!(day.simulate(&even, 2000, 7).unwrap(), estimate);
+        assert!(day.simulate(&[1.0, 1.0], 10, 1).is_err());
+        assert!(day.simulate(&[0.0, 0.0, 0.0], 10, 1).is_err());
+        assert!(day.simulate(&even, 0, 1).is_err());
+        assert_eq!(
+            day.expected_score(&[1.0, 1.0, 1.0, 1.0])
+                .unwrap_err()
+                .to_string(),
+            "Day00 unexpected input: Expected 3 weights not 4"
+        );
+        assert!(day.expected_score(&[0.0, 0.0, 0.0]).is_err());
+        assert!(day.expected_score(&[1.0, -1.0, 1.0]).is_err());
+        assert!(day.expected_score(&[1.0, f64::NAN, 1.0]).is_err());
+
+        // In part 2 the guide gives the outcomes so only the shapes played change the score
+        // Against Rock : Rock draws, Scissors loses and Paper wins
+        let mut day = Day::instance(false);
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.expected_score(&only_rock).unwrap(), 4.0 + 3.0 + 8.0);
+        assert_eq!(
+            day.expected_score(&[0.0, 1.0, 0.0]).unwrap(),
+            5.0 + 1.0 + 9.0
+        );
+    }
+
+    #[test]
+    fn options() {
+        let input = Input::from_lines(&str_array_to_string_array(vec!["A Y", "B X", "C Z"]));
+        let answer = |settings: Vec<(&str, &str)>| {
+            let settings: Vec<(String, String)> = settings
+                .iter()
+                .map(|&(name, value)| (name.to_string(), value.to_string()))
+                .collect();
+            return run_part_with(&PUZZLE, &settings, &input, 1)
+                .answer
+                .map_err(|err| err.to_string());
+        };
+        assert_eq!(answer(vec![("max-wins", "1")]), Ok(Answer::Number(19)));
+        assert_eq!(
+            answer(vec![("opponent", "1,0,0")]),
+            Ok(Answer::Text("15.000".to_string()))
+        );
+        assert_eq!(
+            answer(vec![("opponent", "1,0,0"), ("trials", "10"), ("seed", "3")]),
+            Ok(Answer::Text(
+                "mean 15.000 std dev 0.000 min 15 max 15".to_string()
+            ))
+        );
+        assert_eq!(
+            answer(vec![("opponent", "1,0")]),
+            Err("Day02 unexpected input: Expected 3 weights not 2".to_string())
+        );
+        assert_eq!(
+            answer(vec![("max-wins", "x")]),
+            Err("Day02 unexpected input: Invalid max-wins 'x'".to_string())
+        );
+        assert!(answer(vec![("trials", "10")]).is_err());
+        assert!(answer(vec![("opponent", "1,1,1"), ("max-wins", "1")]).is_err());
+    }
+}
@@ -0,0 +1,368 @@
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
+
+/*
+
+--- Day 3: Rucksack Reorganization ---
+
+One Elf has the important job of loading all of the rucksacks with supplies for the jungle journey.
+Unfortunately, that Elf didn't quite follow the packing instructions, and so a few items now need to be rearranged.
+
+Each rucksack has two large compartments.
+All items of a given type are meant to go into exactly one of the two compartments.
+The Elf that did the packing failed to follow this rule for exactly one item type per rucksack.
+
+The Elves have made a list of all of the items currently in each rucksack (your puzzle input), but they need your help finding the errors.
+Every item type is identified by a single lowercase or uppercase letter (that is, a and A refer to different types of items).
+
+The list of items for each rucksack is given as characters all on a single line.
+A given rucksack always has the same number of items in each of its two compartments, so the first half of the characters represent items in the first compartment, while the second half of the characters represent items in the second compartment.
+
+For example, suppose you have the following list of contents from six rucksacks:
+
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+The first rucksack contains the items vJrwpWtwJgWrhcsFMMfFFhFp, which means its first compartment contains the items vJrwpWtwJgWr, while the second compartment contains the items hcsFMMfFFhFp.
+The only item type that appears in both compartments is lowercase p.
+The second rucksack's compartments contain jqHRNqRjqzjGDLGL and rsFMfFZSrLrFZsSL.
+The only item type that appears in both compartments is uppercase L.
+The third rucksack's compartments contain PmmdzqPrV and vPwwTWBwg; the only common item type is uppercase P.
+The fourth rucksack's compartments only share item type v.
+The fifth rucksack's compartments only share item type t.
+The sixth rucksack's compartments only share item type s.
+To help prioritize item rearrangement, every item type can be converted to a priority:
+
+Lowercase item types a through z have priorities 1 through 26.
+Uppercase item types A through Z have priorities 27 through 52.
+In the above example, the priority of the item type that appears in both compartments of each rucksack is 16 (p), 38 (L), 42 (P), 22 (v), 20 (t), and 19 (s); the sum of these is 157.
+
+Find the item type that appears in both compartments of each rucksack.
+
+What is the sum of the priorities of those item types?
+
+Your puzzle answer was 7737.
+
+--- Part Two ---
+
+As you finish identifying the misplaced items, the Elves come to you with another issue.
+
+For safety, the Elves are divided into groups of three.
+Every Elf carries a badge that identifies their group.
+For efficiency, within each group of three Elves, the badge is the only item type carried by all three Elves.
+That is, if a group's badge is item type B, then all three Elves will have item type B somewhere in their rucksack, and at most two of the Elves will be carrying any other item type.
+
+The problem is that someone forgot to put this year's updated authenticity sticker on the badges.
+All of the badges need to be pulled out of the rucksacks so the new authenticity stickers can be attached.
+
+Additionally, nobody wrote down which item type corresponds to each group's badges.
+The only way to tell which item type is the right one is by finding the one item type that is common between all three Elves in each group.
+
+Every set of three lines in your list corresponds to a single group, but each group can have a different badge item type.
+So, in the above example, the first group's rucksacks are the first three lines:
+
+vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+And the second group's rucksacks are the next three lines:
+
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+In the first group, the only item type that appears in all three rucksacks is lowercase r; this must be their badges.
+In the second group, their badge item type must be Z.
+
+Priorities for these items must still be found to organize the sticker attachment efforts: here, they are 18 (r) for the first group and 52 (Z) for the second group.
+The sum of these is 70.
+
+Find the item type that corresponds to the badges of each three-Elf group.
+
+What is the sum of the priorities of those item types?
+
+*/
+
+static INPUT_FILE: &str = "data/2022/day03/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 3,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
+
+// a-z have priorities 1 to 26 and A-Z 27 to 52, None for anything else
+pub fn priority(item: u8) -> Option<u32> {
+    return match item {
+        b'a'..=b'z' => Some((item - b'a') as u32 + 1),
+        b'A'..=b'Z' => Some((item - b'A') as u32 + 27),
+        _ => None,
+    };
+}
+
+// The item with a priority, None when it is not from 1 to 52
+pub fn item(priority: u32) -> Option<u8> {
+    return match priority {
+        1..=26 => Some(b'a' + (priority - 1) as u8),
+        27..=52 => Some(b'A' + (priority - 27) as u8),
+        _ => None,
+    };
+}
+
+// A set of items with bit p - 1 set for an item of priority p
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub fn new() -> ItemSet {
+        return ItemSet(0);
+    }
+
+    // Items that are not a-z or A-Z are left out
+    pub fn from_items(items: &[u8]) -> ItemSet {
+        let mut set = ItemSet::new();
+        for &item in items {
+            set.insert(item);
+        }
+        return set;
+    }
+
+    pub fn insert(&mut self, item: u8) {
+        if let Some(p) = priority(item) {
+            self.0 |= 1 << (p - 1);
+        }
+    }
+
+    pub fn contains(&self, item: u8) -> bool {
+        return match priority(item) {
+            Some(p) => self.0 & (1 << (p - 1)) != 0,
+            None => false,
+        };
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        return ItemSet(self.0 & other.0);
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        return ItemSet(self.0 | other.0);
+    }
+
+    pub fn len(&self) -> usize {
+        return self.0.count_ones() as usize;
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0 == 0;
+    }
+
+    // In priority order
+    pub fn items(&self) -> Vec<u8> {
+        let mut items = Vec::new();
+        let mut bits = self.0;
+        while bits != 0 {
+            // Only the bits of priorities 1 to 52 are ever set
+            items.extend(item(bits.trailing_zeros() + 1));
+            bits &= bits - 1;
+        }
+        return items;
+    }
+
+    pub fn sum_priorities(&self) -> i64 {
+        let mut total = 0;
+        let mut bits = self.0;
+        while bits != 0 {
+            total += bits.trailing_zeros() as i64 + 1;
+            bits &= bits - 1;
+        }
+        return total;
+    }
+}
+
+// The items in each half of a rucksack
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rucksack {
+    pub left: ItemSet,
+    pub right: ItemSet,
+}
+
+impl Rucksack {
+    // The first half of the items go in the left compartment
+    pub fn from_items(items: &[u8]) -> Rucksack {
+        let half = items.len() / 2;
+        return Rucksack {
+            left: ItemSet::from_items(&items[0..half]),
+            right: ItemSet::from_items(&items[half..]),
+        };
+    }
+
+    pub fn all(&self) -> ItemSet {
+        return self.left.union(self.right);
+    }
+
+    // The items in both compartments
+    pub fn shared(&self) -> ItemSet {
+        return self.left.intersection(self.right);
+    }
+}
+
+// Each line is only looked at once and a rucksack is two u64 so large inputs parse in
+// linear time without keeping a copy of the items
+pub struct Day {
+    rucksacks: Vec<Rucksack>,
+}
+
+impl Day {
+    pub fn instance() -> Day {
+        Day {
+            rucksacks: Vec::new(),
+        }
+    }
+
+    // The items in both compartments of each rucksack
+    pub fn shared_items(&self) -> Vec<ItemSet> {
+        return self.rucksacks.iter().map(|r| r.shared()).collect();
+    }
+
+    // The items every rucksack of each group has, the rucksacks are in groups of group_size
+    // in input order
+    pub fn group_badges(&self, group_size: usize) -> Result<Vec<ItemSet>> {
+        if group_size == 0 || self.rucksacks.len() % group_size != 0 {
+            return Err(Error::input(&format!(
+                "The rucksacks are not in groups of {group_size}"
+            )));
+        }
+        let mut badges = Vec::new();
+        for group in self.rucksacks.chunks(group_size) {
+            let mut badge = group[0].all();
+            for rucksack in &group[1..] {
+                badge = badge.intersection(rucksack.all());
+            }
+            badges.push(badge);
+        }
+        return Ok(badges);
+    }
+
+    // Each rucksack must have exactly one item in both compartments
+    pub fn sum_priorities(&self) -> Result<i64> {
+        let mut total = 0;
+        for (r, shared) in self.shared_items().iter().enumerate() {
+            if shared.len() != 1 {
+                return Err(Error::input(&format!(
+                    "Rucksack {} has {} items in both compartments instead of one",
+                    r + 1,
+                    shared.len()
+                )));
+            }
+            total += shared.sum_priorities();
+        }
+        return Ok(total);
+    }
+
+    // Every item in both compartments counts however many each rucksack has
+    pub fn sum_priorities_all_shared(&self) -> i64 {
+        return self.shared_items().iter().map(|s| s.sum_priorities()).sum();
+    }
+
+    pub fn sum_priorities_groups(&self, group_size: usize) -> Result<i64> {
+        let badges = self.group_badges(group_size)?;
+        return Ok(badges.iter().map(|b| b.sum_priorities()).sum());
+    }
+
+    pub fn sum_priorities_three(&self) -> Result<i64> {
+        return self.sum_priorities_groups(3);
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.byte_lines().enumerate() {
+            if let Some(c) = line.iter().position(|b| !b.is_ascii_alphabetic()) {
+                return Err(Error::parse(i, c + 1, "Items must be a-z or A-Z"));
+            }
+            self.rucksacks.push(Rucksack::from_items(line));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.sum_priorities()?.into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(self.sum_priorities_three()?.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn part1() {
+        let input: Vec<&str> = vec![
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.sum_priorities().unwrap(), 157);
+        assert_eq!(day.sum_priorities_all_shared(), 157);
+    }
+
+    #[test]
+    fn part2() {
+        let input: Vec<&str> = vec![
+            "vJrwpWtwJgWrhcsFMMfFFhFp",
+            "jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL",
+            "PmmdzqPrVvPwwTWBwg",
+            "wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn",
+            "ttgJtRGJQctTZtZT",
+            "CrZsJsPPZsGzwwsLwLmpwMDw",
+        ];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.sum_priorities_three().unwrap(), 70);
+    }
+
+    #[test]
+    fn item_sets() {
+        assert_eq!(priority(b'p'), Some(16));
+        assert_eq!(priority(b'L'), Some(38));
+        assert_eq!(priority(b'1'), None);
+        assert_eq!(item(38), Some(b'L'));
+        assert_eq!(item(0), None);
+        assert_eq!(item(53), None);
+        let set = ItemSet::from_items(b"vJrwpWtwJgWr");
+        assert_eq!(set.len(), 8);
+        assert!(set.contains(b'J') && !set.contains(b'j'));
+        assert_eq!(set.items(), b"gprtvwJW".to_vec());
+
+        // Every shared item is reported, here a and Z, part 1 expects one in each rucksack
+        let lines = str_array_to_string_array(vec!["aZbcaZde", "abcd", "aXcY", "bXcZ"]);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.shared_items()[0].items(), b"aZ".to_vec());
+        assert_eq!(day.sum_priorities_all_shared(), 1 + 52);
+        assert_eq!(
+            day.sum_priorities().unwrap_err().to_string(),
+            "Day00 unexpected input: Rucksack 1 has 2 items in both compartments instead of one"
+        );
+        // In pairs the badges are a b c d and c X
+        let badges = day.group_badges(2).unwrap();
+        assert_eq!(badges[0].items(), b"abcd".to_vec());
+        assert_eq!(badges[1].items(), b"cX".to_vec());
+        assert_eq!(day.sum_priorities_groups(4).unwrap(), 3);
+        assert!(day.group_badges(3).is_err());
+    }
+}
@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -70,17 +72,16 @@ In how many assignment pairs do the ranges overlap?
 
 */
 
-static INPUT_FILE: &str = "data/day04/input.txt";
+static INPUT_FILE: &str = "data/2022/day04/input.txt";
 
-pub fn run() {
-    println!("Day04: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day04: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 4,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     first_mins: Vec<u32>,
     first_maxs: Vec<u32>,
     second_mins: Vec<u32>,
@@ -88,9 +89,8 @@ struct Day {
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             first_mins: Vec::new(),
             first_maxs: Vec::new(),
             second_mins: Vec::new(),
@@ -98,47 +98,7 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.count_contains();
-            println!("Day04: Result1 {result1}");
-            let expected = 602;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.count_overlaps();
-            println!("Day04: Result2 {result2}");
-            let expected = 891;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            // 2-6,4-8
-            let comma_toks: Vec<&str> = line.split(',').collect();
-            let toks: Vec<&str> = comma_toks[0].split('-').collect();
-            let min = toks[0].parse().expect("Not a number");
-            let max = toks[1].parse().expect("Not a number");
-            self.first_mins.push(min);
-            self.first_maxs.push(max);
-            assert!(max >= min);
-            let toks: Vec<&str> = comma_toks[1].split('-').collect();
-            let min = toks[0].parse().expect("Not a number");
-            let max = toks[1].parse().expect("Not a number");
-            self.second_mins.push(min);
-            self.second_maxs.push(max);
-            assert!(max >= min);
-        }
-    }
-
-    fn count_contains(&self) -> i64 {
+    pub fn count_contains(&self) -> i64 {
         let count = self.first_mins.len();
         let mut total = 0_i64;
         for i in 0..count {
@@ -155,7 +115,7 @@ impl Day {
         return total;
     }
 
-    fn count_overlaps(&self) -> i64 {
+    pub fn count_overlaps(&self) -> i64 {
         let count = self.first_mins.len();
         let mut total = 0_i64;
         for i in 0..count {
@@ -165,18 +125,63 @@ impl Day {
             let second_max = self.second_maxs[i];
             if first_max >= second_min && first_min <= second_max {
                 total += 1;
-            } else if second_max >= first_min && second_min <= first_max {
-                total += 1;
             }
         }
         return total;
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            // 2-6,4-8
+            let comma_toks: Vec<&str> = line.split(',').collect();
+            if comma_toks.len() != 2 {
+                return Err(Error::parse(i, 1, "Expected two ranges"));
+            }
+            let toks: Vec<&str> = comma_toks[0].split('-').collect();
+            let min = parse_field(i, line, &toks, 0)?;
+            let max = parse_field(i, line, &toks, 1)?;
+            if max < min {
+                return Err(Error::parse_token(
+                    i,
+                    line,
+                    comma_toks[0],
+                    "Range is backwards",
+                ));
+            }
+            self.first_mins.push(min);
+            self.first_maxs.push(max);
+            let toks: Vec<&str> = comma_toks[1].split('-').collect();
+            let min = parse_field(i, line, &toks, 0)?;
+            let max = parse_field(i, line, &toks, 1)?;
+            if max < min {
+                return Err(Error::parse_token(
+                    i,
+                    line,
+                    comma_toks[1],
+                    "Range is backwards",
+                ));
+            }
+            self.second_mins.push(min);
+            self.second_maxs.push(max);
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.count_contains().into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(self.count_overlaps().into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -184,8 +189,8 @@ mod tests {
             "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8",
         ];
         let lines = str_array_to_string_array(input);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.count_contains(), 2);
     }
 
@@ -195,8 +200,8 @@ mod tests {
             "2-4,6-8", "2-3,4-5", "5-7,7-9", "2-8,3-7", "6-6,4-6", "2-6,4-8",
         ];
         let lines = str_array_to_string_array(input);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.count_overlaps(), 4);
     }
 }
@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::{blocks, scan};
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -116,16 +119,16 @@ After the rearrangement procedure completes, what crate ends up on top of each s
 
 */
 
-static INPUT_FILE: &str = "data/day05/input.txt";
+static INPUT_FILE: &str = "data/2022/day05/input.txt";
 
-pub fn run() {
-    println!("Day05: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day05: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 5,
+    input_file: INPUT_FILE,
+    instance: |part1| Box::new(Day::instance(part1)),
+};
 
-struct Day {
+pub struct Day {
     part1: bool,
     stacks: Vec<Vec<u8>>,
     move_counts: Vec<usize>,
@@ -134,7 +137,7 @@ struct Day {
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance(part1: bool) -> Day {
         Day {
             part1: part1,
             stacks: Vec::new(),
@@ -144,79 +147,7 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.rearrange_top_crates();
-            println!("Day05: Result1 {result1}");
-            let expected = "ZSQVCCJLL";
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.rearrange_top_crates();
-            println!("Day05: Result2 {result2}");
-            let expected = "QZFJRWHGS";
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let count = lines.len();
-        let mut do_stack = true;
-        let mut do_moves = false;
-        for i in 0..count {
-            let line = &lines[i];
-            if line.is_empty() {
-                assert!(do_stack == false);
-                assert!(do_moves == false);
-                do_moves = true;
-                continue;
-            }
-            if do_stack {
-                if line.starts_with(" 1") {
-                    //  1   2   3
-                    do_stack = false;
-                    continue;
-                }
-                //[Z] [M] [P]
-                //01234567890
-                let count_stacks = 1 + (line.len() - 2) / 4;
-                if count_stacks > self.stacks.len() {
-                    self.stacks.resize(count_stacks, Vec::new());
-                }
-                for s in 0..count_stacks {
-                    let index = 1 + s * 4;
-                    let value = line.as_bytes()[index];
-                    if value != 32 {
-                        assert!(value >= 'A' as u8);
-                        assert!(value <= 'Z' as u8);
-                        self.stacks[s].push(value);
-                    }
-                }
-                continue;
-            }
-            if do_moves {
-                // move 1 from 2 to 1
-                assert!(line.starts_with("move"));
-                let f = line.find("from").unwrap();
-                let move_count: usize = line[4..f].trim().parse().expect("Not a number");
-                let t = line.find("to").unwrap();
-                let move_from: usize = line[f + 4..t].trim().parse().expect("Not a number");
-                let move_to: usize = line[t + 2..].trim().parse().expect("Not a number");
-                self.move_counts.push(move_count);
-                self.move_froms.push(move_from - 1);
-                self.move_tos.push(move_to - 1);
-                continue;
-            }
-        }
-    }
-
-    fn rearrange_top_crates(&mut self) -> String {
+    pub fn rearrange_top_crates(&mut self) -> String {
         let count_stacks = self.stacks.len();
         let count_moves = self.move_counts.len();
         for m in 0..count_moves {
@@ -241,10 +172,80 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        let blocks = blocks(&lines);
+        if blocks.len() != 2 {
+            return Err(Error::input(
+                "Expected the stacks then the moves after a blank line",
+            ));
+        }
+        let (stack_start, stack_lines) = blocks[0];
+        let numbers_index = stack_start + stack_lines.len() - 1;
+        //  1   2   3
+        if !lines[numbers_index].starts_with(" 1") {
+            return Err(Error::parse(
+                numbers_index,
+                1,
+                "Missing the stack numbers line",
+            ));
+        }
+        for i in stack_start..numbers_index {
+            let line = &lines[i];
+            //[Z] [M] [P]
+            //01234567890
+            if line.len() < 3 {
+                return Err(Error::parse(i, 1, "Expected a line of crates"));
+            }
+            let count_stacks = 1 + (line.len() - 2) / 4;
+            if count_stacks > self.stacks.len() {
+                self.stacks.resize(count_stacks, Vec::new());
+            }
+            for s in 0..count_stacks {
+                let index = 1 + s * 4;
+                let value = line.as_bytes()[index];
+                if value != 32 {
+                    if !value.is_ascii_uppercase() {
+                        return Err(Error::parse(i, index + 1, "Crates must be A-Z"));
+                    }
+                    self.stacks[s].push(value);
+                }
+            }
+        }
+
+        let (moves_start, move_lines) = blocks[1];
+        for (m, line) in move_lines.iter().enumerate() {
+            let i = moves_start + m;
+            let fields = scan(i, line, "move {} from {} to {}")?;
+            let move_count: usize = fields.number(0)?;
+            let move_from: usize = fields.number(1)?;
+            let move_to: usize = fields.number(2)?;
+            for (f, stack) in [(1, move_from), (2, move_to)] {
+                if stack == 0 || stack > self.stacks.len() {
+                    return Err(Error::parse_token(i, line, fields.str(f), "Unknown stack"));
+                }
+            }
+            self.move_counts.push(move_count);
+            self.move_froms.push(move_from - 1);
+            self.move_tos.push(move_to - 1);
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.rearrange_top_crates().into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(self.rearrange_top_crates().into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -261,7 +262,7 @@ mod tests {
         ];
         let lines = str_array_to_string_array(input);
         let mut day = Day::instance(true);
-        day.parse(&lines);
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.rearrange_top_crates(), "CMZ");
     }
 
@@ -280,7 +281,7 @@ mod tests {
         ];
         let lines = str_array_to_string_array(input);
         let mut day = Day::instance(false);
-        day.parse(&lines);
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.rearrange_top_crates(), "MCD");
     }
 }
@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -62,58 +64,26 @@ zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw: first marker after character 26
 How many characters need to be processed before the first start-of-message marker is detected?
 */
 
-static INPUT_FILE: &str = "data/day06/input.txt";
+static INPUT_FILE: &str = "data/2022/day06/input.txt";
 
-pub fn run() {
-    println!("Day06: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day06: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 6,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
-    strings: Vec<Vec<u8>>,
+pub struct Day {
+    buffer: Vec<u8>,
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
-        Day {
-            part1: part1,
-            strings: Vec::new(),
-        }
-    }
-
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.find_different(4);
-            println!("Day06: Result1 {result1}");
-            let expected = 1093;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.find_different(14);
-            println!("Day06: Result2 {result2}");
-            let expected = 3534;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            self.strings.push(line.as_bytes().to_vec());
-        }
-        assert_eq!(self.strings.len(), 1);
+    pub fn instance() -> Day {
+        Day { buffer: Vec::new() }
     }
 
-    fn find_different(&self, length: usize) -> i64 {
-        let line = &self.strings[0];
+    pub fn find_different(&self, length: usize) -> i64 {
+        let line = &self.buffer;
         let count = line.len();
         let mut four_cc = [0; 14];
         for i in 0..length {
@@ -143,12 +113,42 @@ impl Day {
         }
         return -1;
     }
+
+    fn marker(&self, length: usize) -> Result<Answer> {
+        if self.buffer.len() < length {
+            return Err(Error::input("The datastream is too short"));
+        }
+        let marker = self.find_different(length);
+        if marker < 0 {
+            return Err(Error::input("No marker found"));
+        }
+        return Ok(marker.into());
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&[u8]> = input.byte_lines().collect();
+        if lines.len() != 1 {
+            return Err(Error::input("Expected a single line"));
+        }
+        self.buffer = lines[0].to_vec();
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return self.marker(4);
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return self.marker(14);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -162,8 +162,8 @@ mod tests {
         for test_data in test_datas {
             let input = vec![test_data.0];
             let lines = str_array_to_string_array(input);
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.find_different(4), test_data.1);
         }
     }
@@ -180,8 +180,8 @@ mod tests {
         for test_data in test_datas {
             let input = vec![test_data.0];
             let lines = str_array_to_string_array(input);
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.find_different(14), test_data.1);
         }
     }
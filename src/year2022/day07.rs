@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 --- Day 7: No Space Left On Device ---
@@ -115,50 +117,51 @@ Find the smallest directory that, if deleted, would free up enough space on the
 
 */
 
-static INPUT_FILE: &str = "data/day07/input.txt";
+static INPUT_FILE: &str = "data/2022/day07/input.txt";
 
-pub fn run() {
-    println!("Day07: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day07: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 7,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     dir_dir_totals: Vec<i64>,
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             dir_dir_totals: Vec::new(),
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.sum_directories(100000);
-            println!("Day07: Result1 {result1}");
-            let expected = 1243729;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
+    pub fn sum_directories(&self, max_dir_size: i64) -> i64 {
+        let mut total = 0_i64;
+        for v in &self.dir_dir_totals {
+            if *v <= max_dir_size {
+                total += v;
             }
-        } else {
-            let result2 = self.find_smallest(70000000, 30000000);
-            println!("Day07: Result2 {result2}");
-            let expected = 4443914;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
+        }
+        return total;
+    }
+
+    pub fn find_smallest(&self, disk_size: i64, update_size: i64) -> i64 {
+        let mut min_to_delete = std::i64::MAX;
+        let min_unused_space = disk_size - self.dir_dir_totals[0];
+        for v in &self.dir_dir_totals {
+            if min_unused_space + v >= update_size {
+                min_to_delete = std::cmp::min(min_to_delete, *v);
             }
         }
+        return min_to_delete;
     }
+}
 
-    fn parse(&mut self, lines: &Vec<String>) {
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
         let mut dir_parents: Vec<usize> = Vec::new();
         let mut dir_files: Vec<Vec<(&str, i64)>> = Vec::new();
         let mut dir_dirs: Vec<Vec<(&str, usize)>> = Vec::new();
@@ -169,18 +172,20 @@ impl Day {
         dir_dirs.push(Vec::new());
         dir_file_totals.push(0);
         self.dir_dir_totals.push(0);
-        for line in lines {
+        for (i, line) in lines.iter().enumerate() {
             // lines begin with $ are commands you executed, very much like some modern computers:
-            if line.starts_with("$ cd") {
+            if let Some(dir_name) = line.strip_prefix("$ cd ") {
                 // cd x : look in current directory for the directory x and make it current directory.
                 // cd .. : moves up one level
                 // cd / : switches the current directory to the outermost directory, /.
-                let dir_name = &line[5..];
                 if dir_name == "/" {
                     current_dir = 0;
                     continue;
                 }
                 if dir_name == ".." {
+                    if current_dir == 0 {
+                        return Err(Error::parse_token(i, line, dir_name, "Already at /"));
+                    }
                     current_dir = dir_parents[current_dir];
                     continue;
                 }
@@ -192,15 +197,16 @@ impl Day {
                         break;
                     }
                 }
-                assert!(found);
+                if !found {
+                    return Err(Error::parse_token(i, line, dir_name, "Unknown directory"));
+                }
                 continue;
             } else if line.starts_with("$ ls") {
                 // ls : lists all files and directories immediately contained by current directory:
                 continue;
             }
-            if line.starts_with("dir ") {
+            if let Some(dir_name) = line.strip_prefix("dir ") {
                 // dir xyz : directory contains a directory named xyz.
-                let dir_name = &line[4..];
                 let mut dir_index = std::usize::MAX;
                 for sd in &dir_dirs[current_dir] {
                     if sd.0 == dir_name {
@@ -221,7 +227,10 @@ impl Day {
             }
             // 123 abc : directory contains a file named abc with size 123.
             let toks: Vec<&str> = line.split(" ").collect();
-            let file_size = toks[0].parse().expect("Not a number");
+            if toks.len() != 2 {
+                return Err(Error::parse(i, 1, "Expected a file size and name"));
+            }
+            let file_size = parse_field(i, line, &toks, 0)?;
             let file_name = toks[1];
             let mut found = false;
             for sd in &dir_files[current_dir] {
@@ -250,34 +259,26 @@ impl Day {
             }
             self.dir_dir_totals[d] = sub_dir_total;
         }
+        return Ok(());
     }
 
-    fn sum_directories(&self, max_dir_size: i64) -> i64 {
-        let mut total = 0_i64;
-        for v in &self.dir_dir_totals {
-            if *v <= max_dir_size {
-                total += v;
-            }
-        }
-        return total;
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.sum_directories(100000).into());
     }
 
-    fn find_smallest(&self, disk_size: i64, update_size: i64) -> i64 {
-        let mut min_to_delete = std::i64::MAX;
-        let min_unused_space = disk_size - self.dir_dir_totals[0];
-        for v in &self.dir_dir_totals {
-            if min_unused_space + v >= update_size {
-                min_to_delete = std::cmp::min(min_to_delete, *v);
-            }
+    fn part2(&mut self) -> Result<Answer> {
+        let smallest = self.find_smallest(70000000, 30000000);
+        if smallest == std::i64::MAX {
+            return Err(Error::input("No directory is big enough to delete"));
         }
-        return min_to_delete;
+        return Ok(smallest.into());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -310,8 +311,8 @@ mod tests {
             95437,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.sum_directories(100000), input.1);
     }
 
@@ -346,8 +347,8 @@ mod tests {
             24933642,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.find_smallest(70000000, 30000000), input.1);
     }
 }
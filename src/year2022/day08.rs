@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::Result;
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -84,20 +87,18 @@ What is the highest scenic score possible for any tree?
 
 */
 
-static INPUT_FILE: &str = "data/day08/input.txt";
+static INPUT_FILE: &str = "data/2022/day08/input.txt";
 
-pub fn run() {
-    println!("Day08: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day08: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 8,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
-    grid: Vec<u8>,
-    visibility: Vec<u8>,
-    dim: usize,
+pub struct Day {
+    grid: Grid<u8>,
+    visibility: Grid<u8>,
 }
 
 impl Day {
@@ -106,191 +107,77 @@ impl Day {
     const FROM_UP: u8 = 4;
     const FROM_DOWN: u8 = 8;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
-            grid: Vec::new(),
-            visibility: Vec::new(),
-            dim: 0,
+            grid: Grid::new(0, 0, 0),
+            visibility: Grid::new(0, 0, 0),
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.count_visible();
-            println!("Day08: Result1 {result1}");
-            let expected = 1792;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.compute_scenic();
-            println!("Day08: Result2 {result2}");
-            let expected = 334880;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
+    // Marks the trees taller than every tree before them along a line of trees
+    fn mark_visible(&mut self, line: impl Iterator<Item = (usize, usize)>, from: u8) {
+        let mut highest: u8 = 0;
+        for xy in line {
+            let value = self.grid[xy];
+            if value > highest {
+                self.visibility[xy] |= from;
+                highest = value;
             }
         }
     }
 
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut y = 0;
-        self.dim = lines.len();
-        let dim = self.dim;
-        self.grid.resize(dim * dim, 0);
-        self.visibility.resize(dim * dim, 0);
-        for line in lines {
-            let source = line.as_bytes().to_vec();
-            for x in 0..dim {
-                assert_eq!(source.len(), dim);
-                let value = source[x] as u8 - '0' as u8;
-                let index = y * dim + x;
-                self.grid[index] = value + 1;
-            }
-            y += 1;
-        }
-        self.compute_visibility();
-    }
-
     fn compute_visibility(&mut self) {
-        let dim = self.dim;
-        // Test if visible from left
-        for y in 0..dim {
-            let mut highest: u8 = 0;
-            for x in 0..dim {
-                let index = y * dim + x;
-                let value = self.grid[index];
-                if value > highest {
-                    self.visibility[index] |= Day::FROM_LEFT;
-                    highest = value;
-                }
-            }
+        let width = self.grid.width();
+        let height = self.grid.height();
+        for y in 0..height {
+            self.mark_visible((0..width).map(|x| (x, y)), Day::FROM_LEFT);
+            self.mark_visible((0..width).rev().map(|x| (x, y)), Day::FROM_RIGHT);
         }
-        // Test if visible from right
-        for y in 0..dim {
-            let mut highest: u8 = 0;
-            for x in 0..dim {
-                let index = y * dim + (dim - 1 - x);
-                let value = self.grid[index];
-                if value > highest {
-                    self.visibility[index] |= Day::FROM_RIGHT;
-                    highest = value;
-                }
-            }
-            // Test if visible from top
-            for x in 0..dim {
-                let mut highest: u8 = 0;
-                for y in 0..dim {
-                    let index = y * dim + x;
-                    let value = self.grid[index];
-                    if value > highest {
-                        self.visibility[index] |= Day::FROM_UP;
-                        highest = value;
-                    }
-                }
-            }
-            // Test if visible from bottom
-            for x in 0..dim {
-                let mut highest: u8 = 0;
-                for y in 0..dim {
-                    let index = (dim - 1 - y) * dim + x;
-                    let value = self.grid[index];
-                    if value > highest {
-                        self.visibility[index] |= Day::FROM_DOWN;
-                        highest = value;
-                    }
-                }
-            }
+        for x in 0..width {
+            self.mark_visible((0..height).map(|y| (x, y)), Day::FROM_UP);
+            self.mark_visible((0..height).rev().map(|y| (x, y)), Day::FROM_DOWN);
         }
     }
 
-    fn count_visible(&self) -> i64 {
-        let mut total = 0_i64;
-        let dim = self.dim;
-        for y in 0..dim {
-            for x in 0..dim {
-                let index = y * dim + x;
-                if self.visibility[index] != 0 {
-                    total += 1;
-                }
-            }
-        }
-        return total;
+    pub fn count_visible(&self) -> i64 {
+        return self.visibility.cells().iter().filter(|&&v| v != 0).count() as i64;
     }
 
     // stop at edge or first tree that is same height or taller than the starting tree
-    fn compute_scenic_left(&self, x: usize, y: usize) -> i64 {
-        let dim = self.dim;
-        let mut index = y * dim + x;
-        let start_height = self.grid[index];
+    fn compute_scenic_direction(&self, x: usize, y: usize, step: (i64, i64)) -> i64 {
+        let start_height = self.grid[(x, y)];
         let mut count = 0;
-        for _i in 0..x {
+        let mut xy = (x, y);
+        while let Some(next) = self.grid.step(xy.0, xy.1, step) {
             count += 1;
-            index -= 1;
-            let height = self.grid[index];
-            if height >= start_height {
+            if self.grid[next] >= start_height {
                 break;
             }
+            xy = next;
         }
         return count;
     }
 
+    fn compute_scenic_left(&self, x: usize, y: usize) -> i64 {
+        return self.compute_scenic_direction(x, y, (-1, 0));
+    }
+
     fn compute_scenic_right(&self, x: usize, y: usize) -> i64 {
-        let dim = self.dim;
-        let mut index = y * dim + x;
-        let start_height = self.grid[index];
-        let mut count = 0;
-        for _i in x + 1..dim {
-            count += 1;
-            index += 1;
-            let height = self.grid[index];
-            if height >= start_height {
-                break;
-            }
-        }
-        return count;
+        return self.compute_scenic_direction(x, y, (1, 0));
     }
 
     fn compute_scenic_up(&self, x: usize, y: usize) -> i64 {
-        let dim = self.dim;
-        let mut index = y * dim + x;
-        let start_height = self.grid[index];
-        let mut count = 0;
-        for _i in 0..y {
-            count += 1;
-            index -= dim;
-            let height = self.grid[index];
-            if height >= start_height {
-                break;
-            }
-        }
-        return count;
+        return self.compute_scenic_direction(x, y, (0, -1));
     }
 
     fn compute_scenic_down(&self, x: usize, y: usize) -> i64 {
-        let dim = self.dim;
-        let mut index = y * dim + x;
-        let start_height = self.grid[index];
-        let mut count = 0;
-        for _i in y + 1..dim {
-            count += 1;
-            index += dim;
-            let height = self.grid[index];
-            if height >= start_height {
-                break;
-            }
-        }
-        return count;
+        return self.compute_scenic_direction(x, y, (0, 1));
     }
 
-    fn compute_scenic(&self) -> i64 {
-        let dim = self.dim;
+    pub fn compute_scenic(&self) -> i64 {
         let mut max_scenic = 0;
-        for y in 0..dim {
-            for x in 0..dim {
+        for y in 0..self.grid.height() {
+            for x in 0..self.grid.width() {
                 let l = self.compute_scenic_left(x, y);
                 let r = self.compute_scenic_right(x, y);
                 let u = self.compute_scenic_up(x, y);
@@ -305,17 +192,38 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        // Heights are stored 1 higher so the edge trees are taller than the start height of 0
+        self.grid = Grid::parse(&lines, "Tree heights must be 0-9", |_, _, c| {
+            c.is_ascii_digit().then(|| c - b'0' + 1)
+        })?;
+        self.visibility = Grid::new(self.grid.width(), self.grid.height(), 0);
+        self.compute_visibility();
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.count_visible().into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(self.compute_scenic().into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
         let input: (Vec<&str>, i64) = (vec!["30373", "25512", "65332", "33549", "35390"], 21);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.count_visible(), input.1);
     }
 
@@ -323,8 +231,8 @@ mod tests {
     fn part2() {
         let input: (Vec<&str>, i64) = (vec!["30373", "25512", "65332", "33549", "35390"], 8);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.compute_scenic(), input.1);
     }
 
@@ -335,8 +243,8 @@ mod tests {
         let results = [1, 2];
         let xys: [(usize, usize); 2] = [(2, 1), (2, 3)];
         for i in 0..results.len() {
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.compute_scenic_left(xys[i].0, xys[i].1), results[i]);
         }
     }
@@ -348,8 +256,8 @@ mod tests {
         let results = [2, 2];
         let xys: [(usize, usize); 2] = [(2, 1), (2, 3)];
         for i in 0..results.len() {
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.compute_scenic_right(xys[i].0, xys[i].1), results[i]);
             if i == 0 {
                 break;
@@ -364,8 +272,8 @@ mod tests {
         let results = [1, 2];
         let xys: [(usize, usize); 2] = [(2, 1), (2, 3)];
         for i in 0..results.len() {
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.compute_scenic_up(xys[i].0, xys[i].1), results[i]);
         }
     }
@@ -377,8 +285,8 @@ mod tests {
         let results = [2, 1];
         let xys: [(usize, usize); 2] = [(2, 1), (2, 3)];
         for i in 0..results.len() {
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.compute_scenic_down(xys[i].0, xys[i].1), results[i]);
             if i == 0 {
                 break;
@@ -1,4 +1,8 @@
-use crate::file_to_vec;
+use crate::error::{parse_number, Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::render::Frames;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -708,77 +712,65 @@ How many positions does the tail of the rope visit at least once?
 
 */
 
-static INPUT_FILE: &str = "data/day09/input.txt";
+static INPUT_FILE: &str = "data/2022/day09/input.txt";
 
-pub fn run() {
-    println!("Day09: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day09: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 9,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     move_dxs: Vec<i32>,
     move_dys: Vec<i32>,
     step_counts: Vec<i32>,
+    // The area the head moves over, the other knots stay inside it
+    head_min: (i32, i32),
+    head_max: (i32, i32),
+    frames: Option<Frames>,
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             move_dxs: Vec::new(),
             move_dys: Vec::new(),
             step_counts: Vec::new(),
+            head_min: (0, 0),
+            head_max: (0, 0),
+            frames: None,
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.count_tail_positions(2);
-            println!("Day09: Result1 {result1}");
-            let expected = 6087;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.count_tail_positions(10);
-            println!("Day09: Result2 {result2}");
-            let expected = 2493;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
+    // The knots over the places the tail has been, y goes up so the rows are flipped
+    fn send_frame(&mut self, knot_xs: &[i32], knot_ys: &[i32], tail_xys: &[(i32, i32)]) {
+        let Some(mut frames) = self.frames.take() else {
+            return;
+        };
+        let (min_x, min_y) = self.head_min;
+        let (max_x, max_y) = self.head_max;
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+        let mut image = Grid::new(width, height, [20, 20, 30]);
+        let pixel = |x: i32, y: i32| ((x - min_x) as usize, (max_y - y) as usize);
+        for &(x, y) in tail_xys {
+            image[pixel(x, y)] = [70, 70, 110];
         }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            // R 4
-            let bytes = line.as_bytes();
-            let move_dir = bytes[0];
-            let mut dx = 0;
-            let mut dy = 0;
-            if move_dir == 'R' as u8 {
-                dx = 1;
-            } else if move_dir == 'L' as u8 {
-                dx = -1;
-            } else if move_dir == 'U' as u8 {
-                dy = 1;
-            } else if move_dir == 'D' as u8 {
-                dy = -1;
-            }
-            self.move_dxs.push(dx);
-            self.move_dys.push(dy);
-            let step_count: i32 = line[2..].parse().expect("Not a number");
-            self.step_counts.push(step_count);
+        // The head is drawn last so it is on top
+        for k in (0..knot_xs.len()).rev() {
+            let colour = if k == 0 {
+                [230, 80, 80]
+            } else {
+                [230, 190, 90]
+            };
+            image[pixel(knot_xs[k], knot_ys[k])] = colour;
         }
+        frames(&image);
+        self.frames = Some(frames);
     }
 
-    fn count_tail_positions(&self, rope_length: usize) -> i64 {
+    pub fn count_tail_positions(&mut self, rope_length: usize) -> i64 {
         let count = self.move_dxs.len();
         let mut knot_xs = Vec::new();
         let mut knot_ys = Vec::new();
@@ -824,16 +816,66 @@ impl Day {
                 if !tail_xys.contains(&(tail_x, tail_y)) {
                     tail_xys.push((tail_x, tail_y));
                 }
+                self.send_frame(&knot_xs, &knot_ys, &tail_xys);
             }
         }
         return tail_xys.len() as i64;
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let mut head = (0, 0);
+        for (i, line) in input.lines().enumerate() {
+            // R 4
+            let bytes = line.as_bytes();
+            if bytes.len() < 3 || bytes[1] != ' ' as u8 {
+                return Err(Error::parse(i, 1, "Expected a direction and step count"));
+            }
+            let move_dir = bytes[0];
+            let mut dx = 0;
+            let mut dy = 0;
+            if move_dir == 'R' as u8 {
+                dx = 1;
+            } else if move_dir == 'L' as u8 {
+                dx = -1;
+            } else if move_dir == 'U' as u8 {
+                dy = 1;
+            } else if move_dir == 'D' as u8 {
+                dy = -1;
+            } else {
+                return Err(Error::parse(i, 1, "Direction must be R, L, U or D"));
+            }
+            self.move_dxs.push(dx);
+            self.move_dys.push(dy);
+            let step_count: i32 = parse_number(i, line, &line[2..])?;
+            self.step_counts.push(step_count);
+            let steps = step_count.max(0);
+            head = (head.0 + dx * steps, head.1 + dy * steps);
+            self.head_min = (self.head_min.0.min(head.0), self.head_min.1.min(head.1));
+            self.head_max = (self.head_max.0.max(head.0), self.head_max.1.max(head.1));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(self.count_tail_positions(2).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(self.count_tail_positions(10).into());
+    }
+
+    fn set_frames(&mut self, frames: Frames) -> bool {
+        self.frames = Some(frames);
+        return true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -842,8 +884,8 @@ mod tests {
             13,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.count_tail_positions(2), input.1);
     }
 
@@ -854,8 +896,8 @@ mod tests {
             36,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.count_tail_positions(10), input.1);
     }
 }
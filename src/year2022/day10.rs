@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{parse_number, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -349,85 +351,41 @@ What eight capital letters appear on your CRT?
 
 */
 
-static INPUT_FILE: &str = "data/day10/input.txt";
+static INPUT_FILE: &str = "data/2022/day10/input.txt";
 
-pub fn run() {
-    println!("Day10: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day10: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 10,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
-    strings: Vec<Vec<u8>>,
+pub struct Day {
+    // The value of each addx, None for a noop
+    operands: Vec<Option<i64>>,
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
-            strings: Vec::new(),
-        }
-    }
-
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day10: Result1 {result1}");
-            let expected = 17380;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day10: Result2");
-            for line in &result2 {
-                println!("{}", line);
-            }
-            // FGCUZREC
-            let expected: Vec<&str> = vec![
-                "####..##...##..#..#.####.###..####..##..",
-                "#....#..#.#..#.#..#....#.#..#.#....#..#.",
-                "###..#....#....#..#...#..#..#.###..#....",
-                "#....#.##.#....#..#..#...###..#....#....",
-                "#....#..#.#..#.#..#.#....#.#..#....#..#.",
-                "#.....###..##...##..####.#..#.####..##..",
-            ];
-            for l in 0..6 {
-                if result2[l] != expected[l] {
-                    panic!("Part2 is broken {} != {}", result2[l], expected[l]);
-                }
-            }
+            operands: Vec::new(),
         }
     }
 
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            self.strings.push(line.as_bytes().to_vec());
-        }
-    }
-
-    fn part1(&self) -> i64 {
-        let count = self.strings.len();
+    pub fn part1(&self) -> i64 {
+        let count = self.operands.len();
         let mut total = 0_i64;
         let mut cycle = 1;
         let mut reg_x = 1;
         let mut period = 20;
         for i in 0..count {
-            let line = &self.strings[i];
+            let operand = self.operands[i];
             period -= 1;
             if period == 0 {
                 total += cycle * reg_x;
                 period = 40;
             }
-            if line[0] == 'n' as u8 {
-                // noop takes one cycle to complete.
-                cycle += 1;
-            } else {
+            if let Some(value) = operand {
                 // addx V takes two cycles to complete.
                 // After two cycles, the X register is increased by the value V. (V can be negative.)
                 cycle += 1;
@@ -437,13 +395,11 @@ impl Day {
                     period = 40;
                 }
                 cycle += 1;
-                let value: i64 = std::str::from_utf8(&line[5..])
-                    .unwrap()
-                    .parse()
-                    .expect("Not a number");
                 reg_x += value;
+            } else {
+                // noop takes one cycle to complete.
+                cycle += 1;
             }
-            if !self.part1 {}
             if cycle > 220 {
                 return total;
             }
@@ -451,8 +407,8 @@ impl Day {
         return total;
     }
 
-    fn part2(&self) -> Vec<String> {
-        let count = self.strings.len();
+    pub fn part2(&self) -> Vec<String> {
+        let count = self.operands.len();
         let mut pixel_pos = 0;
         let mut spirte_left = 0;
         let mut spirte_right = 2;
@@ -460,21 +416,14 @@ impl Day {
         screen.resize(6, String::new());
         let mut y = 0;
         for i in 0..count {
-            let line = &self.strings[i];
+            let operand = self.operands[i];
             if spirte_left <= pixel_pos && pixel_pos <= spirte_right {
                 screen[y].push('#');
             } else {
                 screen[y].push('.');
             }
-            if line[0] == 'n' as u8 {
-                // noop takes one cycle to complete.
-                pixel_pos += 1;
-            } else {
+            if let Some(value) = operand {
                 // addx V takes two cycles to complete.
-                let value: i64 = std::str::from_utf8(&line[5..])
-                    .unwrap()
-                    .parse()
-                    .expect("Not a number");
                 // After two cycles, the X register is increased by the value V. (V can be negative.)
                 pixel_pos += 1;
                 if pixel_pos == 40 {
@@ -489,6 +438,9 @@ impl Day {
                 pixel_pos += 1;
                 spirte_left += value;
                 spirte_right += value;
+            } else {
+                // noop takes one cycle to complete.
+                pixel_pos += 1;
             }
             if pixel_pos == 40 {
                 y += 1;
@@ -499,10 +451,36 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            // noop
+            // addx -5
+            if line == "noop" {
+                self.operands.push(None);
+                continue;
+            }
+            match line.strip_prefix("addx ") {
+                Some(value) => self.operands.push(Some(parse_number(i, line, value)?)),
+                None => return Err(Error::parse(i, 1, "Expected noop or addx V")),
+            }
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -530,8 +508,8 @@ mod tests {
             13140,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 
@@ -557,8 +535,8 @@ mod tests {
             "addx 1", "addx 2", "addx 2", "addx -6", "addx -11", "noop", "noop", "noop",
         ];
         let lines = str_array_to_string_array(input);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         let result = day.part2();
         let expected: Vec<&str> = vec![
             "##..##..##..##..##..##..##..##..##..##..",
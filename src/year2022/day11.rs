@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::{blocks, scan};
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -323,17 +326,16 @@ Starting again from the initial state in your puzzle input, what is the level of
 
 */
 
-static INPUT_FILE: &str = "data/day11/input.txt";
+static INPUT_FILE: &str = "data/2022/day11/input.txt";
 
-pub fn run() {
-    println!("Day11: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day11: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 11,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     m_items: Vec<Vec<usize>>,
     m_tests: Vec<usize>,
     m_adds: Vec<bool>,
@@ -348,9 +350,8 @@ struct Day {
 impl Day {
     const MAX_MONKEYS: usize = 8;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             m_items: Vec::new(),
             m_tests: Vec::new(),
             m_adds: Vec::new(),
@@ -363,119 +364,7 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day11: Result1 {result1}");
-            let expected = 110264;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day11: Result2 {result2}");
-            let expected = 23612457316;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut m = Day::MAX_MONKEYS;
-        self.m_items.resize(Day::MAX_MONKEYS, Vec::new());
-        self.m_tests.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
-        self.m_adds.resize(Day::MAX_MONKEYS, false);
-        self.m_param_b_olds.resize(Day::MAX_MONKEYS, false);
-        self.m_param_b_values
-            .resize(Day::MAX_MONKEYS, std::usize::MAX);
-        self.m_trues.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
-        self.m_falses.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
-        self.m_inspection_counts.resize(Day::MAX_MONKEYS, 0);
-
-        for line in lines {
-            if line.is_empty() {
-                continue;
-            }
-            // Monkey 0:
-            if line.starts_with("Monkey") {
-                m = line[line.find("y").unwrap() + 1..line.find(":").unwrap()]
-                    .trim()
-                    .parse()
-                    .expect("Not a number");
-                assert!(m < Day::MAX_MONKEYS);
-                if m > self.monkey_count {
-                    self.monkey_count = m;
-                }
-                continue;
-            }
-            //   Starting items: 79, 98
-            if line.starts_with("  Starting items:") {
-                let toks = line[line.find(":").unwrap() + 1..].split(",");
-                for tok in toks {
-                    let v = tok.trim().parse().expect("Not a number");
-                    self.m_items[m].push(v);
-                }
-                continue;
-            }
-            //   Operation: new = old + 8
-            //   Operation: new = old + old
-            //   Operation: new = old * 19
-            //   Operation: new = old * old
-            if line.starts_with("  Operation: new =") {
-                let toks: Vec<&str> = line[line.find("= ").unwrap() + 2..].split(" ").collect();
-                let param_a = toks[0];
-                let op = toks[1];
-                let param_b = toks[2];
-                assert_eq!(param_a, "old");
-                if op == "+" {
-                    self.m_adds[m] = true;
-                } else {
-                    assert_eq!(op, "*");
-                }
-                if param_b == "old" {
-                    self.m_param_b_olds[m] = true;
-                } else {
-                    self.m_param_b_olds[m] = false;
-                    self.m_param_b_values[m] = param_b.trim().parse().expect("Not a number");
-                }
-                continue;
-            }
-            //   Test: divisible by 23
-            if line.starts_with("  Test: divisible by") {
-                let v = line[line.find("by ").unwrap() + 2..]
-                    .trim()
-                    .parse()
-                    .expect("Not a number");
-                self.m_tests[m] = v;
-                continue;
-            }
-            //     If true: throw to monkey 2
-            if line.starts_with("    If true: throw to monkey") {
-                let v = line[line.find("ey").unwrap() + 2..]
-                    .trim()
-                    .parse()
-                    .expect("Not a number");
-                self.m_trues[m] = v;
-                continue;
-            }
-            //     If false: throw to monkey 3
-            if line.starts_with("    If false: throw to monkey") {
-                let v = line[line.find("ey").unwrap() + 2..]
-                    .trim()
-                    .parse()
-                    .expect("Not a number");
-                self.m_falses[m] = v;
-                continue;
-            }
-            panic!("Unknown line {line}");
-        }
-    }
-
-    fn do_game(&mut self, rounds_count: usize, worry_reduce: usize) -> usize {
+    pub fn do_game(&mut self, rounds_count: usize, worry_reduce: usize) -> usize {
         let mut base = 1;
         for b in &self.m_tests {
             base *= b;
@@ -531,19 +420,142 @@ impl Day {
         return total;
     }
 
-    fn part1(&mut self) -> usize {
+    pub fn part1(&mut self) -> usize {
         return self.do_game(20, 3);
     }
 
-    fn part2(&mut self) -> usize {
+    pub fn part2(&mut self) -> usize {
         return self.do_game(10000, 1);
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        self.m_items.resize(Day::MAX_MONKEYS, Vec::new());
+        self.m_tests.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
+        self.m_adds.resize(Day::MAX_MONKEYS, false);
+        self.m_param_b_olds.resize(Day::MAX_MONKEYS, false);
+        self.m_param_b_values
+            .resize(Day::MAX_MONKEYS, std::usize::MAX);
+        self.m_trues.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
+        self.m_falses.resize(Day::MAX_MONKEYS, Day::MAX_MONKEYS);
+        self.m_inspection_counts.resize(Day::MAX_MONKEYS, 0);
+
+        for (start, block) in blocks(&lines) {
+            // Monkey 0:
+            //   Starting items: 79, 98
+            //   Operation: new = old * 19
+            //   Test: divisible by 23
+            //     If true: throw to monkey 2
+            //     If false: throw to monkey 3
+            if block.len() != 6 {
+                return Err(Error::parse(start, 1, "Expected a monkey to have 6 lines"));
+            }
+            let line = |l: usize| -> (usize, &str) {
+                return (start + l, block[l]);
+            };
+
+            let (i, text) = line(0);
+            let fields = scan(i, text, "Monkey {}:")?;
+            let m: usize = fields.number(0)?;
+            if m >= Day::MAX_MONKEYS {
+                return Err(Error::parse_token(
+                    i,
+                    text,
+                    fields.str(0),
+                    "Too many monkeys",
+                ));
+            }
+            if m > self.monkey_count {
+                self.monkey_count = m;
+            }
+
+            let (i, text) = line(1);
+            self.m_items[m] = scan(i, text, "  Starting items: {}")?.numbers(0)?;
+
+            //   Operation: new = old + 8
+            //   Operation: new = old + old
+            //   Operation: new = old * old
+            let (i, text) = line(2);
+            let fields = scan(i, text, "  Operation: new = old {} {}")?;
+            self.m_adds[m] = match fields.str(0) {
+                "+" => true,
+                "*" => false,
+                op => return Err(Error::parse_token(i, text, op, "Expected + or *")),
+            };
+            if fields.str(1) == "old" {
+                self.m_param_b_olds[m] = true;
+            } else {
+                self.m_param_b_olds[m] = false;
+                self.m_param_b_values[m] = fields.number(1)?;
+            }
+
+            let (i, text) = line(3);
+            let fields = scan(i, text, "  Test: divisible by {}")?;
+            let v: usize = fields.number(0)?;
+            if v == 0 {
+                return Err(Error::parse_token(
+                    i,
+                    text,
+                    fields.str(0),
+                    "Can not divide by 0",
+                ));
+            }
+            self.m_tests[m] = v;
+
+            for (l, template) in [
+                (4, "    If true: throw to monkey {}"),
+                (5, "    If false: throw to monkey {}"),
+            ] {
+                let (i, text) = line(l);
+                let fields = scan(i, text, template)?;
+                let v: usize = fields.number(0)?;
+                if v >= Day::MAX_MONKEYS || v == m {
+                    return Err(Error::parse_token(
+                        i,
+                        text,
+                        fields.str(0),
+                        "Invalid monkey to throw to",
+                    ));
+                }
+                if l == 4 {
+                    self.m_trues[m] = v;
+                } else {
+                    self.m_falses[m] = v;
+                }
+            }
+        }
+        for m in 0..=self.monkey_count {
+            let missing = self.m_tests[m] == Day::MAX_MONKEYS
+                || self.m_trues[m] > self.monkey_count
+                || self.m_falses[m] > self.monkey_count;
+            if missing {
+                return Err(Error::input(&format!("Monkey {m} is incomplete")));
+            }
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        if self.monkey_count == 0 {
+            return Err(Error::input("Less than two monkeys"));
+        }
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        if self.monkey_count == 0 {
+            return Err(Error::input("Less than two monkeys"));
+        }
+        return Ok(Day::part2(self).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -580,8 +592,8 @@ mod tests {
             10605,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 
@@ -620,8 +632,8 @@ mod tests {
             2713310158,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
@@ -0,0 +1,219 @@
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::search::bfs;
+use crate::solution::{Answer, Puzzle, Solution};
+
+/*
+
+--- Day 12: Hill Climbing Algorithm ---
+
+You try contacting the Elves using your handheld device, but the river you're following must be too low to get a decent signal.
+
+You ask the device for a heightmap of the surrounding area (your puzzle input).
+The heightmap shows the local area from above broken into a grid; the elevation of each square of the grid is given by a single lowercase letter, where a is the lowest elevation, b is the next-lowest, and so on up to the highest elevation, z.
+
+Also included on the heightmap are marks for your current position (S) and the location that should get the best signal (E).
+Your current position (S) has elevation a, and the location that should get the best signal (E) has elevation z.
+
+You'd like to reach E, but to save energy, you should do it in as few steps as possible.
+During each step, you can move exactly one square up, down, left, or right.
+To avoid needing to get out your climbing gear, the elevation of the destination square can be at most one higher than the elevation of your current square; that is, if your current elevation is m, you could step to elevation n, but not to elevation o.
+(This also means that the elevation of the destination square can be much lower than the elevation of your current square.)
+
+For example:
+
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+Here, you start in the top-left corner; your goal is near the middle.
+You could start by moving down or right, but eventually you'll need to head toward the e at the bottom.
+From there, you can spiral around to the goal:
+
+v..v<<<<
+>v.vv<<^
+.>vv>E^^
+..v>>>^^
+..>>>>>^
+In the above diagram, the symbols indicate whether the path exits each square moving up (^), down (v), left (<), or right (>).
+The location that should get the best signal is still E, and .  marks unvisited squares.
+
+This path reaches the goal in 31 steps, the fewest possible.
+
+What is the fewest steps required to move from your current position to the location that should get the best signal?
+
+Your puzzle answer was 350.
+
+--- Part Two ---
+
+As you walk up the hill, you suspect that the Elves will want to turn this into a hiking trail. The beginning isn't very scenic, though; perhaps you can find a better starting point.
+
+To maximize exercise while hiking, the trail should start as low as possible: elevation a. The goal is still the square marked E. However, the trail should still be direct, taking the fewest steps to reach its goal. So, you'll need to find the shortest path from any square at elevation a to the square marked E.
+
+Again consider the example from above:
+
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+Now, there are six choices for starting position (five marked a, plus the square marked S that counts as being at elevation a). If you start at the bottom-left square, you can reach the goal most quickly:
+
+...v<<<<
+...vv<<^
+...v>E^^
+.>v>>>^^
+>^>>>>>^
+This path reaches the goal in only 29 steps, the fewest possible.
+
+What is the fewest steps required to move starting from any square with elevation a to the location that should get the best signal?
+
+*/
+
+static INPUT_FILE: &str = "data/2022/day12/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 12,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
+
+pub struct Day {
+    grid: Grid<usize>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl Day {
+    pub fn instance() -> Day {
+        Day {
+            grid: Grid::new(0, 0, 0),
+            start: (0, 0),
+            end: (0, 0),
+        }
+    }
+
+    // Steps are to a neighbour that is at most one higher
+    pub fn find_shortest_path(&self, starts: impl IntoIterator<Item = (usize, usize)>) -> usize {
+        let path = bfs(
+            starts,
+            |&(x, y)| {
+                let max_height = self.grid[(x, y)] + 1;
+                return self
+                    .grid
+                    .neighbours4(x, y)
+                    .filter(move |&xy| self.grid[xy] <= max_height);
+            },
+            |&xy| xy == self.end,
+        );
+        return path.map_or(usize::MAX, |path| path.cost);
+    }
+
+    pub fn part1(&self) -> usize {
+        return self.find_shortest_path([self.start]);
+    }
+
+    // Searching from every a at once finds the nearest one
+    pub fn part2(&self) -> usize {
+        let mut starts = Vec::new();
+        for i in 0..self.grid.cells().len() {
+            if self.grid.cells()[i] == 0 {
+                starts.push(self.grid.xy(i));
+            }
+        }
+        return self.find_shortest_path(starts);
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut found_start = false;
+        let mut found_end = false;
+        self.grid = Grid::parse(&lines, "Heights must be a-z, S or E", |x, y, b| {
+            // S = 'a'
+            // E = 'z'
+            if b == b'S' {
+                self.start = (x, y);
+                found_start = true;
+                return Some(0);
+            } else if b == b'E' {
+                self.end = (x, y);
+                found_end = true;
+                return Some(25);
+            } else if b.is_ascii_lowercase() {
+                return Some((b - b'a') as usize);
+            }
+            return None;
+        })?;
+        if !found_start || !found_end {
+            return Err(Error::input("Missing the start S or end E"));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        let length = Day::part1(self);
+        if length == std::usize::MAX {
+            return Err(Error::input("No path from the start to the end"));
+        }
+        return Ok(length.into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        let length = Day::part2(self);
+        if length == std::usize::MAX {
+            return Err(Error::input("No path from any a to the end"));
+        }
+        return Ok(length.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn part1() {
+        let input: (Vec<&str>, usize) = (
+            vec!["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"],
+            31,
+        );
+        let lines = str_array_to_string_array(input.0);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part1(), input.1);
+    }
+
+    #[test]
+    fn part2() {
+        let input: (Vec<&str>, usize) = (
+            vec!["Sabqponm", "abcryxxl", "accszExk", "acctuvwj", "abdefghi"],
+            29,
+        );
+        let lines = str_array_to_string_array(input.0);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part2(), input.1);
+    }
+}
+
+/*
+
+Walk graph
+
+nodes.push (index:start_index, len:0)
+
+while !nodes.empty
+  node = nodes.pop;
+  if node == end_index : min_len = min(len,min_len) : continue
+  if up is valid : push up, len +=1
+  if down is valid : push down, len +=1
+  if left is valid : push left, len +=1
+  if right is valid : push right, len +=1
+
+*/
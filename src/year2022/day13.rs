@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -175,17 +177,16 @@ Organize all of the packets into the correct order. What is the decoder key for
 
 */
 
-static INPUT_FILE: &str = "data/day13/input.txt";
+static INPUT_FILE: &str = "data/2022/day13/input.txt";
 
-pub fn run() {
-    println!("Day13: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day13: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 13,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     packets: Vec<Vec<u8>>,
 }
 
@@ -196,61 +197,12 @@ impl Day {
     const ZERO: u8 = '0' as u8;
     const NINE: u8 = '9' as u8;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             packets: Vec::new(),
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day13: Result1 {result1}");
-            let expected = 5393;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day13: Result2 {result2}");
-            let expected = 26712;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut left = true;
-        let mut right = true;
-        for line in lines {
-            // [1,1,3,1,1]
-            // [1,1,5,1,1]
-
-            let data = line.as_bytes().to_vec();
-            if left {
-                self.packets.push(data);
-                left = false;
-                right = true;
-                continue;
-            } else if right {
-                self.packets.push(data);
-                left = false;
-                right = false;
-                continue;
-            } else if data.is_empty() {
-                left = true;
-                right = false;
-                continue;
-            }
-            panic!("Unknown line {line}");
-        }
-    }
-
     fn is_list_start(v: u8) -> bool {
         return v == Day::LIST_START;
     }
@@ -403,7 +355,36 @@ impl Day {
         return false;
     }
 
-    fn part1(&mut self) -> usize {
+    // A packet is a list of lists and numbers : [1,[2,[3,4]],5]
+    fn check_packet(data: &[u8], line_index: usize) -> Result<()> {
+        if data.first() != Some(&Day::LIST_START) {
+            return Err(Error::parse(line_index, 1, "A packet must start with ["));
+        }
+        let mut depth = 0;
+        for i in 0..data.len() {
+            let c = data[i];
+            if Day::is_list_start(c) {
+                depth += 1;
+            } else if Day::is_list_end(c) {
+                depth -= 1;
+            } else if !Day::is_separator(c) && !Day::is_number(c) {
+                return Err(Error::parse(line_index, i + 1, "Unexpected character"));
+            }
+            if depth == 0 && i != data.len() - 1 {
+                return Err(Error::parse(
+                    line_index,
+                    i + 2,
+                    "Packet continues after it ends",
+                ));
+            }
+        }
+        if depth != 0 {
+            return Err(Error::parse(line_index, data.len() + 1, "Missing ]"));
+        }
+        return Ok(());
+    }
+
+    pub fn part1(&mut self) -> usize {
         let count = self.packets.len() / 2;
         let mut total = 0;
         for i in 0..count {
@@ -415,7 +396,7 @@ impl Day {
         return total;
     }
 
-    fn part2(&mut self) -> usize {
+    pub fn part2(&mut self) -> usize {
         // Count how many less than "[[2]]"
         // Count how many less than "[[6]]"
         // Start from 1
@@ -438,10 +419,54 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let mut left = true;
+        let mut right = true;
+        for (i, line) in input.byte_lines().enumerate() {
+            // [1,1,3,1,1]
+            // [1,1,5,1,1]
+
+            let data = line.to_vec();
+            if left || right {
+                Day::check_packet(&data, i)?;
+            }
+            if left {
+                self.packets.push(data);
+                left = false;
+                right = true;
+                continue;
+            } else if right {
+                self.packets.push(data);
+                left = false;
+                right = false;
+                continue;
+            } else if data.is_empty() {
+                left = true;
+                right = false;
+                continue;
+            }
+            return Err(Error::parse(i, 1, "Expected a blank line between pairs"));
+        }
+        if self.packets.len() % 2 != 0 {
+            return Err(Error::input("The packets are not in pairs"));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -488,8 +513,8 @@ mod tests {
         ];
         for test in tests {
             let lines = str_array_to_string_array(test.0);
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.part1(), test.1);
         }
     }
@@ -525,8 +550,8 @@ mod tests {
             140,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
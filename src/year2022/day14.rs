@@ -1,4 +1,8 @@
-use crate::file_to_vec;
+use crate::error::{parse_field, Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::render::{Frames, Image};
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -188,125 +192,48 @@ How many units of sand come to rest?
 
 */
 
-static INPUT_FILE: &str = "data/day14/input.txt";
+static INPUT_FILE: &str = "data/2022/day14/input.txt";
 
-pub fn run() {
-    println!("Day14: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day14: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 14,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     line_starts: Vec<(i32, i32)>,
     line_ends: Vec<(i32, i32)>,
-    grid: Vec<u8>,
+    // Covers grid_min to grid_max
+    grid: Grid<u8>,
     grid_min: (i32, i32),
     grid_max: (i32, i32),
-    grid_size: (i32, i32),
     rock_bottom: i32,
+    frames: Option<Frames>,
 }
 
 impl Day {
-    const EMPTY: u8 = 0;
-    const ROCK: u8 = 1;
-    const WATER: u8 = 2;
+    const EMPTY: u8 = b'.';
+    const ROCK: u8 = b'#';
+    const WATER: u8 = b'o';
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             line_starts: Vec::new(),
             line_ends: Vec::new(),
-            grid: Vec::new(),
+            grid: Grid::new(0, 0, Day::EMPTY),
             grid_min: (0, 0),
             grid_max: (0, 0),
-            grid_size: (0, 0),
             rock_bottom: 0,
+            frames: None,
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day14: Result1 {result1}");
-            let expected = 1003;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day14: Result2 {result2}");
-            let expected = 25771;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        // Parse input to make lines to fill then compute maximum size of the required grid
-        let mut min_x = std::i32::MAX;
-        let mut max_x = std::i32::MIN;
-        let mut min_y = std::i32::MAX;
-        let mut max_y = std::i32::MIN;
-        for line in lines {
-            let point_toks: Vec<&str> = line.split(" -> ").collect();
-            // 498,4 -> 498,6 -> 496,6
-            // line 0 498,4 to 498,6
-            // line 1 498,6 to 496,6
-            let xy_toks: Vec<&str> = point_toks[0].split(",").collect();
-            let mut start = (
-                xy_toks[0].parse().expect("Not a number"),
-                xy_toks[1].parse().expect("Not a number"),
-            );
-            min_x = std::cmp::min(min_x, start.0);
-            min_y = std::cmp::min(min_y, start.1);
-            max_x = std::cmp::max(max_x, start.0);
-            max_y = std::cmp::max(max_y, start.1);
-            for p in 1..point_toks.len() {
-                self.line_starts.push(start);
-                // 498,4 -> x,y
-                let xy_toks: Vec<&str> = point_toks[p].split(",").collect();
-                let end = (
-                    xy_toks[0].parse().expect("Not a number"),
-                    xy_toks[1].parse().expect("Not a number"),
-                );
-                min_x = std::cmp::min(min_x, end.0);
-                min_y = std::cmp::min(min_y, end.1);
-                max_x = std::cmp::max(max_x, end.0);
-                max_y = std::cmp::max(max_y, end.1);
-                self.line_ends.push(end);
-                start = end;
-            }
-        }
-        assert!(min_x >= 10);
-        assert!(min_x < 500);
-        assert!(max_x > 500);
-        self.rock_bottom = max_y;
-        min_x = 0;
-        min_x -= max_y;
-        min_y = 0;
-        max_x += max_y;
-        max_y += 3;
-        self.grid_min = (min_x, min_y);
-        self.grid_max = (max_x, max_y);
-        self.grid_size = (
-            self.grid_max.0 - self.grid_min.0,
-            self.grid_max.1 - self.grid_min.1,
+    fn cell(&self, x: i32, y: i32) -> (usize, usize) {
+        return (
+            (x - self.grid_min.0) as usize,
+            (y - self.grid_min.1) as usize,
         );
-        self.grid
-            .resize((self.grid_size.0 * self.grid_size.1) as usize, Day::EMPTY);
-        assert_eq!(self.line_starts.len(), self.line_ends.len());
-        self.fill_grid();
-    }
-
-    fn grid_index(&self, x: i32, y: i32) -> usize {
-        let index = (y - self.grid_min.1) * self.grid_size.0 + (x - self.grid_min.0);
-        return index as usize;
     }
 
     fn fill_grid(&mut self) {
@@ -341,8 +268,8 @@ impl Day {
             let mut x = start.0;
             let mut y = start.1;
             for _p in 0..len {
-                let index = self.grid_index(x, y);
-                self.grid[index] = Day::ROCK;
+                let cell = self.cell(x, y);
+                self.grid[cell] = Day::ROCK;
                 x += dx;
                 y += dy;
             }
@@ -359,59 +286,58 @@ impl Day {
             assert!(y < self.grid_max.1);
             // Sand tries to move down, then down-left, then down-right.
             // If all three possible destinations are blocked, sand comes to rest
-            let index = self.grid_index(x, y + 1);
-            if self.grid[index] == Day::EMPTY {
+            if self.grid[self.cell(x, y + 1)] == Day::EMPTY {
                 y = y + 1;
                 continue;
             }
-            let index = self.grid_index(x - 1, y + 1);
-            if self.grid[index] == Day::EMPTY {
+            if self.grid[self.cell(x - 1, y + 1)] == Day::EMPTY {
                 x = x - 1;
                 y = y + 1;
                 continue;
             }
-            let index = self.grid_index(x + 1, y + 1);
-            if self.grid[index] == Day::EMPTY {
+            if self.grid[self.cell(x + 1, y + 1)] == Day::EMPTY {
                 x = x + 1;
                 y = y + 1;
                 continue;
             }
-            let index = self.grid_index(x, y);
-            self.grid[index] = Day::WATER;
+            let cell = self.cell(x, y);
+            self.grid[cell] = Day::WATER;
             return true;
         }
         return false;
     }
 
-    fn output_grid(&self) {
-        println!("");
-        for y in self.grid_min.1..self.grid_max.1 {
-            for x in self.grid_min.0..self.grid_max.0 {
-                let grid = self.grid[self.grid_index(x, y)];
-                let c;
-                if grid == Day::EMPTY {
-                    c = ".";
-                } else if grid == Day::ROCK {
-                    c = "#";
-                } else if grid == Day::WATER {
-                    c = "o";
-                } else {
-                    panic!("Unknown grid value {grid}");
-                }
-                print!("{c}");
-            }
-            println!("");
+    // The triangle below 500,0 that the sand can reach
+    fn image(&self) -> Image {
+        let half_width = self.rock_bottom + 2;
+        let left = 500 - half_width;
+        let width = (half_width * 2 + 1) as usize;
+        let height = (self.rock_bottom + 3) as usize;
+        return Grid::from_fn(width, height, |x, y| {
+            let (grid_x, grid_y) = self.cell(left + x as i32, y as i32);
+            return match self.grid.get(grid_x, grid_y) {
+                Some(&Day::ROCK) => [120, 120, 130],
+                Some(&Day::WATER) => [230, 190, 90],
+                _ => [20, 20, 30],
+            };
+        });
+    }
+
+    fn send_frame(&mut self) {
+        if let Some(mut frames) = self.frames.take() {
+            frames(&self.image());
+            self.frames = Some(frames);
         }
-        println!("");
     }
 
-    fn part1(&mut self) -> usize {
+    pub fn part1(&mut self) -> usize {
         return self.simulate(100000);
     }
 
-    fn simulate(&mut self, max_loops: usize) -> usize {
+    pub fn simulate(&mut self, max_loops: usize) -> usize {
         let mut total = 0;
-        let start_index = self.grid_index(500, 0);
+        let start = self.cell(500, 0);
+        self.send_frame();
         for _ in 0..max_loops {
             // Part1 drop sand from 500,0 until it doesn't stop
             // Part2 drop sand from 500,0 until it stops at 500,0
@@ -419,29 +345,120 @@ impl Day {
                 return total;
             }
             total += 1;
-            if self.grid[start_index] == Day::WATER {
+            self.send_frame();
+            if self.grid[start] == Day::WATER {
                 return total;
             }
         }
-        self.output_grid();
+        crate::log_debug!("Grid when the loops ran out\n{}", self.grid);
         panic!("Did not settle ran out of loops {max_loops}");
     }
 
-    fn part2(&mut self) -> usize {
+    pub fn part2(&mut self) -> usize {
         self.rock_bottom += 2;
         let y = self.rock_bottom;
         for x in self.grid_min.0..self.grid_max.0 {
-            let index = self.grid_index(x, y);
-            self.grid[index] = Day::ROCK;
+            let cell = self.cell(x, y);
+            self.grid[cell] = Day::ROCK;
         }
         return self.simulate(100000);
     }
 }
 
+// 498,4
+fn parse_point(line_index: usize, line: &str, point: &str) -> Result<(i32, i32)> {
+    let xy_toks: Vec<&str> = point.split(",").collect();
+    if xy_toks.len() != 2 {
+        return Err(Error::parse_token(line_index, line, point, "Expected x,y"));
+    }
+    return Ok((
+        parse_field(line_index, line, &xy_toks, 0)?,
+        parse_field(line_index, line, &xy_toks, 1)?,
+    ));
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        // Parse input to make lines to fill then compute maximum size of the required grid
+        let mut min_x = std::i32::MAX;
+        let mut max_x = std::i32::MIN;
+        let mut min_y = std::i32::MAX;
+        let mut max_y = std::i32::MIN;
+        for (i, line) in lines.iter().enumerate() {
+            let point_toks: Vec<&str> = line.split(" -> ").collect();
+            // 498,4 -> 498,6 -> 496,6
+            // line 0 498,4 to 498,6
+            // line 1 498,6 to 496,6
+            let mut start = parse_point(i, line, point_toks[0])?;
+            min_x = std::cmp::min(min_x, start.0);
+            min_y = std::cmp::min(min_y, start.1);
+            max_x = std::cmp::max(max_x, start.0);
+            max_y = std::cmp::max(max_y, start.1);
+            for p in 1..point_toks.len() {
+                self.line_starts.push(start);
+                // 498,4 -> x,y
+                let end = parse_point(i, line, point_toks[p])?;
+                if end.0 != start.0 && end.1 != start.1 {
+                    return Err(Error::parse_token(
+                        i,
+                        line,
+                        point_toks[p],
+                        "Rock lines must be horizontal or vertical",
+                    ));
+                }
+                min_x = std::cmp::min(min_x, end.0);
+                min_y = std::cmp::min(min_y, end.1);
+                max_x = std::cmp::max(max_x, end.0);
+                max_y = std::cmp::max(max_y, end.1);
+                self.line_ends.push(end);
+                start = end;
+            }
+        }
+        if lines.is_empty() {
+            return Err(Error::input("No rocks"));
+        }
+        if min_x < 10 || min_x >= 500 || max_x <= 500 || min_y < 0 {
+            return Err(Error::input(
+                "The rocks must be below and either side of 500,0",
+            ));
+        }
+        self.rock_bottom = max_y;
+        min_x = 0;
+        min_x -= max_y;
+        min_y = 0;
+        max_x += max_y;
+        max_y += 3;
+        self.grid_min = (min_x, min_y);
+        self.grid_max = (max_x, max_y);
+        self.grid = Grid::new(
+            (self.grid_max.0 - self.grid_min.0) as usize,
+            (self.grid_max.1 - self.grid_min.1) as usize,
+            Day::EMPTY,
+        );
+        assert_eq!(self.line_starts.len(), self.line_ends.len());
+        self.fill_grid();
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+
+    fn set_frames(&mut self, frames: Frames) -> bool {
+        self.frames = Some(frames);
+        return true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -453,8 +470,8 @@ mod tests {
             24,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 
@@ -468,8 +485,8 @@ mod tests {
             93,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::scan;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -135,17 +138,16 @@ Find the only possible position for the distress beacon. What is its tuning freq
 
 */
 
-static INPUT_FILE: &str = "data/day15/input.txt";
+static INPUT_FILE: &str = "data/2022/day15/input.txt";
 
-pub fn run() {
-    println!("Day15: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day15: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 15,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     sensors: Vec<(i32, i32)>,
     unique_beacons: Vec<(i32, i32)>,
     min_distances: Vec<u32>,
@@ -154,9 +156,8 @@ struct Day {
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             sensors: Vec::new(),
             unique_beacons: Vec::new(),
             min_distances: Vec::new(),
@@ -165,80 +166,7 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1(2000000);
-            println!("Day15: Result1 {result1}");
-            let expected = 4883971;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2(4000000);
-            println!("Day15: Result2 {result2}");
-            let expected = 12691026767556;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            // Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-            let toks: Vec<&str> = line[line.find("x=").expect("Bad parse") + 2..]
-                .split(",")
-                .collect();
-            let sx: i32 = toks[0].parse().expect("Not a number");
-            let toks: Vec<&str> = line[line.find("y=").expect("Bad parse") + 2..]
-                .split(":")
-                .collect();
-            let sy: i32 = toks[0].parse().expect("Not a number");
-            self.sensors.push((sx, sy));
-
-            // Sensor at x=2, y=18: closest beacon is at x=-2, y=15
-            let toks: Vec<&str> = line[line.rfind("x=").expect("Bad parse") + 2..]
-                .split(",")
-                .collect();
-            let bx: i32 = toks[0].parse().expect("Not a number");
-            let toks: Vec<&str> = line[line.rfind("y=").expect("Bad parse") + 2..]
-                .split(",")
-                .collect();
-            let by: i32 = toks[0].parse().expect("Not a number");
-            let distance = bx.abs_diff(sx) + by.abs_diff(sy);
-            self.min_distances.push(distance);
-            let sensor_min_x = sx - distance as i32;
-            let sensor_max_x = sx + distance as i32;
-            self.grid_min.0 = self.grid_min.0.min(sx - sensor_min_x);
-            self.grid_min.1 = self.grid_min.1.min(sy);
-
-            self.grid_min.0 = self.grid_min.0.min(bx);
-            self.grid_min.1 = self.grid_min.1.min(by);
-
-            self.grid_max.0 = self.grid_max.0.max(sx + sensor_max_x);
-            self.grid_max.1 = self.grid_max.1.max(sy);
-
-            self.grid_max.0 = self.grid_max.0.max(bx);
-            self.grid_max.1 = self.grid_max.1.max(by);
-
-            // Unique beacons
-            let mut found = false;
-            for b in 0..self.unique_beacons.len() {
-                if self.unique_beacons[b].0 == bx && self.unique_beacons[b].1 == by {
-                    found = true;
-                    break;
-                }
-            }
-            if !found {
-                self.unique_beacons.push((bx, by));
-            }
-        }
-    }
-
-    fn part1(&self, row: i32) -> usize {
+    pub fn part1(&self, row: i32) -> usize {
         let mut total = 0;
         let y = row;
         let sensor_count = self.sensors.len();
@@ -324,7 +252,7 @@ impl Day {
         return total - beacon_count;
     }
 
-    fn part2(&self, max_row: i32) -> i64 {
+    pub fn part2(&self, max_row: i32) -> i64 {
         let sensor_count = self.sensors.len();
         for i in 0..max_row {
             let y = max_row - i;
@@ -585,14 +513,76 @@ impl Day {
             //     }
             // }
         }
-        panic!("Did not find it");
+        // Not found
+        return -1;
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            let fields = scan(
+                i,
+                line,
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+            )?;
+            let sx: i32 = fields.number(0)?;
+            let sy: i32 = fields.number(1)?;
+            self.sensors.push((sx, sy));
+
+            let bx: i32 = fields.number(2)?;
+            let by: i32 = fields.number(3)?;
+            let distance = bx.abs_diff(sx) + by.abs_diff(sy);
+            self.min_distances.push(distance);
+            let sensor_min_x = sx - distance as i32;
+            let sensor_max_x = sx + distance as i32;
+            self.grid_min.0 = self.grid_min.0.min(sx - sensor_min_x);
+            self.grid_min.1 = self.grid_min.1.min(sy);
+
+            self.grid_min.0 = self.grid_min.0.min(bx);
+            self.grid_min.1 = self.grid_min.1.min(by);
+
+            self.grid_max.0 = self.grid_max.0.max(sx + sensor_max_x);
+            self.grid_max.1 = self.grid_max.1.max(sy);
+
+            self.grid_max.0 = self.grid_max.0.max(bx);
+            self.grid_max.1 = self.grid_max.1.max(by);
+
+            // Unique beacons
+            let mut found = false;
+            for b in 0..self.unique_beacons.len() {
+                if self.unique_beacons[b].0 == bx && self.unique_beacons[b].1 == by {
+                    found = true;
+                    break;
+                }
+            }
+            if !found {
+                self.unique_beacons.push((bx, by));
+            }
+        }
+        if self.sensors.is_empty() {
+            return Err(Error::input("No sensors"));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self, 2000000).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        let frequency = Day::part2(self, 4000000);
+        if frequency < 0 {
+            return Err(Error::input("No position for the distress beacon"));
+        }
+        return Ok(frequency.into());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -616,8 +606,8 @@ mod tests {
             26,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(10), input.1);
     }
 
@@ -643,8 +633,14 @@ mod tests {
             56000011,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(20), input.1);
     }
+
+    #[test]
+    fn no_sensors() {
+        let err = Day::instance().parse(&Input::default()).unwrap_err();
+        assert_eq!(err.to_string(), "Day00 unexpected input: No sensors");
+    }
 }
@@ -1,5 +1,9 @@
 // Optimise this
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::scan;
+use crate::search::bfs;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -257,17 +261,16 @@ With you and an elephant working together for 26 minutes, what is the most press
 
 */
 
-static INPUT_FILE: &str = "data/day16/input.txt";
+static INPUT_FILE: &str = "data/2022/day16/input.txt";
 
-pub fn run() {
-    println!("Day16: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day16: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 16,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     node_names: Vec<String>,
     node_rates: Vec<usize>,
     node_links: Vec<Vec<usize>>,
@@ -278,20 +281,9 @@ struct Day {
     max_rate: usize,
 }
 
-struct Node {
-    index: usize,
-    visited_nodes: Vec<usize>,
-    pressure: usize,
-    active_pressure: usize,
-    time: usize,
-    me_node: usize,
-    max_rate: usize,
-}
-
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             node_names: Vec::new(),
             node_rates: Vec::new(),
             node_links: Vec::new(),
@@ -303,97 +295,9 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1(30);
-            println!("Day16: Result1 {result1}");
-            let expected = 1991;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2(26);
-            println!("Day16: Result2 {result2}");
-            let expected = 2705;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut link_names: Vec<Vec<String>> = Vec::new();
-        for line in lines {
-            // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
-            // Valve HH has flow rate=22; tunnel leads to valve GG
-            let name = line[6..8].trim();
-            let rate: usize = line
-                [line.find("=").expect("No =") + 1..line.find("; tunnel").expect("No ; tunnel")]
-                .parse()
-                .expect("No a number");
-            let node_index = self.node_names.len();
-            if name == "AA" {
-                assert_eq!(self.start_node, usize::MAX);
-                self.start_node = node_index;
-            }
-            self.node_names.push(name.to_string());
-            self.node_rates.push(rate);
-            let mut search = "valves ";
-            if line.rfind(search).is_none() {
-                search = "valve ";
-            }
-            let toks: Vec<&str> = line[line.rfind(search).expect("No {search}") + search.len()..]
-                .split(",")
-                .collect();
-            let mut links: Vec<String> = Vec::new();
-            for l in toks {
-                links.push(l.to_string());
-            }
-            link_names.push(links.to_owned());
-
-            if rate > 0 {
-                self.valves.push(node_index);
-                self.max_rate += rate;
-            }
-        }
-        self.node_count = self.node_names.len();
-        assert!(self.start_node < self.node_count);
-        assert_eq!(self.node_count, self.node_rates.len());
-        assert_eq!(self.node_count, link_names.len());
-        for n in 0..self.node_count {
-            let mut node_links: Vec<usize> = Vec::new();
-            for l in &link_names[n] {
-                let mut link_index = self.node_count;
-                let link_name = l.trim();
-                for i in 0..self.node_count {
-                    if self.node_names[i] == link_name {
-                        link_index = i;
-                        break;
-                    }
-                }
-                assert_ne!(link_index, self.node_count);
-                node_links.push(link_index);
-            }
-            self.node_links.push(node_links);
-        }
-        assert_eq!(self.node_count, self.node_links.len());
-        // sort valves highest to lowest
-        for i in 0..self.valves.len() - 1 {
-            for j in i + 1..self.valves.len() {
-                let valve_i = self.valves[i];
-                let valve_j = self.valves[j];
-                if self.node_rates[valve_i] < self.node_rates[valve_j] {
-                    self.valves[i] = valve_j;
-                    self.valves[j] = valve_i;
-                }
-            }
-        }
-    }
-
-    fn print_steps(&self, route: &Vec<usize>) {
+    // The minutes walked to reach each valve of a route for the debug output
+    fn route_steps(&self, route: &[usize]) -> String {
+        let mut text = String::new();
         let mut from = route[0];
         let mut count_steps = 0;
         for i in 0..route.len() {
@@ -401,67 +305,38 @@ impl Day {
             let steps = self.steps[from][to];
             count_steps += steps;
             from = to;
-            if count_steps < 10 {
-                print!(" ");
-            }
-            print!(" {count_steps}");
+            text += &format!(" {count_steps:2}");
         }
+        return text;
     }
 
-    fn print_route(&self, route: &Vec<usize>) {
+    fn route_names(&self, route: &[usize]) -> String {
+        let mut text = String::new();
         for v in route {
             let to = *v;
             let name = &self.node_names[to];
-            print!(" {name}");
+            text += &format!(" {name}");
         }
+        return text;
     }
 
-    fn shortest_distance(&self, start: usize, end: usize) -> Vec<usize> {
-        let mut min_steps = usize::MAX;
-        let mut nodes_to_visit: Vec<usize> = Vec::new();
-        let mut visited_nodes_to_visit: Vec<Vec<usize>> = Vec::new();
-        let mut steps_to_visit: Vec<usize> = Vec::new();
-        let mut shortest_route: Vec<usize> = Vec::new();
-
-        nodes_to_visit.push(start);
-        steps_to_visit.push(0);
-        visited_nodes_to_visit.push(Vec::new());
-        while !nodes_to_visit.is_empty() {
-            let this_node = nodes_to_visit.pop().unwrap();
-            let visited_nodes = visited_nodes_to_visit.pop().unwrap();
-            let mut new_visited_nodes = visited_nodes.to_owned();
-
-            let this_steps = steps_to_visit.pop().unwrap();
-            // Reached the end
-            if this_node == end {
-                if this_steps < min_steps {
-                    min_steps = this_steps;
-                    shortest_route = visited_nodes.to_owned();
-                    shortest_route.push(this_node);
-                }
-                continue;
-            }
-            // Route is too long
-            if this_steps >= min_steps {
-                continue;
-            }
-            new_visited_nodes.push(this_node);
-            let new_steps = this_steps + 1;
-            for l in &self.node_links[this_node] {
-                let link = *l;
-                // No back tracking
-                if new_visited_nodes.contains(&link) {
-                    continue;
-                }
-                nodes_to_visit.push(link);
-                steps_to_visit.push(new_steps);
-                visited_nodes_to_visit.push(new_visited_nodes.to_owned());
-            }
-        }
-        return shortest_route;
+    // The valves along the shortest way from start to end, both included
+    fn shortest_distance(&self, start: usize, end: usize) -> Result<Vec<usize>> {
+        let path = bfs(
+            [start],
+            |&node| self.node_links[node].iter().copied(),
+            |&node| node == end,
+        );
+        let Some(path) = path else {
+            return Err(Error::input(&format!(
+                "No tunnels lead from {} to {}",
+                self.node_names[start], self.node_names[end]
+            )));
+        };
+        return Ok(path.states);
     }
 
-    fn compute_steps(&mut self) {
+    fn compute_steps(&mut self) -> Result<()> {
         self.steps.resize(self.node_count, Vec::new());
         for s in 0..self.node_count {
             if s != self.start_node && self.node_rates[s] == 0 {
@@ -474,23 +349,23 @@ impl Day {
                 if start == end {
                     continue;
                 }
-                let route = self.shortest_distance(start, end);
+                let route = self.shortest_distance(start, end)?;
                 let min_steps = route.len() - 1;
                 self.steps[start][end] = min_steps;
-                // let start_name = &self.node_names[start];
-                // let end_name = &self.node_names[end];
-                // let step_count = self.fsteps[start][end];
-                // print!("Node {start_name} -> {end_name} Steps {step_count} :");
-                // self.print_route(&route);
-                // println!("");
+                crate::log_trace!(
+                    "Node {} -> {} Steps {min_steps} :{}",
+                    self.node_names[start],
+                    self.node_names[end],
+                    self.route_names(&route)
+                );
             }
         }
+        return Ok(());
     }
 
-    fn part1(&mut self, minutes: usize) -> usize {
-        self.compute_steps();
+    pub fn part1(&mut self, minutes: usize) -> Result<usize> {
+        self.compute_steps()?;
         let mut max_total_pressure = 0;
-        let mut best_route: Vec<usize> = Vec::new();
         let mut nodes_to_visit: Vec<usize> = Vec::new();
         let mut visited_nodes_to_visit: Vec<Vec<usize>> = Vec::new();
         let mut pressure_to_visit: Vec<usize> = Vec::new();
@@ -529,11 +404,11 @@ impl Day {
             }
             if max_pressure > max_total_pressure {
                 max_total_pressure = max_pressure;
-                best_route = new_visited_nodes.to_owned();
-                // print!("New Max {max_total_pressure} Time {new_time} Route:");
-                // self.print_route(&new_visited_nodes);
-                // print!("New Max {max_total_pressure} Time {new_time} Route:");
-                // self.print_steps(&new_visited_nodes);
+                crate::log_debug!(
+                    "New Max {max_total_pressure} Time {this_time} Route:{} Steps:{}",
+                    self.route_names(&new_visited_nodes),
+                    self.route_steps(&new_visited_nodes)
+                );
             }
 
             for v in &self.valves {
@@ -563,17 +438,12 @@ impl Day {
                 max_rates_to_visit.push(new_max_rate);
             }
         }
-        // self.print_route(&best_route);
-        // println!("");
-        // self.print_steps(&best_route);
-        // println!("");
-        return max_total_pressure;
+        return Ok(max_total_pressure);
     }
 
-    fn part2(&mut self, minutes: usize) -> usize {
-        self.compute_steps();
+    pub fn part2(&mut self, minutes: usize) -> Result<usize> {
+        self.compute_steps()?;
         let mut max_total_pressure = 0;
-        let mut best_route: Vec<usize> = Vec::new();
         let mut nodes_to_visit: Vec<usize> = Vec::new();
         let mut visited_nodes_to_visit: Vec<Vec<usize>> = Vec::new();
         let mut max_rates_to_visit: Vec<usize> = Vec::new();
@@ -644,12 +514,10 @@ impl Day {
             let max_pressure = el_max_pressure + me_max_pressure;
             if max_pressure > max_total_pressure {
                 max_total_pressure = max_pressure;
-                best_route = new_visited_nodes.to_owned();
-                // println!("New Max {max_total_pressure}");
-                // print!("New Max {max_total_pressure} Time {new_time} Route:");
-                // self.print_route(&new_visited_nodes);
-                // print!("New Max {max_total_pressure} Time {new_time} Route:");
-                // self.print_steps(&new_visited_nodes);
+                crate::log_debug!(
+                    "New Max {max_total_pressure} Times {el_time} {me_time} Route:{}",
+                    self.route_names(&new_visited_nodes)
+                );
             }
 
             if me_remaining_time <= 1 && el_remaining_time <= 1 {
@@ -658,7 +526,9 @@ impl Day {
             let remaining_time = el_remaining_time.max(me_remaining_time);
             let max_possible_pressure = remaining_time * this_max_rate + max_pressure;
             if max_possible_pressure < max_total_pressure {
-                // println!("Ignoring route Max {max_total_pressure} Possible {max_possible_pressure}");
+                crate::log_trace!(
+                    "Ignoring route Max {max_total_pressure} Possible {max_possible_pressure}"
+                );
                 continue;
             }
 
@@ -725,18 +595,112 @@ impl Day {
                 max_rates_to_visit.push(new_max_rate);
             }
         }
-        // self.print_route(&best_route);
-        // println!("");
-        // self.print_steps(&best_route);
-        // println!("");
-        return max_total_pressure;
+        return Ok(max_total_pressure);
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        let mut link_names: Vec<Vec<String>> = Vec::new();
+        for (i, line) in lines.iter().enumerate() {
+            // Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
+            // Valve HH has flow rate=22; tunnel leads to valve GG
+            let fields = scan(i, line, "Valve {} has flow rate={}; {}")?;
+            let name = fields.str(0);
+            let rate: usize = fields.number(1)?;
+            let tunnels = fields.str(2);
+            let Some(link_list) = tunnels
+                .strip_prefix("tunnels lead to valves ")
+                .or_else(|| tunnels.strip_prefix("tunnel leads to valve "))
+            else {
+                return Err(Error::parse_token(
+                    i,
+                    line,
+                    tunnels,
+                    "Expected tunnels lead to valves XX, YY",
+                ));
+            };
+            let node_index = self.node_names.len();
+            if name == "AA" {
+                if self.start_node != usize::MAX {
+                    return Err(Error::parse_token(i, line, name, "Valve AA is repeated"));
+                }
+                self.start_node = node_index;
+            }
+            self.node_names.push(name.to_string());
+            self.node_rates.push(rate);
+            let mut links: Vec<String> = Vec::new();
+            for l in link_list.split(", ") {
+                links.push(l.to_string());
+            }
+            link_names.push(links.to_owned());
+
+            if rate > 0 {
+                self.valves.push(node_index);
+                self.max_rate += rate;
+            }
+        }
+        self.node_count = self.node_names.len();
+        if self.start_node == usize::MAX {
+            return Err(Error::input("No valve AA"));
+        }
+        if self.valves.is_empty() {
+            return Err(Error::input("No valves with a flow rate"));
+        }
+        assert_eq!(self.node_count, self.node_rates.len());
+        assert_eq!(self.node_count, link_names.len());
+        for n in 0..self.node_count {
+            let mut node_links: Vec<usize> = Vec::new();
+            for l in &link_names[n] {
+                let mut link_index = self.node_count;
+                let link_name = l.trim();
+                for i in 0..self.node_count {
+                    if self.node_names[i] == link_name {
+                        link_index = i;
+                        break;
+                    }
+                }
+                if link_index == self.node_count {
+                    let column = lines[n].rfind(link_name).unwrap_or(0) + 1;
+                    return Err(Error::parse(
+                        n,
+                        column,
+                        &format!("Unknown valve {link_name}"),
+                    ));
+                }
+                node_links.push(link_index);
+            }
+            self.node_links.push(node_links);
+        }
+        assert_eq!(self.node_count, self.node_links.len());
+        // sort valves highest to lowest
+        for i in 0..self.valves.len() - 1 {
+            for j in i + 1..self.valves.len() {
+                let valve_i = self.valves[i];
+                let valve_j = self.valves[j];
+                if self.node_rates[valve_i] < self.node_rates[valve_j] {
+                    self.valves[i] = valve_j;
+                    self.valves[j] = valve_i;
+                }
+            }
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self, 30)?.into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self, 26)?.into());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -756,9 +720,9 @@ mod tests {
             1651,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
-        assert_eq!(day.part1(30), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part1(30).unwrap(), input.1);
     }
 
     #[test]
@@ -779,8 +743,24 @@ mod tests {
             1707,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
-        assert_eq!(day.part2(26), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part2(26).unwrap(), input.1);
+    }
+
+    #[test]
+    fn unreachable() {
+        let lines = str_array_to_string_array(vec![
+            "Valve AA has flow rate=0; tunnel leads to valve CC",
+            "Valve BB has flow rate=5; tunnel leads to valve CC",
+            "Valve CC has flow rate=0; tunnel leads to valve AA",
+        ]);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        let err = day.part1(30).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day00 unexpected input: No tunnels lead from AA to BB"
+        );
     }
 }
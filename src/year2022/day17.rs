@@ -1,4 +1,9 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::render::{Frames, Image};
+use crate::solution::{Answer, Puzzle, Solution};
+use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 
 /*
@@ -366,27 +371,24 @@ How tall will the tower be after 1000000000000 rocks have stopped?
 
 */
 
-static INPUT_FILE: &str = "data/day17/input.txt";
+static INPUT_FILE: &str = "data/2022/day17/input.txt";
 
-pub fn run() {
-    println!("Day17: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day17: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 17,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     jets: Vec<u8>,
     jet_index: usize,
     rock_index: usize,
-    grid: Vec<u8>,
-    grid_width: i32,
-    grid_height: i32,
+    // The chamber with y going up from the floor
+    grid: Grid<u8>,
     rock_max_height: i32,
-    rock_grids: Vec<Vec<u8>>,
-    rock_widths: Vec<i32>,
-    rock_heights: Vec<i32>,
+    rock_grids: Vec<Grid<u8>>,
+    frames: Option<Frames>,
 }
 
 impl Day {
@@ -394,137 +396,64 @@ impl Day {
     const ROCK: u8 = '#' as u8;
     const JET_LEFT: u8 = '<' as u8;
     const JET_RIGHT: u8 = '>' as u8;
-
-    fn instance(part1: bool) -> Day {
+    const GRID_WIDTH: usize = 7;
+    const GRID_HEIGHT: usize = 10000;
+    // Rows shown in each frame
+    const VIEW_HEIGHT: usize = 48;
+    // Rocks are inverted to match grid
+    const ROCKS: [&'static [&'static str]; 5] = [
+        &["####"],
+        &[".#.", "###", ".#."],
+        &["###", "..#", "..#"],
+        &["#", "#", "#", "#"],
+        &["##", "##"],
+    ];
+
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             jets: Vec::new(),
-            grid: Vec::new(),
+            grid: Grid::new(Day::GRID_WIDTH, Day::GRID_HEIGHT, Day::EMPTY),
             jet_index: 0,
             rock_index: 0,
-            grid_width: 7,
-            grid_height: 10000,
             rock_max_height: 0,
             rock_grids: Vec::new(),
-            rock_widths: Vec::new(),
-            rock_heights: Vec::new(),
+            frames: None,
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1(2022);
-            println!("Day17: Result1 {result1}");
-            let expected = 3109;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2(1000000000000);
-            println!("Day17: Result2 {result2}");
-            let expected = 1541449275365;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
+    // Is any part of the rock on a rock in the grid when its bottom left is at x, y
+    fn rock_blocked(&self, rock: &Grid<u8>, x: i32, y: i32) -> bool {
+        for local_rock_y in 0..rock.height() {
+            for local_rock_x in 0..rock.width() {
+                let grid_x = local_rock_x + x as usize;
+                let grid_y = local_rock_y + y as usize;
+                if self.grid[(grid_x, grid_y)] == Day::ROCK
+                    && rock[(local_rock_x, local_rock_y)] == Day::ROCK
+                {
+                    return true;
+                }
             }
         }
+        return false;
     }
 
-    fn parse(&mut self, lines: &Vec<String>) {
-        self.jets = lines[0].as_bytes().to_vec();
-        assert_eq!(lines.len(), 1);
-        self.jet_index = 0;
-        self.grid
-            .resize((self.grid_height * self.grid_width) as usize, Day::EMPTY);
-
-        self.rock_grids.resize(5, Vec::new());
-        self.rock_widths.resize(5, 0);
-        self.rock_heights.resize(5, 0);
-
-        // Rocks are inverted to match grid
-        // '####'
-        self.rock_widths[0] = 4;
-        self.rock_heights[0] = 1;
-        self.rock_grids[0] = vec![Day::ROCK, Day::ROCK, Day::ROCK, Day::ROCK];
-
-        // '.#.'
-        // '###'
-        // '.#.'
-        self.rock_widths[1] = 3;
-        self.rock_heights[1] = 3;
-        self.rock_grids[1] = vec![
-            Day::EMPTY,
-            Day::ROCK,
-            Day::EMPTY,
-            Day::ROCK,
-            Day::ROCK,
-            Day::ROCK,
-            Day::EMPTY,
-            Day::ROCK,
-            Day::EMPTY,
-        ];
-
-        // ###
-        // ..#
-        // ..#
-        self.rock_widths[2] = 3;
-        self.rock_heights[2] = 3;
-        self.rock_grids[2] = vec![
-            Day::ROCK,
-            Day::ROCK,
-            Day::ROCK,
-            Day::EMPTY,
-            Day::EMPTY,
-            Day::ROCK,
-            Day::EMPTY,
-            Day::EMPTY,
-            Day::ROCK,
-        ];
-
-        // #
-        // #
-        // #
-        // #
-        self.rock_widths[3] = 1;
-        self.rock_heights[3] = 4;
-        self.rock_grids[3] = vec![Day::ROCK, Day::ROCK, Day::ROCK, Day::ROCK];
-
-        // ##
-        // ##
-        self.rock_widths[4] = 2;
-        self.rock_heights[4] = 2;
-        self.rock_grids[4] = vec![Day::ROCK, Day::ROCK, Day::ROCK, Day::ROCK];
-        for i in 0..self.rock_grids.len() {
-            assert_eq!(
-                self.rock_grids[i].len(),
-                (self.rock_widths[i] * self.rock_heights[i]) as usize
-            );
-        }
-    }
-
-    fn grid_index(&self, x: i32, y: i32) -> usize {
-        return (x + y * self.grid_width) as usize;
+    // The top of the chamber, y goes up so the rows are flipped
+    fn image(&self) -> Image {
+        let top = (self.rock_max_height as usize + 4).max(Day::VIEW_HEIGHT);
+        return Grid::from_fn(Day::GRID_WIDTH, Day::VIEW_HEIGHT, |x, y| {
+            if self.grid[(x, top - 1 - y)] == Day::ROCK {
+                return [150, 110, 220];
+            }
+            return [20, 20, 30];
+        });
     }
 
-    /*
-    fn print_grid(&self, y_min: i32, y_max: i32) {
-        println!("");
-        for i in y_min..y_max {
-            print!("|");
-            let y = y_max - i - 1;
-            for x in 0..self.grid_width {
-                let grid_index = self.grid_index(x, y);
-                let grid = self.grid[grid_index];
-                let c = grid as char;
-                print!("{c}");
-            }
-            println!("|");
+    fn send_frame(&mut self) {
+        if let Some(mut frames) = self.frames.take() {
+            frames(&self.image());
+            self.frames = Some(frames);
         }
-        println!("+-------+");
     }
-    */
 
     fn drop_rock(&mut self) {
         let jet_max = self.jets.len();
@@ -532,8 +461,9 @@ impl Day {
         // rock start
         // left edge is at x = 2
         // bottom edge is at rock_height + 3
-        let rock_width = self.rock_widths[self.rock_index];
-        let rock_height = self.rock_heights[self.rock_index];
+        let rock = &self.rock_grids[self.rock_index];
+        let rock_width = rock.width() as i32;
+        let rock_height = rock.height() as i32;
         let mut rock_x = 2;
         let mut rock_y = self.rock_max_height + 3;
         let mut falling = true;
@@ -553,54 +483,15 @@ impl Day {
             if new_rock_x < 0 {
                 blocked = true;
             }
-            if new_rock_x + rock_width > 7 {
+            if new_rock_x + rock_width > Day::GRID_WIDTH as i32 {
                 blocked = true;
             }
-            if !blocked {
-                for local_rock_y in 0..rock_height {
-                    for local_rock_x in 0..rock_width {
-                        let grid_x = local_rock_x + new_rock_x;
-                        let grid_y = local_rock_y + rock_y;
-                        let grid_index = self.grid_index(grid_x, grid_y);
-                        let grid = self.grid[grid_index];
-                        if grid == Day::ROCK {
-                            let local_rock_index =
-                                (local_rock_x + local_rock_y * rock_width) as usize;
-                            let rock = self.rock_grids[self.rock_index][local_rock_index];
-                            if rock == Day::ROCK {
-                                blocked = true;
-                                break;
-                            }
-                        }
-                    }
-                }
-                if !blocked {
-                    rock_x = new_rock_x;
-                }
+            if !blocked && !self.rock_blocked(rock, new_rock_x, rock_y) {
+                rock_x = new_rock_x;
             }
             // Falling movement down
             let new_rock_y = rock_y - 1;
-            falling = false;
-            if new_rock_y >= 0 {
-                falling = true;
-                for local_rock_y in 0..rock_height {
-                    for local_rock_x in 0..rock_width {
-                        let grid_x = local_rock_x + rock_x;
-                        let grid_y = local_rock_y + new_rock_y;
-                        let grid_index = self.grid_index(grid_x, grid_y);
-                        let grid = self.grid[grid_index];
-                        if grid == Day::ROCK {
-                            let local_rock_index =
-                                (local_rock_x + local_rock_y * rock_width) as usize;
-                            let rock = self.rock_grids[self.rock_index][local_rock_index];
-                            if rock == Day::ROCK {
-                                falling = false;
-                                break;
-                            }
-                        }
-                    }
-                }
-            }
+            falling = new_rock_y >= 0 && !self.rock_blocked(rock, rock_x, new_rock_y);
             if falling {
                 rock_y = new_rock_y
             }
@@ -608,23 +499,22 @@ impl Day {
             self.jet_index %= jet_max;
         }
         // Put the rock into the grid
-        for local_rock_y in 0..rock_height {
-            for local_rock_x in 0..rock_width {
-                let grid_x = local_rock_x + rock_x;
-                let grid_y = local_rock_y + rock_y;
-                let grid_index = self.grid_index(grid_x, grid_y);
-                let local_rock_index = (local_rock_x + local_rock_y * rock_width) as usize;
-                let rock = self.rock_grids[self.rock_index][local_rock_index];
-                if rock == Day::ROCK {
-                    assert!(self.grid[grid_index] == Day::EMPTY);
-                    self.grid[grid_index] = rock;
+        for local_rock_y in 0..rock.height() {
+            for local_rock_x in 0..rock.width() {
+                let grid_xy = (
+                    local_rock_x + rock_x as usize,
+                    local_rock_y + rock_y as usize,
+                );
+                if rock[(local_rock_x, local_rock_y)] == Day::ROCK {
+                    assert!(self.grid[grid_xy] == Day::EMPTY);
+                    self.grid[grid_xy] = Day::ROCK;
                 }
             }
         }
         self.rock_max_height = self.rock_max_height.max(rock_y + rock_height);
     }
 
-    fn part1(&mut self, max_count_rocks: usize) -> i32 {
+    pub fn part1(&mut self, max_count_rocks: usize) -> i32 {
         self.jet_index = 0;
         self.rock_index = 0;
         let rock_max = self.rock_grids.len();
@@ -632,11 +522,12 @@ impl Day {
             self.rock_index = i;
             self.rock_index %= rock_max;
             self.drop_rock();
+            self.send_frame();
         }
         return self.rock_max_height;
     }
 
-    fn part2(&mut self, max_count_rocks: usize) -> usize {
+    pub fn part2(&mut self, max_count_rocks: usize) -> usize {
         // Run the simulation store : jet_index, rock_index, total rock count, rock_height
         // Look for when when jet_index, rock_index : match
         self.jet_index = 0;
@@ -648,6 +539,7 @@ impl Day {
             self.rock_index = i;
             self.rock_index %= rock_max;
             self.drop_rock();
+            self.send_frame();
             let cycle = (self.jet_index, self.rock_index);
             previous_cycles.push(cycle);
             previous_heights.push(self.rock_max_height as usize);
@@ -665,9 +557,9 @@ impl Day {
                 if this.0 == cycle.0 && this.1 == cycle.1 {
                     let cycle_len = j - i;
                     // Ignore cycle lengths which are multiples of previous cycles
-                    if !found_cycle_lengths.contains_key(&cycle) {
+                    if let Entry::Vacant(entry) = found_cycle_lengths.entry(cycle) {
                         let cycle_height = height_after - height_before;
-                        found_cycle_lengths.insert(cycle, cycle_len);
+                        entry.insert(cycle_len);
                         found_cycle_heights.insert(cycle, cycle_height);
                         let entry = found_cycle_counts.entry(cycle).or_insert(0);
                         *entry += 1;
@@ -691,24 +583,59 @@ impl Day {
         let start_loops = max_count_rocks - cycle_count * cycle_length;
         assert!(cycle_count > 0);
         assert!(cycle_height > 0);
-        println!("Cycle {cycle_length} Count {cycle_count} Start Loops {start_loops} Cycle Height {cycle_height}");
+        crate::log_debug!("Cycle {cycle_length} Count {cycle_count} Start Loops {start_loops} Cycle Height {cycle_height}");
         let start_height = previous_heights[start_loops - 1];
         let rock_max_height = start_height + cycle_count * cycle_height;
         return rock_max_height;
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&[u8]> = input.byte_lines().collect();
+        if lines.len() != 1 || lines[0].is_empty() {
+            return Err(Error::input("Expected a single line of jets"));
+        }
+        self.jets = lines[0].to_vec();
+        for i in 0..self.jets.len() {
+            if self.jets[i] != Day::JET_LEFT && self.jets[i] != Day::JET_RIGHT {
+                return Err(Error::parse(0, i + 1, "Jets must be < or >"));
+            }
+        }
+        self.jet_index = 0;
+
+        for rock in Day::ROCKS {
+            self.rock_grids
+                .push(Grid::parse(rock, "Unknown rock", |_, _, c| Some(c))?);
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self, 2022).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self, 1000000000000).into());
+    }
+
+    fn set_frames(&mut self, frames: Frames) -> bool {
+        self.frames = Some(frames);
+        return true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
         let input = (vec![">>><<><>><<<>><>>><<<>>><<<><<<>><>><<>>"], 3068);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(2022), input.1);
     }
 
@@ -719,8 +646,8 @@ mod tests {
             1514285714288,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(1000000000000), input.1);
     }
 }
@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{parse_field, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -54,16 +56,15 @@ What is the exterior surface area of your scanned lava droplet?
 
 */
 
-static INPUT_FILE: &str = "data/day18/input.txt";
+static INPUT_FILE: &str = "data/2022/day18/input.txt";
 
-pub fn run() {
-    println!("Day18: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day18: End");
-}
-struct Day {
-    part1: bool,
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 18,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
+pub struct Day {
     grid_data: Vec<u8>,
     grid_size: usize,
     grid_y_index: usize,
@@ -78,9 +79,8 @@ impl Day {
     const AIR: u8 = 0;
     const LAVA: u8 = 1;
     const WATER: u8 = 2;
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             grid_data: Vec::new(),
             grid_size: 0,
             grid_y_index: 0,
@@ -92,62 +92,6 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day18: Result1 {result1}");
-            let expected = 3448;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day18: Result2 {result2}");
-            let expected = 2052;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut max_grid: usize = usize::MIN;
-        for line in lines {
-            // 2,2,2
-            let toks: Vec<&str> = line.trim().split(",").collect();
-            let x: usize = toks[0].trim().parse().expect("Not a number");
-            let y: usize = toks[1].trim().parse().expect("Not a number");
-            let z: usize = toks[2].trim().parse().expect("Not a number");
-            assert!(x < 100);
-            assert!(y < 100);
-            assert!(z < 100);
-            max_grid = max_grid.max(x);
-            max_grid = max_grid.max(y);
-            max_grid = max_grid.max(z);
-            self.cube_xs.push(x);
-            self.cube_ys.push(y);
-            self.cube_zs.push(z);
-        }
-        max_grid += 1;
-        assert!(max_grid < 100);
-        self.grid_size = max_grid;
-        self.grid_y_index = self.grid_size;
-        self.grid_z_index = self.grid_size * self.grid_size;
-        self.grid_data
-            .resize(max_grid * max_grid * max_grid as usize, Day::AIR);
-        self.count_cubes = self.cube_xs.len();
-        for i in 0..self.count_cubes {
-            let x = self.cube_xs[i];
-            let y = self.cube_ys[i];
-            let z = self.cube_zs[i];
-            let grid_index = self.grid_index(x, y, z);
-            self.grid_data[grid_index] = Day::LAVA;
-        }
-    }
-
     fn grid_index(&self, x: usize, y: usize, z: usize) -> usize {
         return x + y * self.grid_y_index + z * self.grid_z_index;
     }
@@ -215,11 +159,11 @@ impl Day {
         return total;
     }
 
-    fn part1(&self) -> usize {
+    pub fn part1(&self) -> usize {
         return self.count_surface_area();
     }
 
-    fn part2(&mut self) -> usize {
+    pub fn part2(&mut self) -> usize {
         // Flood fill from any empty cube with water : start from 0,0,0
         let start_index = self.grid_index(0, 0, 0);
         assert_eq!(self.grid_data[start_index], Day::AIR);
@@ -293,10 +237,62 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let mut max_grid: usize = usize::MIN;
+        for (i, line) in input.lines().enumerate() {
+            // 2,2,2
+            let toks: Vec<&str> = line.trim().split(",").map(|t| t.trim()).collect();
+            if toks.len() != 3 {
+                return Err(Error::parse(i, 1, "Expected x,y,z"));
+            }
+            let x: usize = parse_field(i, line, &toks, 0)?;
+            let y: usize = parse_field(i, line, &toks, 1)?;
+            let z: usize = parse_field(i, line, &toks, 2)?;
+            if x >= 99 || y >= 99 || z >= 99 {
+                return Err(Error::parse(i, 1, "Cubes must be less than 99,99,99"));
+            }
+            max_grid = max_grid.max(x);
+            max_grid = max_grid.max(y);
+            max_grid = max_grid.max(z);
+            self.cube_xs.push(x);
+            self.cube_ys.push(y);
+            self.cube_zs.push(z);
+        }
+        max_grid += 1;
+        self.grid_size = max_grid;
+        self.grid_y_index = self.grid_size;
+        self.grid_z_index = self.grid_size * self.grid_size;
+        self.grid_data
+            .resize(max_grid * max_grid * max_grid, Day::AIR);
+        self.count_cubes = self.cube_xs.len();
+        for i in 0..self.count_cubes {
+            let x = self.cube_xs[i];
+            let y = self.cube_ys[i];
+            let z = self.cube_zs[i];
+            let grid_index = self.grid_index(x, y, z);
+            self.grid_data[grid_index] = Day::LAVA;
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        // The flood fill starts from 0,0,0
+        if self.grid_data[self.grid_index(0, 0, 0)] != Day::AIR {
+            return Err(Error::input("Expected no cube at 0,0,0"));
+        }
+        return Ok(Day::part2(self).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -312,8 +308,8 @@ mod tests {
         ];
         for input in inputs {
             let lines = str_array_to_string_array(input.0);
-            let mut day = Day::instance(true);
-            day.parse(&lines);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
             assert_eq!(day.part1(), input.1);
         }
     }
@@ -328,8 +324,8 @@ mod tests {
             58,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
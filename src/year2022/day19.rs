@@ -1,6 +1,9 @@
 use std::collections::VecDeque;
 
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::scan;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -400,17 +403,16 @@ Don't worry about quality levels; instead, just determine the largest number of
 
 */
 
-static INPUT_FILE: &str = "data/day19/input.txt";
+static INPUT_FILE: &str = "data/2022/day19/input.txt";
 
-pub fn run() {
-    println!("Day19: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day19: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 19,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     count_bps: usize,
     bp_produces_ore: Vec<u16>,
     bp_produces_clay: Vec<u16>,
@@ -440,9 +442,8 @@ impl Day {
     const GEODE: usize = 3;
     const COUNT_ROBOTS: usize = 4;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             count_bps: 0,
             bp_produces_ore: Vec::new(),
             bp_produces_clay: Vec::new(),
@@ -454,104 +455,7 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1(24);
-            println!("Day19: Result1 {result1}");
-            let expected = 1266;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2(32, 3);
-            println!("Day19: Result2 {result2}");
-            let expected = 5800;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn product_type(product: &str) -> usize {
-        if product == "ore" {
-            return Day::ORE;
-        } else if product == "clay" {
-            return Day::CLAY;
-        } else if product == "obsidian" {
-            return Day::OBSIDIAN;
-        } else if product == "geode" {
-            return Day::GEODE;
-        }
-        panic!("Unknown product");
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let mut bp_index = 0;
-        for line in lines {
-            bp_index += 1;
-            // 'Blueprint 1: Each ore robot costs 4 ore.  Each clay robot costs 2 ore.  Each obsidian robot costs 3 ore and 14 clay.  Each geode robot costs 2 ore and 7 obsidian.',
-            let toks: Vec<&str> = line.trim().split(":").collect();
-            assert!(toks[0].starts_with("Blueprint "));
-            let blueprint: usize = toks[0][toks[0].find("t ").unwrap() + 2..]
-                .parse()
-                .expect("Not a number");
-            assert_eq!(blueprint, bp_index);
-            let robots: Vec<&str> = toks[1].trim().split(". ").collect();
-            assert_eq!(robots.len(), Day::COUNT_ROBOTS);
-            for r in 0..Day::COUNT_ROBOTS {
-                let rb = robots[r].trim().trim_end_matches(".");
-                let toks: Vec<&str> = rb.split(" ").collect();
-                let product = toks[1].trim();
-                let mut ore_output = 0;
-                let mut clay_output = 0;
-                let mut obs_output = 0;
-                let mut geo_output = 0;
-                match Day::product_type(product) {
-                    Day::ORE => ore_output = 1,
-                    Day::CLAY => clay_output = 1,
-                    Day::OBSIDIAN => obs_output = 1,
-                    Day::GEODE => geo_output = 1,
-                    _ => panic!("Unknown product {product}"),
-                }
-                self.bp_produces_ore.push(ore_output);
-                self.bp_produces_clay.push(clay_output);
-                self.bp_produces_obs.push(obs_output);
-                self.bp_produces_geo.push(geo_output);
-
-                let mut ore_cost: u16 = 0;
-                let mut clay_cost: u16 = 0;
-                let mut obs_cost: u16 = 0;
-                let cost_count: u16 = toks[4].parse().expect("Not a number");
-                let cost_type = Day::product_type(toks[5]);
-                match cost_type {
-                    Day::ORE => ore_cost = cost_count,
-                    Day::CLAY => clay_cost = cost_count,
-                    Day::OBSIDIAN => obs_cost = cost_count,
-                    _ => panic!("Unknown product {product}"),
-                }
-                if toks.len() == 9 {
-                    let cost_count_two: u16 = toks[7].parse().expect("Not a number");
-                    let cost_type_two = Day::product_type(toks[8]);
-                    assert_ne!(cost_type, cost_type_two);
-                    match cost_type_two {
-                        Day::ORE => ore_cost = cost_count_two,
-                        Day::CLAY => clay_cost = cost_count_two,
-                        Day::OBSIDIAN => obs_cost = cost_count_two,
-                        _ => panic!("Unknown product {product}"),
-                    }
-                }
-                self.bp_costs_ore.push(ore_cost);
-                self.bp_costs_clay.push(clay_cost);
-                self.bp_costs_obs.push(obs_cost);
-            }
-        }
-        self.count_bps = bp_index;
-    }
-
-    fn max_geodes(&mut self, minutes: u16, bp: usize) -> usize {
+    pub fn max_geodes(&mut self, minutes: u16, bp: usize) -> usize {
         // Can only make 1 robot per turn :
         // Once we have enough robots to make
         // any item per turn then we don't need any more of that robot
@@ -701,7 +605,7 @@ impl Day {
         return max_count_geo;
     }
 
-    fn part1(&mut self, minutes: u16) -> usize {
+    pub fn part1(&mut self, minutes: u16) -> usize {
         let mut result = 0;
         for bp in 0..self.count_bps {
             let max_count_geo = self.max_geodes(minutes, bp);
@@ -710,7 +614,7 @@ impl Day {
         return result;
     }
 
-    fn part2(&mut self, minutes: u16, max_bps: usize) -> usize {
+    pub fn part2(&mut self, minutes: u16, max_bps: usize) -> usize {
         let mut result = 1;
         for bp in 0..max_bps {
             let max_count_geo = self.max_geodes(minutes, bp);
@@ -720,10 +624,64 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let mut bp_index = 0;
+        for (i, line) in input.lines().enumerate() {
+            bp_index += 1;
+            let fields = scan(
+                i,
+                line,
+                "Blueprint {}: Each ore robot costs {} ore. \
+                Each clay robot costs {} ore. \
+                Each obsidian robot costs {} ore and {} clay. \
+                Each geode robot costs {} ore and {} obsidian.",
+            )?;
+            let blueprint: usize = fields.number(0)?;
+            if blueprint != bp_index {
+                return Err(Error::parse_token(
+                    i,
+                    line,
+                    fields.str(0),
+                    &format!("Expected Blueprint {bp_index}"),
+                ));
+            }
+            // The product and the ore, clay and obsidian costs of each robot
+            let robots = [
+                (Day::ORE, [fields.number(1)?, 0, 0]),
+                (Day::CLAY, [fields.number(2)?, 0, 0]),
+                (Day::OBSIDIAN, [fields.number(3)?, fields.number(4)?, 0]),
+                (Day::GEODE, [fields.number(5)?, 0, fields.number(6)?]),
+            ];
+            for (product, costs) in robots {
+                self.bp_produces_ore.push((product == Day::ORE) as u16);
+                self.bp_produces_clay.push((product == Day::CLAY) as u16);
+                self.bp_produces_obs.push((product == Day::OBSIDIAN) as u16);
+                self.bp_produces_geo.push((product == Day::GEODE) as u16);
+                self.bp_costs_ore.push(costs[0]);
+                self.bp_costs_clay.push(costs[1]);
+                self.bp_costs_obs.push(costs[2]);
+            }
+        }
+        self.count_bps = bp_index;
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self, 24).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        // Only the first three blueprints are left
+        let max_bps = self.count_bps.min(3);
+        return Ok(Day::part2(self, 32, max_bps).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -732,8 +690,8 @@ mod tests {
 "Blueprint 2: Each ore robot costs 2 ore.  Each clay robot costs 3 ore.  Each obsidian robot costs 3 ore and 8 clay.  Each geode robot costs 3 ore and 12 obsidian.",],
             33);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(24), input.1);
     }
 
@@ -744,8 +702,8 @@ mod tests {
 "Blueprint 2: Each ore robot costs 2 ore.  Each clay robot costs 3 ore.  Each obsidian robot costs 3 ore and 8 clay.  Each geode robot costs 3 ore and 12 obsidian.",],
             56 * 62);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(32, 2), input.1);
     }
 }
@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{parse_number, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -128,57 +130,27 @@ What is the sum of the three numbers that form the grove coordinates?
 
 */
 
-static INPUT_FILE: &str = "data/day20/input.txt";
+static INPUT_FILE: &str = "data/2022/day20/input.txt";
 
-pub fn run() {
-    println!("Day20: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day20: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 20,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     starting_values: Vec<i64>,
 }
 
 impl Day {
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             starting_values: Vec::new(),
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day20: Result1 {result1}");
-            let expected = 19559;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day20: Result2 {result2}");
-            let expected = 912226207972;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            let v = line.parse().expect("Not anumber");
-            self.starting_values.push(v);
-        }
-    }
-
-    fn decipher(&self, key: i64, count_mixes: usize) -> i64 {
+    pub fn decipher(&self, key: i64, count_mixes: usize) -> i64 {
         let values: Vec<i64> = self.starting_values.iter().map(|&v| v * key).collect();
         let count = values.len();
         let mut mixed_indexes: Vec<usize> = (0..count).collect();
@@ -212,26 +184,48 @@ impl Day {
         return sum;
     }
 
-    fn part1(&self) -> i64 {
+    pub fn part1(&self) -> i64 {
         return self.decipher(1, 1);
     }
 
-    fn part2(&self) -> i64 {
+    pub fn part2(&self) -> i64 {
         return self.decipher(811589153, 10);
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            let v = parse_number(i, line, line)?;
+            self.starting_values.push(v);
+        }
+        // The grove coordinates are found from the 0
+        if !self.starting_values.contains(&0) {
+            return Err(Error::input("No 0 in the file"));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
         let input = (vec!["1", "2", "-3", "3", "-2", "0", "4"], 3);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 
@@ -239,8 +233,8 @@ mod tests {
     fn part2() {
         let input = (vec!["1", "2", "-3", "3", "-2", "0", "4"], 1623178306);
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::scan::scan;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -75,17 +78,16 @@ What number do you yell to pass root's equality test?
 
 */
 
-static INPUT_FILE: &str = "data/day21/input.txt";
+static INPUT_FILE: &str = "data/2022/day21/input.txt";
 
-pub fn run() {
-    println!("Day21: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day21: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 21,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
     monkey_names: Vec<String>,
     monkey_has_value: Vec<bool>,
     monkey_values: Vec<i64>,
@@ -106,9 +108,8 @@ impl Day {
     const DIV: u8 = 4;
     const EQU: u8 = 5;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             monkey_names: Vec::new(),
             monkey_has_value: Vec::new(),
             monkey_values: Vec::new(),
@@ -122,118 +123,6 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day21: Result1 {result1}");
-            let expected = 145167969204648;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day21: Result2 {result2}");
-            let expected = 3330805295850;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        let count = lines.len();
-        let mut monkey_lhs_names: Vec<String> = Vec::new();
-        let mut monkey_rhs_names: Vec<String> = Vec::new();
-        self.monkey_root = usize::MAX;
-        self.monkey_humn = usize::MAX;
-        for line in lines {
-            // root: pppw + sjmn
-            // dbpl: 5
-            let mut iter = line.split_ascii_whitespace().into_iter();
-            let name = iter.next().unwrap().trim_end_matches(":");
-            let value: i64;
-            let lhs = usize::MAX;
-            let op;
-            let rhs = usize::MAX;
-            let value_lhs_string = iter.next().unwrap();
-            let result = value_lhs_string.parse();
-            let has_value = result.is_ok();
-            let lhs_name;
-            let rhs_name;
-            let uses_human;
-            let has_human;
-            if has_value {
-                value = result.unwrap();
-                op = Day::VAL;
-                lhs_name = "";
-                rhs_name = "";
-            } else {
-                value = i64::MAX;
-                lhs_name = value_lhs_string;
-                let op_name = iter.next().unwrap().trim();
-                op = match op_name {
-                    "+" => Day::ADD,
-                    "-" => Day::SUB,
-                    "*" => Day::MUL,
-                    "/" => Day::DIV,
-                    _ => panic!("Unknown operation {op_name}"),
-                };
-                rhs_name = iter.next().unwrap().trim();
-            }
-            if name == "root" {
-                self.monkey_root = self.monkey_names.len();
-            }
-            if name == "humn" {
-                self.monkey_humn = self.monkey_names.len();
-                uses_human = true;
-                has_human = true;
-            } else {
-                uses_human = false;
-                has_human = has_value;
-            }
-            monkey_lhs_names.push(lhs_name.to_string());
-            monkey_rhs_names.push(rhs_name.to_string());
-            self.monkey_names.push(name.to_string());
-            self.monkey_values.push(value);
-            self.monkey_has_value.push(has_value);
-            self.monkey_lhs.push(lhs);
-            self.monkey_op.push(op);
-            self.monkey_rhs.push(rhs);
-            self.monkey_uses_human.push(uses_human);
-            self.monkey_has_human.push(has_human);
-        }
-        assert!(self.monkey_root < self.monkey_names.len());
-        assert!(self.monkey_humn < self.monkey_names.len());
-        // Loop over and resolve names -> indexes
-        for i in 0..count {
-            let lhs_name = &monkey_lhs_names[i];
-            let rhs_name = &monkey_rhs_names[i];
-            if lhs_name.is_empty() {
-                assert!(rhs_name.is_empty());
-                continue;
-            }
-            if rhs_name.is_empty() {
-                assert!(lhs_name.is_empty());
-                continue;
-            }
-            let lhs = self
-                .monkey_names
-                .iter()
-                .position(|n| n == lhs_name)
-                .unwrap();
-            let rhs = self
-                .monkey_names
-                .iter()
-                .position(|n| n == rhs_name)
-                .unwrap();
-            self.monkey_lhs[i] = lhs;
-            self.monkey_rhs[i] = rhs;
-        }
-    }
-
     fn reset_equations(&mut self) {
         for i in 0..self.monkey_names.len() {
             if self.monkey_op[i] != Day::VAL {
@@ -242,66 +131,68 @@ impl Day {
         }
     }
 
-    fn find_human_value(&mut self, monkey: usize, value: i64) -> i64 {
+    fn find_human_value(&mut self, monkey: usize, value: i64) -> Result<i64> {
         if monkey == self.monkey_humn {
-            return value;
+            return Ok(value);
         }
+        let name = &self.monkey_names[monkey];
         let result = value;
         let lhs = self.monkey_lhs[monkey];
         let rhs = self.monkey_rhs[monkey];
-        assert_ne!(lhs, rhs);
         let op = self.monkey_op[monkey];
+        if self.monkey_uses_human[lhs] && self.monkey_uses_human[rhs] {
+            return Err(Error::input(&format!(
+                "Monkey {name} uses humn on both sides"
+            )));
+        }
+        let zero = || Error::input(&format!("Monkey {name} can not be undone, it gives 0"));
         if self.monkey_uses_human[lhs] {
-            assert!(!self.monkey_uses_human[rhs]);
-            assert!(self.monkey_has_value[rhs]);
             let rhs_value = self.monkey_values[rhs];
             let lhs_value = match op {
                 Day::ADD => result - rhs_value, // result = Z + rhs
                 Day::SUB => result + rhs_value, // result = Z - rhs
-                Day::MUL => result / rhs_value, // result = Z * rhs
+                Day::MUL => result.checked_div(rhs_value).ok_or_else(zero)?, // result = Z * rhs
                 Day::DIV => result * rhs_value, // result = Z / rhs
-                _ => panic!("Unknown operation"),
+                _ => return Err(Error::input(&format!("Monkey {name} has no operation"))),
             };
             return self.find_human_value(lhs, lhs_value);
         } else if self.monkey_uses_human[rhs] {
-            assert!(!self.monkey_uses_human[lhs]);
-            assert!(self.monkey_has_value[lhs]);
             let lhs_value = self.monkey_values[lhs];
             let rhs_value = match op {
                 Day::ADD => result - lhs_value, // result = lhs + Z
                 Day::SUB => lhs_value - result, // result = lhs - Z
-                Day::MUL => result / lhs_value, // result = lhs * Z
-                // Day::DIV => lhs_value / result, // result = lhs / Z
-                _ => panic!("Unknown operation"),
+                Day::MUL => result.checked_div(lhs_value).ok_or_else(zero)?, // result = lhs * Z
+                Day::DIV => lhs_value.checked_div(result).ok_or_else(zero)?, // result = lhs / Z
+                _ => return Err(Error::input(&format!("Monkey {name} has no operation"))),
             };
             return self.find_human_value(rhs, rhs_value);
         }
-        panic!("Bad state");
+        return Err(Error::input(&format!("Monkey {name} does not use humn")));
     }
 
-    fn resolve(&mut self, monkey: usize) -> i64 {
+    fn resolve(&mut self, monkey: usize) -> Result<i64> {
         if self.monkey_has_value[monkey] {
-            return self.monkey_values[monkey];
+            return Ok(self.monkey_values[monkey]);
         }
-        let lhs_value = self.resolve(self.monkey_lhs[monkey]);
-        let rhs_value = self.resolve(self.monkey_rhs[monkey]);
+        let lhs_value = self.resolve(self.monkey_lhs[monkey])?;
+        let rhs_value = self.resolve(self.monkey_rhs[monkey])?;
         let result = match self.monkey_op[monkey] {
-            Day::ADD => lhs_value + rhs_value,
-            Day::SUB => lhs_value - rhs_value,
-            Day::MUL => lhs_value * rhs_value,
-            Day::DIV => lhs_value / rhs_value,
-            Day::EQU => {
-                if lhs_value == rhs_value {
-                    1
-                } else {
-                    0
-                }
-            }
-            _ => panic!("Unknown operation"),
+            Day::ADD => lhs_value.checked_add(rhs_value),
+            Day::SUB => lhs_value.checked_sub(rhs_value),
+            Day::MUL => lhs_value.checked_mul(rhs_value),
+            Day::DIV => lhs_value.checked_div(rhs_value),
+            Day::EQU => Some((lhs_value == rhs_value) as i64),
+            _ => None,
+        };
+        let Some(result) = result else {
+            return Err(Error::input(&format!(
+                "Monkey {} divides by 0 or overflows",
+                self.monkey_names[monkey]
+            )));
         };
         self.monkey_has_value[monkey] = true;
         self.monkey_values[monkey] = result;
-        return result;
+        return Ok(result);
     }
 
     fn track_humn_usage(&mut self, monkey: usize) -> bool {
@@ -316,17 +207,24 @@ impl Day {
         return result;
     }
 
-    fn part1(&mut self) -> i64 {
+    pub fn part1(&mut self) -> Result<i64> {
         return self.resolve(self.monkey_root);
     }
 
-    fn part2(&mut self) -> i64 {
-        assert!(self.monkey_has_value[self.monkey_humn]);
+    pub fn part2(&mut self) -> Result<i64> {
+        if !self.monkey_has_value[self.monkey_humn] {
+            return Err(Error::input("Monkey humn is not a number"));
+        }
+        if self.monkey_op[self.monkey_root] == Day::VAL {
+            return Err(Error::input("Monkey root is not an operation"));
+        }
         // Compute initial values
-        _ = self.resolve(self.monkey_root);
+        _ = self.resolve(self.monkey_root)?;
 
         // Track which equations use "humn" and propagate it
-        assert!(self.track_humn_usage(self.monkey_root));
+        if !self.track_humn_usage(self.monkey_root) {
+            return Err(Error::input("Monkey root does not use humn"));
+        }
 
         // lhs nor rhs can use human
         let monkey_root_lhs = self.monkey_lhs[self.monkey_root];
@@ -334,7 +232,9 @@ impl Day {
         let lhs_human = self.monkey_uses_human[monkey_root_lhs];
         let rhs_human = self.monkey_uses_human[monkey_root_rhs];
 
-        assert!(lhs_human ^ rhs_human);
+        if lhs_human && rhs_human {
+            return Err(Error::input("Monkey root uses humn on both sides"));
+        }
 
         let target_start;
         let target_value;
@@ -346,21 +246,130 @@ impl Day {
             target_value = self.monkey_values[monkey_root_lhs];
         }
         // Inverse recurse undo one eqation at a time with a known result
-        let human_value = self.find_human_value(target_start, target_value);
+        let human_value = self.find_human_value(target_start, target_value)?;
 
         // Change monkey root equation to equals to check the answer
         self.monkey_values[self.monkey_humn] = human_value;
         self.reset_equations();
         self.monkey_op[self.monkey_root] = Day::EQU;
-        assert_eq!(self.resolve(self.monkey_root), 1);
-        return human_value;
+        if self.resolve(self.monkey_root)? != 1 {
+            return Err(Error::input("No humn value makes both sides of root equal"));
+        }
+        return Ok(human_value);
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        let count = lines.len();
+        let mut monkey_lhs_names: Vec<String> = Vec::new();
+        let mut monkey_rhs_names: Vec<String> = Vec::new();
+        self.monkey_root = usize::MAX;
+        self.monkey_humn = usize::MAX;
+        for (i, line) in lines.iter().enumerate() {
+            // root: pppw + sjmn
+            // dbpl: 5
+            let fields = scan(i, line, "{}: {}")?;
+            let name = fields.str(0);
+            let value: i64;
+            let lhs = usize::MAX;
+            let op;
+            let rhs = usize::MAX;
+            let has_value = !fields.str(1).contains(' ');
+            let lhs_name;
+            let rhs_name;
+            let uses_human;
+            let has_human;
+            if has_value {
+                value = fields.number(1)?;
+                op = Day::VAL;
+                lhs_name = "";
+                rhs_name = "";
+            } else {
+                let fields = scan(i, line, "{}: {} {} {}")?;
+                value = i64::MAX;
+                lhs_name = fields.str(1);
+                let op_name = fields.str(2);
+                op = match op_name {
+                    "+" => Day::ADD,
+                    "-" => Day::SUB,
+                    "*" => Day::MUL,
+                    "/" => Day::DIV,
+                    _ => {
+                        return Err(Error::parse_token(
+                            i,
+                            line,
+                            op_name,
+                            &format!("Unknown operation {op_name}"),
+                        ))
+                    }
+                };
+                rhs_name = fields.str(3);
+            }
+            if name == "root" {
+                self.monkey_root = self.monkey_names.len();
+            }
+            if name == "humn" {
+                self.monkey_humn = self.monkey_names.len();
+                uses_human = true;
+                has_human = true;
+            } else {
+                uses_human = false;
+                has_human = has_value;
+            }
+            monkey_lhs_names.push(lhs_name.to_string());
+            monkey_rhs_names.push(rhs_name.to_string());
+            self.monkey_names.push(name.to_string());
+            self.monkey_values.push(value);
+            self.monkey_has_value.push(has_value);
+            self.monkey_lhs.push(lhs);
+            self.monkey_op.push(op);
+            self.monkey_rhs.push(rhs);
+            self.monkey_uses_human.push(uses_human);
+            self.monkey_has_human.push(has_human);
+        }
+        if self.monkey_root == usize::MAX {
+            return Err(Error::input("No root monkey"));
+        }
+        if self.monkey_humn == usize::MAX {
+            return Err(Error::input("No humn monkey"));
+        }
+        // Loop over and resolve names -> indexes
+        for i in 0..count {
+            let lhs_name = &monkey_lhs_names[i];
+            let rhs_name = &monkey_rhs_names[i];
+            if lhs_name.is_empty() {
+                continue;
+            }
+            let find = |name: &str| -> Result<usize> {
+                return self
+                    .monkey_names
+                    .iter()
+                    .position(|n| n == name)
+                    .ok_or_else(|| Error::input(&format!("Unknown monkey '{name}'")));
+            };
+            let lhs = find(lhs_name)?;
+            let rhs = find(rhs_name)?;
+            self.monkey_lhs[i] = lhs;
+            self.monkey_rhs[i] = rhs;
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self)?.into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self)?.into());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -385,9 +394,9 @@ mod tests {
             152,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
-        assert_eq!(day.part1(), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part1().unwrap(), input.1);
     }
 
     #[test]
@@ -413,8 +422,64 @@ mod tests {
             301,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
-        assert_eq!(day.part2(), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part2().unwrap(), input.1);
+    }
+
+    #[test]
+    fn bad_input() {
+        let part2 = |input: Vec<&str>| {
+            let lines = str_array_to_string_array(input);
+            let mut day = Day::instance();
+            day.parse(&Input::from_lines(&lines)).unwrap();
+            return day.part2().map_err(|err| err.to_string());
+        };
+        // humn as the divisor is undone
+        assert_eq!(
+            part2(vec![
+                "root: aaaa + bbbb",
+                "aaaa: 12",
+                "bbbb: cccc / humn",
+                "cccc: 36",
+                "humn: 1"
+            ]),
+            Ok(3)
+        );
+        assert_eq!(
+            part2(vec![
+                "root: aaaa + bbbb",
+                "aaaa: 0",
+                "bbbb: cccc / humn",
+                "cccc: 36",
+                "humn: 1"
+            ]),
+            Err("Day00 unexpected input: Monkey bbbb can not be undone, it gives 0".to_string())
+        );
+        assert_eq!(
+            part2(vec![
+                "root: aaaa + bbbb",
+                "aaaa: humn * humn",
+                "bbbb: 4",
+                "humn: 1"
+            ]),
+            Err("Day00 unexpected input: Monkey aaaa uses humn on both sides".to_string())
+        );
+        assert_eq!(
+            part2(vec!["root: aaaa + bbbb", "aaaa: 2", "bbbb: 4", "humn: 1"]),
+            Err("Day00 unexpected input: Monkey root does not use humn".to_string())
+        );
+        assert_eq!(
+            part2(vec!["root: aaaa + humn", "aaaa: 2", "humn: aaaa + aaaa"]),
+            Err("Day00 unexpected input: Monkey humn is not a number".to_string())
+        );
+        let lines =
+            str_array_to_string_array(vec!["root: aaaa / bbbb", "aaaa: 2", "bbbb: 0", "humn: 1"]);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(
+            day.part1().unwrap_err().to_string(),
+            "Day00 unexpected input: Monkey root divides by 0 or overflows"
+        );
     }
 }
@@ -1,4 +1,7 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -179,16 +182,16 @@ What is the final password?
 
 */
 
-static INPUT_FILE: &str = "data/day22/input.txt";
+static INPUT_FILE: &str = "data/2022/day22/input.txt";
 
-pub fn run() {
-    println!("Day22: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day22: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 22,
+    input_file: INPUT_FILE,
+    instance: |part1| Box::new(Day::instance(part1)),
+};
 
-struct Day {
+pub struct Day {
     part1: bool,
     map: Vec<Vec<u8>>,
     movement: Vec<u8>,
@@ -197,7 +200,7 @@ struct Day {
     y_mins: Vec<usize>,
     y_maxs: Vec<usize>,
     face_size: usize,
-    face_data: [Vec<u8>; 6],
+    face_data: [Grid<u8>; 6],
     face_connect: [[(usize, usize); 4]; 6],
     face_y_mins: [usize; 6],
     face_x_mins: [usize; 6],
@@ -223,7 +226,7 @@ impl Day {
     const EDGE_LEFT: usize = 3;
     const EDGE_UNKNOWN: usize = 4;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance(part1: bool) -> Day {
         Day {
             part1: part1,
             map: Vec::new(),
@@ -233,7 +236,7 @@ impl Day {
             y_maxs: Vec::new(),
             movement: Vec::new(),
             face_size: 0,
-            face_data: [(); 6].map(|_| Vec::new()),
+            face_data: [(); 6].map(|_| Grid::new(0, 0, Day::VOID)),
             face_connect: [[(usize::MAX, usize::MAX); 4]; 6],
             face_y_mins: [usize::MAX; 6],
             face_x_mins: [usize::MAX; 6],
@@ -244,28 +247,173 @@ impl Day {
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
+    fn get_map_xy(&self, face: usize, face_x: usize, face_y: usize) -> (usize, usize) {
+        let map_x = self.face_x_mins[face] + face_x;
+        let map_y = self.face_y_mins[face] + face_y;
+        return (map_x, map_y);
+    }
 
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day22: Result1 {result1}");
-            let expected = 27492;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
+    fn move_monkey(&mut self, steps: u8) {
+        let mut x = self.monkey_x;
+        let mut y = self.monkey_y;
+        let mut face = self.monkey_face;
+        let mut facing = self.monkey_facing;
+        for _ in 0..steps {
+            let mut new_x = x;
+            let mut new_y = y;
+            let mut new_face = face;
+            let mut new_facing = facing;
+            let mut from_edge = Day::EDGE_UNKNOWN;
+            if facing == Day::FACE_L {
+                if x > 0 {
+                    new_x = x - 1;
+                } else {
+                    from_edge = Day::EDGE_LEFT;
+                }
+            } else if facing == Day::FACE_R {
+                if x < self.face_size - 1 {
+                    new_x = x + 1;
+                } else {
+                    from_edge = Day::EDGE_RIGHT;
+                }
+            } else if facing == Day::FACE_U {
+                if y > 0 {
+                    new_y = y - 1;
+                } else {
+                    from_edge = Day::EDGE_TOP;
+                }
+            } else if facing == Day::FACE_D {
+                if y < self.face_size - 1 {
+                    new_y = y + 1;
+                } else {
+                    from_edge = Day::EDGE_BOTTOM;
+                }
+            } else {
+                panic!("Unknown facing {facing}");
             }
-        } else {
-            let result2 = self.part2();
-            println!("Day22: Result2 {result2}");
-            let expected = 78291;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
+            if from_edge != Day::EDGE_UNKNOWN {
+                let connect = self.face_connect[face][from_edge];
+                new_face = connect.0;
+                let new_edge = connect.1;
+                if new_edge == Day::EDGE_TOP {
+                    new_x = match facing {
+                        Day::FACE_R => self.face_size - 1 - new_y,
+                        Day::FACE_D => new_x,
+                        Day::FACE_L => new_y,
+                        Day::FACE_U => self.face_size - 1 - new_x,
+                        _ => panic!("Bad facing"),
+                    };
+                    new_y = 0;
+                    new_facing = Day::FACE_D;
+                } else if new_edge == Day::EDGE_RIGHT {
+                    new_y = match facing {
+                        Day::FACE_R => self.face_size - 1 - new_y,
+                        Day::FACE_D => new_x,
+                        Day::FACE_L => new_y,
+                        Day::FACE_U => self.face_size - 1 - new_x,
+                        _ => panic!("Bad facing"),
+                    };
+                    new_x = self.face_size - 1;
+                    new_facing = Day::FACE_L;
+                } else if new_edge == Day::EDGE_BOTTOM {
+                    new_x = match facing {
+                        Day::FACE_R => new_y,
+                        Day::FACE_D => self.face_size - 1 - new_x,
+                        Day::FACE_L => self.face_size - 1 - new_y,
+                        Day::FACE_U => new_x,
+                        _ => panic!("Bad facing"),
+                    };
+                    new_y = self.face_size - 1;
+                    new_facing = Day::FACE_U;
+                } else if new_edge == Day::EDGE_LEFT {
+                    new_y = match facing {
+                        Day::FACE_R => new_y,
+                        Day::FACE_D => self.face_size - 1 - new_x,
+                        Day::FACE_L => self.face_size - 1 - new_y,
+                        Day::FACE_U => new_x,
+                        _ => panic!("Bad facing"),
+                    };
+                    new_x = 0;
+                    new_facing = Day::FACE_R;
+                } else {
+                    panic!("Unknown edge {new_edge}");
+                };
+            }
+            let map = self.face_data[new_face][(new_x, new_y)];
+            if map == Day::WALL {
+                break;
             }
+            assert_eq!(map, Day::CLEAR);
+            x = new_x;
+            y = new_y;
+            face = new_face;
+            facing = new_facing;
         }
+        self.monkey_x = x;
+        self.monkey_y = y;
+        self.monkey_face = face;
+        self.monkey_facing = facing;
     }
 
-    fn parse(&mut self, lines: &Vec<String>) {
+    fn simulate(&mut self) -> usize {
+        let mut distance: u8 = 0;
+        for i in 0..self.movement.len() {
+            let m = self.movement[i];
+            if m == Day::LEFT || m == Day::RIGHT {
+                self.move_monkey(distance);
+                distance = 0;
+            }
+            if m == Day::LEFT {
+                self.monkey_facing = match self.monkey_facing {
+                    Day::FACE_L => Day::FACE_D,
+                    Day::FACE_D => Day::FACE_R,
+                    Day::FACE_R => Day::FACE_U,
+                    Day::FACE_U => Day::FACE_L,
+                    _ => panic!("Unknown facing"),
+                }
+            } else if m == Day::RIGHT {
+                self.monkey_facing = match self.monkey_facing {
+                    Day::FACE_L => Day::FACE_U,
+                    Day::FACE_U => Day::FACE_R,
+                    Day::FACE_R => Day::FACE_D,
+                    Day::FACE_D => Day::FACE_L,
+                    _ => panic!("Unknown facing"),
+                }
+            } else {
+                distance *= 10;
+                distance += m - '0' as u8;
+            }
+        }
+        self.move_monkey(distance);
+        let (x, y) = self.get_map_xy(self.monkey_face, self.monkey_x, self.monkey_y);
+        // let facing = self.get_map_facing(self.monkey_face, self.monkey_facing);
+        return 1000 * (y + 1) + 4 * (x + 1) + self.monkey_facing;
+    }
+
+    pub fn part1(&mut self) -> usize {
+        self.monkey_x = 0;
+        self.monkey_y = 0;
+        self.monkey_face = 0;
+        self.monkey_facing = Day::FACE_R;
+        return self.simulate();
+    }
+
+    pub fn part2(&mut self) -> usize {
+        self.monkey_x = 0;
+        self.monkey_y = 0;
+        self.monkey_face = 0;
+        self.monkey_facing = Day::FACE_R;
+        return self.simulate();
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        // The map, a blank line and the path
+        if lines.len() < 3 {
+            return Err(Error::input("Expected a map and a path"));
+        }
         let height = lines.len() - 2;
         let width = height;
         self.x_mins.resize(height, usize::MAX);
@@ -279,8 +427,11 @@ impl Day {
             let data = line.as_bytes().to_vec();
             if i != lines.len() - 1 {
                 let y = i;
-                let x_min = data.iter().position(|&c| c != Day::VOID).unwrap();
-                let x_max = data.iter().rposition(|&c| c != Day::VOID).unwrap();
+                let x_min = data.iter().position(|&c| c != Day::VOID);
+                let x_max = data.iter().rposition(|&c| c != Day::VOID);
+                let (Some(x_min), Some(x_max)) = (x_min, x_max) else {
+                    return Err(Error::parse(i, 1, "Empty map row"));
+                };
                 self.x_mins[y] = x_min;
                 self.x_maxs[y] = x_max;
                 //     AAAA
@@ -318,10 +469,14 @@ impl Day {
             .min()
             .unwrap()
             + 1;
-        assert_eq!(face_height, face_width);
+        if face_height != face_width {
+            return Err(Error::input(&format!(
+                "Faces are {face_width} wide and {face_height} high"
+            )));
+        }
         self.face_size = face_width;
         for f in 0..6 {
-            self.face_data[f].resize(face_width * face_height, Day::VOID);
+            self.face_data[f] = Grid::new(face_width, face_height, Day::VOID);
         }
         let mut face = 0;
         for map_y in (0..self.map.len()).step_by(face_height) {
@@ -334,8 +489,7 @@ impl Day {
                 self.face_y_mins[face] = map_y;
                 for y in 0..face_height {
                     for x in 0..face_width {
-                        let index = x + y * face_width;
-                        self.face_data[face][index] = self.map[y + map_y][x + map_x];
+                        self.face_data[face][(x, y)] = self.map[y + map_y][x + map_x];
                     }
                 }
                 face += 1;
@@ -641,7 +795,7 @@ impl Day {
                 ];
             }
         } else {
-            panic!("Unknown face size {face_width}");
+            return Err(Error::input(&format!("Unknown face size {face_width}")));
         }
         for f in 0..6 {
             for d in 0..4 {
@@ -655,172 +809,22 @@ impl Day {
         self.monkey_x = 0;
         self.monkey_y = 0;
         self.monkey_facing = Day::FACE_R;
+        return Ok(());
     }
 
-    fn get_map_xy(&self, face: usize, face_x: usize, face_y: usize) -> (usize, usize) {
-        let map_x = self.face_x_mins[face] + face_x;
-        let map_y = self.face_y_mins[face] + face_y;
-        return (map_x, map_y);
-    }
-
-    fn move_monkey(&mut self, steps: u8) {
-        let mut x = self.monkey_x;
-        let mut y = self.monkey_y;
-        let mut face = self.monkey_face;
-        let mut facing = self.monkey_facing;
-        for _ in 0..steps {
-            let mut new_x = x;
-            let mut new_y = y;
-            let mut new_face = face;
-            let mut new_facing = facing;
-            let mut from_edge = Day::EDGE_UNKNOWN;
-            if facing == Day::FACE_L {
-                if x > 0 {
-                    new_x = x - 1;
-                } else {
-                    from_edge = Day::EDGE_LEFT;
-                }
-            } else if facing == Day::FACE_R {
-                if x < self.face_size - 1 {
-                    new_x = x + 1;
-                } else {
-                    from_edge = Day::EDGE_RIGHT;
-                }
-            } else if facing == Day::FACE_U {
-                if y > 0 {
-                    new_y = y - 1;
-                } else {
-                    from_edge = Day::EDGE_TOP;
-                }
-            } else if facing == Day::FACE_D {
-                if y < self.face_size - 1 {
-                    new_y = y + 1;
-                } else {
-                    from_edge = Day::EDGE_BOTTOM;
-                }
-            } else {
-                panic!("Unknown facing {facing}");
-            }
-            if from_edge != Day::EDGE_UNKNOWN {
-                let connect = self.face_connect[face][from_edge];
-                new_face = connect.0;
-                let new_edge = connect.1;
-                if new_edge == Day::EDGE_TOP {
-                    new_x = match facing {
-                        Day::FACE_R => self.face_size - 1 - new_y,
-                        Day::FACE_D => new_x,
-                        Day::FACE_L => new_y,
-                        Day::FACE_U => self.face_size - 1 - new_x,
-                        _ => panic!("Bad facing"),
-                    };
-                    new_y = 0;
-                    new_facing = Day::FACE_D;
-                } else if new_edge == Day::EDGE_RIGHT {
-                    new_y = match facing {
-                        Day::FACE_R => self.face_size - 1 - new_y,
-                        Day::FACE_D => new_x,
-                        Day::FACE_L => new_y,
-                        Day::FACE_U => self.face_size - 1 - new_x,
-                        _ => panic!("Bad facing"),
-                    };
-                    new_x = self.face_size - 1;
-                    new_facing = Day::FACE_L;
-                } else if new_edge == Day::EDGE_BOTTOM {
-                    new_x = match facing {
-                        Day::FACE_R => new_y,
-                        Day::FACE_D => self.face_size - 1 - new_x,
-                        Day::FACE_L => self.face_size - 1 - new_y,
-                        Day::FACE_U => new_x,
-                        _ => panic!("Bad facing"),
-                    };
-                    new_y = self.face_size - 1;
-                    new_facing = Day::FACE_U;
-                } else if new_edge == Day::EDGE_LEFT {
-                    new_y = match facing {
-                        Day::FACE_R => new_y,
-                        Day::FACE_D => self.face_size - 1 - new_x,
-                        Day::FACE_L => self.face_size - 1 - new_y,
-                        Day::FACE_U => new_x,
-                        _ => panic!("Bad facing"),
-                    };
-                    new_x = 0;
-                    new_facing = Day::FACE_R;
-                } else {
-                    panic!("Unknown edge {new_edge}");
-                };
-            }
-            let map = self.face_data[new_face][new_x + new_y * self.face_size];
-            if map == Day::WALL {
-                break;
-            }
-            assert_eq!(map, Day::CLEAR);
-            x = new_x;
-            y = new_y;
-            face = new_face;
-            facing = new_facing;
-        }
-        self.monkey_x = x;
-        self.monkey_y = y;
-        self.monkey_face = face;
-        self.monkey_facing = facing;
-    }
-
-    fn simulate(&mut self) -> usize {
-        let mut distance: u8 = 0;
-        for i in 0..self.movement.len() {
-            let m = self.movement[i];
-            if m == Day::LEFT || m == Day::RIGHT {
-                self.move_monkey(distance);
-                distance = 0;
-            }
-            if m == Day::LEFT {
-                self.monkey_facing = match self.monkey_facing {
-                    Day::FACE_L => Day::FACE_D,
-                    Day::FACE_D => Day::FACE_R,
-                    Day::FACE_R => Day::FACE_U,
-                    Day::FACE_U => Day::FACE_L,
-                    _ => panic!("Unknown facing"),
-                }
-            } else if m == Day::RIGHT {
-                self.monkey_facing = match self.monkey_facing {
-                    Day::FACE_L => Day::FACE_U,
-                    Day::FACE_U => Day::FACE_R,
-                    Day::FACE_R => Day::FACE_D,
-                    Day::FACE_D => Day::FACE_L,
-                    _ => panic!("Unknown facing"),
-                }
-            } else {
-                distance *= 10;
-                distance += m - '0' as u8;
-            }
-        }
-        self.move_monkey(distance);
-        let (x, y) = self.get_map_xy(self.monkey_face, self.monkey_x, self.monkey_y);
-        // let facing = self.get_map_facing(self.monkey_face, self.monkey_facing);
-        return 1000 * (y + 1) + 4 * (x + 1) + self.monkey_facing;
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
     }
 
-    fn part1(&mut self) -> usize {
-        self.monkey_x = 0;
-        self.monkey_y = 0;
-        self.monkey_face = 0;
-        self.monkey_facing = Day::FACE_R;
-        return self.simulate();
-    }
-
-    fn part2(&mut self) -> usize {
-        self.monkey_x = 0;
-        self.monkey_y = 0;
-        self.monkey_face = 0;
-        self.monkey_facing = Day::FACE_R;
-        return self.simulate();
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -845,7 +849,7 @@ mod tests {
         );
         let lines = str_array_to_string_array(input.0);
         let mut day = Day::instance(true);
-        day.parse(&lines);
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 
@@ -872,7 +876,7 @@ mod tests {
         );
         let lines = str_array_to_string_array(input.0);
         let mut day = Day::instance(false);
-        day.parse(&lines);
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
 }
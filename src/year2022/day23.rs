@@ -1,4 +1,8 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::render::{Frames, Image};
+use crate::solution::{Answer, Puzzle, Solution};
 use std::collections::HashMap;
 
 /*
@@ -262,109 +266,97 @@ What is the number of the first round where no Elf moves?
 
 */
 
-static INPUT_FILE: &str = "data/day23/input.txt";
+static INPUT_FILE: &str = "data/2022/day23/input.txt";
 
-pub fn run() {
-    println!("Day23: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day23: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 23,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
-    part1: bool,
+pub struct Day {
+    // The grid index of each elf
     elves: Vec<usize>,
-    grid: Vec<bool>,
+    grid: Grid<bool>,
     elf_count: usize,
     grid_min_x: usize,
     grid_min_y: usize,
     grid_max_x: usize,
     grid_max_y: usize,
+    frames: Option<Frames>,
 }
 
 impl Day {
-    // const EMPTY: char = '.';
+    const EMPTY: char = '.';
     const ELF: char = '#';
     const MOVE_FREE: usize = usize::MAX;
     const MOVE_COLLIDE: usize = usize::MAX - 1;
     const GRID_WIDTH: usize = 2 * 1024;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
             elves: Vec::new(),
-            grid: Vec::new(),
+            grid: Grid::new(0, 0, false),
             elf_count: 0,
             grid_min_x: usize::MAX,
             grid_min_y: usize::MAX,
             grid_max_x: usize::MIN,
             grid_max_y: usize::MIN,
+            frames: None,
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1(10);
-            println!("Day23: Result1 {result1}");
-            let expected = 4109;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day23: Result2 {result2}");
-            let expected = 1055;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
+    fn compute_min_max(&mut self) {
+        for e in 0..self.elf_count {
+            let index = self.elves[e];
+            let (x, y) = self.grid.xy(index);
+            self.grid_min_x = self.grid_min_x.min(x);
+            self.grid_max_x = self.grid_max_x.max(x);
+            self.grid_min_y = self.grid_min_y.min(y);
+            self.grid_max_y = self.grid_max_y.max(y);
         }
     }
 
-    fn parse(&mut self, lines: &Vec<String>) {
-        assert!(Day::GRID_WIDTH > lines.len() * 16);
-        let y0 = Day::GRID_WIDTH / 2;
-        let x0 = Day::GRID_WIDTH / 2;
-        let mut y = y0;
-        self.grid.resize(2048 * 2048, false);
-        for line in lines {
-            assert!(Day::GRID_WIDTH > line.len() * 16);
-            let bytes = line.bytes();
-            let mut x = x0;
-            for c in bytes {
-                let index = Day::get_index_from_xy(x, y);
-                if c == Day::ELF as u8 {
-                    self.elf_count += 1;
-                    self.elves.push(index);
-                    self.grid[index] = true;
-                }
-                x += 1;
-            }
-            y += 1;
+    // The top left and bottom right corners of the rectangle around the elves now
+    // compute_min_max keeps the bounds of every round so far
+    fn bounds(&self) -> ((usize, usize), (usize, usize)) {
+        let mut min = (usize::MAX, usize::MAX);
+        let mut max = (0, 0);
+        for &index in &self.elves {
+            let (x, y) = self.grid.xy(index);
+            min = (min.0.min(x), min.1.min(y));
+            max = (max.0.max(x), max.1.max(y));
         }
+        return (min, max);
     }
 
-    fn get_index_from_xy(x: usize, y: usize) -> usize {
-        return y * Day::GRID_WIDTH + x;
+    fn output_grid(&self) -> String {
+        let (min, max) = self.bounds();
+        let mut text = String::new();
+        for y in min.1..=max.1 {
+            for x in min.0..=max.0 {
+                text.push(if self.grid[(x, y)] { '#' } else { '.' });
+            }
+            text.push('\n');
+        }
+        return text;
     }
 
-    fn get_xy_from_index(index: usize) -> (usize, usize) {
-        let y = index / Day::GRID_WIDTH;
-        let x = index % Day::GRID_WIDTH;
-        assert_eq!(index, Day::get_index_from_xy(x, y));
-        return (x, y);
+    fn image(&self) -> Image {
+        let (min, max) = self.bounds();
+        return Grid::from_fn(max.0 - min.0 + 1, max.1 - min.1 + 1, |x, y| {
+            if self.grid[(min.0 + x, min.1 + y)] {
+                return [90, 200, 90];
+            }
+            return [20, 20, 30];
+        });
     }
 
-    fn compute_min_max(&mut self) {
-        for e in 0..self.elf_count {
-            let index = self.elves[e];
-            let (x, y) = Day::get_xy_from_index(index);
-            self.grid_min_x = self.grid_min_x.min(x);
-            self.grid_max_x = self.grid_max_x.max(x);
-            self.grid_min_y = self.grid_min_y.min(y);
-            self.grid_max_y = self.grid_max_y.max(y);
+    fn send_frame(&mut self) {
+        if let Some(mut frames) = self.frames.take() {
+            frames(&self.image());
+            self.frames = Some(frames);
         }
     }
 
@@ -374,14 +366,13 @@ impl Day {
             let mut neighbours = [0; 9];
             let mut count_neighbours = 0;
             let from_index = self.elves[elf];
-            let (x, y) = Day::get_xy_from_index(from_index);
+            let (x, y) = self.grid.xy(from_index);
             for iy in 0..3 {
                 let yn = y - 1 + iy;
                 for ix in 0..3 {
                     let xn = x - 1 + ix;
-                    let idx = Day::get_index_from_xy(xn, yn);
-                    if idx != from_index {
-                        if self.grid[idx] {
+                    if ix != 1 || iy != 1 {
+                        if self.grid[(xn, yn)] {
                             let n_index = ix + iy * 3;
                             neighbours[n_index] = 1;
                             count_neighbours += 1;
@@ -408,19 +399,19 @@ impl Day {
             ];
             // no Elf N, NE, or NW positions, go north
             if n == 0 && ne == 0 && nw == 0 {
-                proposed_moves[0] = Day::get_index_from_xy(x, y - 1);
+                proposed_moves[0] = self.grid.index(x, y - 1);
             };
             // no Elf S, SE, or SW positions, go south
             if s == 0 && se == 0 && sw == 0 {
-                proposed_moves[1] = Day::get_index_from_xy(x, y + 1);
+                proposed_moves[1] = self.grid.index(x, y + 1);
             };
             // no Elf W, NW, or SW positions, go west
             if w == 0 && nw == 0 && sw == 0 {
-                proposed_moves[2] = Day::get_index_from_xy(x - 1, y);
+                proposed_moves[2] = self.grid.index(x - 1, y);
             };
             // no Elf E, NE, or SE positions, go east
             if e == 0 && ne == 0 && se == 0 {
-                proposed_moves[3] = Day::get_index_from_xy(x + 1, y);
+                proposed_moves[3] = self.grid.index(x + 1, y);
             };
             let mut to_index = Day::MOVE_FREE;
             let rule = round % 4;
@@ -446,19 +437,22 @@ impl Day {
             }
             let from_index = self.elves[elf];
             assert_ne!(to_index, from_index);
-            self.grid[from_index] = false;
-            self.grid[to_index] = true;
+            let from = self.grid.xy(from_index);
+            let to = self.grid.xy(to_index);
+            self.grid[from] = false;
+            self.grid[to] = true;
             self.elves[elf] = to_index;
             moved = true;
         }
         return moved;
     }
 
-    fn part1(&mut self, count_rounds: usize) -> usize {
+    pub fn part1(&mut self, count_rounds: usize) -> usize {
+        self.send_frame();
         for r in 0..count_rounds {
-            // self.compute_min_max();
-            // self.output_grid();
+            crate::log_trace!("Round {r}\n{}", self.output_grid());
             self.simulate_round(r);
+            self.send_frame();
         }
         self.compute_min_max();
         let empty_count = (self.grid_max_y - self.grid_min_y + 1)
@@ -467,21 +461,73 @@ impl Day {
         return empty_count;
     }
 
-    fn part2(&mut self) -> usize {
+    pub fn part2(&mut self) -> usize {
         let max_rounds: usize = 2000;
+        self.send_frame();
         for r in 0..max_rounds {
             if !self.simulate_round(r) {
                 return r + 1;
             }
+            self.send_frame();
         }
         panic!("Ran out of rounds {max_rounds}");
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        // Leave room for the elves to spread out
+        if Day::GRID_WIDTH <= lines.len() * 16 {
+            return Err(Error::input(&format!("Too many lines {}", lines.len())));
+        }
+        let y0 = Day::GRID_WIDTH / 2;
+        let x0 = Day::GRID_WIDTH / 2;
+        let mut y = y0;
+        self.grid = Grid::new(Day::GRID_WIDTH, Day::GRID_WIDTH, false);
+        for (i, line) in lines.iter().enumerate() {
+            if Day::GRID_WIDTH <= line.len() * 16 {
+                return Err(Error::parse(i, 1, &format!("Line too long {}", line.len())));
+            }
+            let bytes = line.bytes();
+            let mut x = x0;
+            for c in bytes {
+                if c == Day::ELF as u8 {
+                    self.elf_count += 1;
+                    self.elves.push(self.grid.index(x, y));
+                    self.grid[(x, y)] = true;
+                } else if c != Day::EMPTY as u8 {
+                    let message = format!("Expected . or # not '{}'", c as char);
+                    return Err(Error::parse(i, x - x0 + 1, &message));
+                }
+                x += 1;
+            }
+            y += 1;
+        }
+        if self.elf_count == 0 {
+            return Err(Error::input("No elves"));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self, 10).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+
+    fn set_frames(&mut self, frames: Frames) -> bool {
+        self.frames = Some(frames);
+        return true;
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -492,8 +538,8 @@ mod tests {
             110,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(10), input.1);
     }
 
@@ -506,8 +552,26 @@ mod tests {
             20,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part2(), input.1);
     }
+
+    #[test]
+    fn bad_input() {
+        let lines = str_array_to_string_array(vec!["..#", ".x#"]);
+        let err = Day::instance()
+            .parse(&Input::from_lines(&lines))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day00 line 2 column 2: Expected . or # not 'x'"
+        );
+        let lines = str_array_to_string_array(vec!["...", "..."]);
+        let err = Day::instance()
+            .parse(&Input::from_lines(&lines))
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Day00 unexpected input: No elves");
+        assert!(Day::instance().parse(&Input::default()).is_err());
+    }
 }
@@ -1,5 +1,9 @@
-use crate::file_to_vec;
-use std::collections::HashMap;
+use crate::error::{Error, Result};
+use crate::grid::Grid;
+use crate::input::Input;
+use crate::render::{Frames, Image};
+use crate::search::astar;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -273,34 +277,26 @@ What is the fewest number of minutes required to reach the goal, go back to the
 
 */
 
-static INPUT_FILE: &str = "data/day24/input.txt";
-
-pub fn run() {
-    println!("Day24: Start");
-    Day::instance(true).execute();
-    Day::instance(false).execute();
-    println!("Day24: End");
-}
-
-struct Move {
-    x: usize,
-    y: usize,
-    t: usize,
-}
-
-struct Day {
-    part1: bool,
-    initial_grid: Vec<u8>,
-    grid_width: usize,
-    grid_height: usize,
-    moves: Vec<Move>,
-    visited: HashMap<usize, Vec<usize>>,
+static INPUT_FILE: &str = "data/2022/day24/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 24,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
+
+pub struct Day {
+    initial_grid: Grid<u8>,
+    // The inside of the valley where the blizzards wrap round
+    blizzards: Grid<u8>,
+    // The blizzards are back where they started after this many minutes
+    period: usize,
     start_x: usize,
     start_y: usize,
     end_x: usize,
     end_y: usize,
-    blizzard_width: usize,
-    blizzard_height: usize,
+    frames: Option<Frames>,
 }
 
 impl Day {
@@ -312,144 +308,66 @@ impl Day {
     const BLIZZARD_DOWN: u8 = 'v' as u8;
     const BLIZZARD_LEFT: u8 = '<' as u8;
 
-    fn instance(part1: bool) -> Day {
+    pub fn instance() -> Day {
         Day {
-            part1: part1,
-            initial_grid: Vec::new(),
-            grid_width: usize::MAX,
-            grid_height: usize::MAX,
-            moves: Vec::new(),
-            visited: HashMap::new(),
+            initial_grid: Grid::new(0, 0, Day::EMPTY),
+            blizzards: Grid::new(0, 0, Day::EMPTY),
+            period: 1,
             start_x: usize::MAX,
             start_y: usize::MAX,
             end_x: usize::MAX,
             end_y: usize::MAX,
-            blizzard_width: usize::MAX,
-            blizzard_height: usize::MAX,
+            frames: None,
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        if self.part1 {
-            let result1 = self.part1();
-            println!("Day24: Result1 {result1}");
-            let expected = 281;
-            if result1 != expected {
-                panic!("Part1 is broken {result1} != {expected}");
-            }
-        } else {
-            let result2 = self.part2();
-            println!("Day24: Result2 {result2}");
-            let expected = 807;
-            if result2 != expected {
-                panic!("Part2 is broken {result2} != {expected}");
-            }
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        self.grid_width = lines[0].len();
-        self.grid_height = lines.len();
-        self.blizzard_width = self.grid_width - 2;
-        self.blizzard_height = self.grid_height - 2;
-        self.start_x = lines[0].find(".").unwrap();
-        self.start_y = 0;
-        self.end_x = lines[lines.len() - 1].find(".").unwrap();
-        self.end_y = self.grid_height - 1;
-        self.initial_grid
-            .resize(self.grid_width * self.grid_height, Day::EMPTY);
-        for y in 0..lines.len() {
-            let line = &lines[y];
-            // #>>.<^<#
-            let bytes = line.as_bytes();
-            for x in 0..line.len() {
-                let b = bytes[x];
-                let idx = self.get_index_from_xy(x, y);
-                self.initial_grid[idx] = b;
-            }
-        }
-        let start_idx = self.get_index_from_xy(self.start_x, self.start_y);
-        self.initial_grid[start_idx] = Day::START_END;
-        let end_idx = self.get_index_from_xy(self.end_x, self.end_y);
-        self.initial_grid[end_idx] = Day::START_END;
-    }
-
-    fn get_index_from_xy(&self, x: usize, y: usize) -> usize {
-        return self.grid_width * y + x;
-    }
-
     fn valid_move(&self, x: usize, y: usize, t: usize) -> bool {
-        if x >= self.grid_width {
+        let Some(&cell) = self.initial_grid.get(x, y) else {
             return false;
-        }
-        if y >= self.grid_height {
+        };
+        if cell == Day::WALL {
             return false;
         }
-        if self.initial_grid[self.get_index_from_xy(x, y)] == Day::WALL {
-            return false;
-        }
-        if self.initial_grid[self.get_index_from_xy(x, y)] == Day::START_END {
+        if cell == Day::START_END {
             return true;
         }
-        let width = self.blizzard_width;
-        let blizzard_steps_x = t % width;
-        // bx(<) = 1 + (x - 1 + width - blizzard_steps_x) % width
-        let b_max_x = 1 + (x - 1 + blizzard_steps_x) % width;
-        if self.initial_grid[self.get_index_from_xy(b_max_x, y)] == Day::BLIZZARD_LEFT {
-            return false;
-        }
-
-        // bx(>) = 1 + (x - 1 + blizzard_steps_x) % width
-        let b_min_x = 1 + (x - 1 + width - blizzard_steps_x) % width;
-        if self.initial_grid[self.get_index_from_xy(b_min_x, y)] == Day::BLIZZARD_RIGHT {
-            return false;
-        }
-
-        let height = self.blizzard_height;
-        let blizzard_steps_y = t % height;
-        // by(v) = 1 + (y - 1 + height - blizzard_steps_y) % height
-        let b_min_y = 1 + (y - 1 + height - blizzard_steps_y) % height;
-        if self.initial_grid[self.get_index_from_xy(x, b_min_y)] == Day::BLIZZARD_DOWN {
-            return false;
-        }
-        // by(^) = 1 + (y - 1 + blizzard_steps_y) % height
-        let b_max_y = 1 + (y - 1 + blizzard_steps_y) % height;
-        if self.initial_grid[self.get_index_from_xy(x, b_max_y)] == Day::BLIZZARD_UP {
-            return false;
-        }
+        return !self.blizzard_at(x, y, t);
+    }
 
-        return true;
+    // A blizzard is at x, y inside the valley at time t if it started t steps back along
+    // its direction
+    fn blizzard_at(&self, x: usize, y: usize, t: usize) -> bool {
+        let bx = x as i64 - 1;
+        let by = y as i64 - 1;
+        let t = t as i64;
+        return *self.blizzards.get_wrapped(bx + t, by) == Day::BLIZZARD_LEFT
+            || *self.blizzards.get_wrapped(bx - t, by) == Day::BLIZZARD_RIGHT
+            || *self.blizzards.get_wrapped(bx, by - t) == Day::BLIZZARD_DOWN
+            || *self.blizzards.get_wrapped(bx, by + t) == Day::BLIZZARD_UP;
     }
 
-    fn add_move(
-        &mut self,
-        x: usize,
-        y: usize,
-        t: usize,
-        end_x: usize,
-        end_y: usize,
-        is_wait: bool,
-    ) -> bool {
-        if x == end_x && y == end_y {
-            return true;
-        }
-        if !self.valid_move(x, y, t) {
-            return false;
-        }
-        if !is_wait {
-            let idx = self.get_index_from_xy(x, y);
-            if self.visited[&idx].contains(&t) {
-                return false;
-            }
-            self.visited.entry(idx).and_modify(|v| v.push(t));
-        }
-        self.moves.push(Move { x, y, t });
-        return false;
+    // The valley at time t with the expedition at x, y
+    fn image(&self, x: usize, y: usize, t: usize) -> Image {
+        return Grid::from_fn(
+            self.initial_grid.width(),
+            self.initial_grid.height(),
+            |gx, gy| {
+                let cell = self.initial_grid[(gx, gy)];
+                if (gx, gy) == (x, y) {
+                    return [230, 80, 80];
+                }
+                if cell == Day::WALL {
+                    return [120, 120, 130];
+                }
+                if cell != Day::START_END && self.blizzard_at(gx, gy, t) {
+                    return [90, 150, 230];
+                }
+                return [20, 20, 30];
+            },
+        );
     }
 
+    // Returns the minute the end is reached
     fn minimum_steps(
         &mut self,
         start_t: usize,
@@ -457,62 +375,135 @@ impl Day {
         start_y: usize,
         end_x: usize,
         end_y: usize,
-    ) -> usize {
-        self.moves.clear();
-        self.visited.clear();
-        for idx in 0..self.grid_height * self.grid_width {
-            self.visited.insert(idx, Vec::new());
-        }
-        self.moves.push(Move {
-            x: start_x,
-            y: start_y,
-            t: start_t,
-        });
-        let max_t = start_t + (self.grid_width + self.grid_height) * 2;
-        let mut min_steps = max_t;
-        while !self.moves.is_empty() {
-            let m = self.moves.pop().unwrap();
-            let x = m.x;
-            let y = m.y;
-            let new_t = m.t + 1;
-            if new_t >= min_steps {
-                continue;
+    ) -> Result<usize> {
+        // The blizzards repeat so a state only needs the time within the period
+        let path = astar(
+            [(start_x, start_y, start_t % self.period)],
+            |&(x, y, t)| {
+                let new_t = (t + 1) % self.period;
+                let mut moves = Vec::new();
+                // Wait or move
+                if self.valid_move(x, y, new_t) {
+                    moves.push(((x, y, new_t), 1));
+                }
+                for (new_x, new_y) in self.initial_grid.neighbours4(x, y) {
+                    if self.valid_move(new_x, new_y, new_t) {
+                        moves.push(((new_x, new_y, new_t), 1));
+                    }
+                }
+                return moves;
+            },
+            |&(x, y, _)| x.abs_diff(end_x) + y.abs_diff(end_y),
+            |&(x, y, _)| x == end_x && y == end_y,
+        );
+        let Some(path) = path else {
+            return Err(Error::input("No way through the blizzards"));
+        };
+        if let Some(mut frames) = self.frames.take() {
+            for &(x, y, t) in &path.states {
+                frames(&self.image(x, y, t));
             }
-            let mut at_end = false;
+            self.frames = Some(frames);
+        }
+        return Ok(start_t + path.cost);
+    }
 
-            at_end |= self.add_move(x, y, new_t, end_x, end_y, true);
-            at_end |= self.add_move(x, y + 1, new_t, end_x, end_y, false);
-            at_end |= self.add_move(x + 1, y, new_t, end_x, end_y, false);
-            if x > 0 {
-                at_end |= self.add_move(x - 1, y, new_t, end_x, end_y, false);
-            }
-            if y > 0 {
-                at_end |= self.add_move(x, y - 1, new_t, end_x, end_y, false);
-            }
-            if at_end {
-                min_steps = min_steps.min(new_t);
+    pub fn part1(&mut self) -> Result<usize> {
+        return self.minimum_steps(0, self.start_x, self.start_y, self.end_x, self.end_y);
+    }
+
+    pub fn part2(&mut self) -> Result<usize> {
+        let steps1 = self.minimum_steps(0, self.start_x, self.start_y, self.end_x, self.end_y)?;
+        let steps2 =
+            self.minimum_steps(steps1, self.end_x, self.end_y, self.start_x, self.start_y)?;
+        let steps3 =
+            self.minimum_steps(steps2, self.start_x, self.start_y, self.end_x, self.end_y)?;
+        return Ok(steps3);
+    }
+}
+
+fn lcm(a: usize, b: usize) -> usize {
+    let mut x = a;
+    let mut y = b;
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    return a / x * b;
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        let lines: Vec<&str> = input.lines().collect();
+        // A wall all the way round with room for a blizzard inside
+        if lines.len() < 3 || lines[0].len() < 3 {
+            return Err(Error::input("The valley is too small"));
+        }
+        // #>>.<^<#
+        self.initial_grid = Grid::parse(&lines, "Unknown map character", |_, _, c| {
+            [
+                Day::EMPTY,
+                Day::WALL,
+                Day::BLIZZARD_UP,
+                Day::BLIZZARD_RIGHT,
+                Day::BLIZZARD_DOWN,
+                Day::BLIZZARD_LEFT,
+            ]
+            .contains(&c)
+            .then_some(c)
+        })?;
+        let width = self.initial_grid.width();
+        let height = self.initial_grid.height();
+        let mut blizzards = Grid::new(width - 2, height - 2, Day::EMPTY);
+        for y in 1..height - 1 {
+            for x in 1..width - 1 {
+                blizzards[(x - 1, y - 1)] = self.initial_grid[(x, y)];
             }
         }
-        assert_ne!(min_steps, max_t);
-        return min_steps;
+        self.blizzards = blizzards;
+        self.period = lcm(width - 2, height - 2);
+        self.start_x = match self
+            .initial_grid
+            .row(0)
+            .iter()
+            .position(|&c| c == Day::EMPTY)
+        {
+            Some(x) => x,
+            None => return Err(Error::parse(0, 1, "No start in the top wall")),
+        };
+        self.start_y = 0;
+        self.end_x = match self
+            .initial_grid
+            .row(height - 1)
+            .iter()
+            .position(|&c| c == Day::EMPTY)
+        {
+            Some(x) => x,
+            None => return Err(Error::parse(height - 1, 1, "No end in the bottom wall")),
+        };
+        self.end_y = height - 1;
+        self.initial_grid[(self.start_x, self.start_y)] = Day::START_END;
+        self.initial_grid[(self.end_x, self.end_y)] = Day::START_END;
+        return Ok(());
     }
 
-    fn part1(&mut self) -> usize {
-        return self.minimum_steps(0, self.start_x, self.start_y, self.end_x, self.end_y);
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self)?.into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self)?.into());
     }
 
-    fn part2(&mut self) -> usize {
-        let steps1 = self.minimum_steps(0, self.start_x, self.start_y, self.end_x, self.end_y);
-        let steps2 = self.minimum_steps(steps1, self.end_x, self.end_y, self.start_x, self.start_y);
-        let steps3 = self.minimum_steps(steps2, self.start_x, self.start_y, self.end_x, self.end_y);
-        return steps3;
+    fn set_frames(&mut self, frames: Frames) -> bool {
+        self.frames = Some(frames);
+        return true;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn part1() {
@@ -523,9 +514,9 @@ mod tests {
             18,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(true);
-        day.parse(&lines);
-        assert_eq!(day.part1(), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part1().unwrap(), input.1);
     }
 
     #[test]
@@ -537,8 +528,21 @@ mod tests {
             54,
         );
         let lines = str_array_to_string_array(input.0);
-        let mut day = Day::instance(false);
-        day.parse(&lines);
-        assert_eq!(day.part2(), input.1);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part2().unwrap(), input.1);
+    }
+
+    #[test]
+    fn blocked() {
+        // The blizzard never leaves the only cell between the start and the end
+        let lines = str_array_to_string_array(vec!["#.#", "#>#", "#.#"]);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        let err = day.part1().unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Day00 unexpected input: No way through the blizzards"
+        );
     }
 }
@@ -1,4 +1,6 @@
-use crate::file_to_vec;
+use crate::error::{Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
 
 /*
 
@@ -132,15 +134,16 @@ What SNAFU number do you supply to Bob's console?
 
 */
 
-static INPUT_FILE: &str = "data/day25/input.txt";
+static INPUT_FILE: &str = "data/2022/day25/input.txt";
 
-pub fn run() {
-    println!("Day25: Start");
-    Day::instance().execute();
-    println!("Day25: End");
-}
+pub static PUZZLE: Puzzle = Puzzle {
+    year: 2022,
+    day: 25,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
 
-struct Day {
+pub struct Day {
     snafus: Vec<String>,
     decimals: Vec<usize>,
 }
@@ -152,34 +155,14 @@ impl Day {
     const SNAFU_MINUS: u8 = '-' as u8;
     const SNAFU_EQUALS: u8 = '=' as u8;
 
-    fn instance() -> Day {
+    pub fn instance() -> Day {
         Day {
             snafus: Vec::new(),
             decimals: Vec::new(),
         }
     }
 
-    fn execute(&mut self) {
-        let lines = file_to_vec(INPUT_FILE).expect("Could not load file");
-        self.parse(&lines);
-
-        let result1 = self.part1();
-        println!("Day25: Result1 {result1}");
-        let expected = "2=01-0-2-0=-0==-1=01";
-        if result1 != expected {
-            panic!("Part1 is broken {result1} != {expected}");
-        }
-    }
-
-    fn parse(&mut self, lines: &Vec<String>) {
-        for line in lines {
-            let snafu = line;
-            self.snafus.push(snafu.to_owned());
-            self.decimals.push(Day::snafu_to_decimal(&snafu));
-        }
-    }
-
-    fn decimal_to_snafu(decimal: &String) -> String {
+    pub fn decimal_to_snafu(decimal: &str) -> String {
         let mut source: usize = decimal.parse().unwrap();
 
         // Base 5 with 0, 1, 2, - (-1), = (-2).
@@ -218,7 +201,7 @@ impl Day {
         return result;
     }
 
-    fn snafu_to_decimal(snafu: &String) -> usize {
+    pub fn snafu_to_decimal(snafu: &str) -> usize {
         let mut source = snafu.as_bytes().to_owned();
         source.reverse();
 
@@ -241,7 +224,7 @@ impl Day {
         return result as usize;
     }
 
-    fn part1(&self) -> String {
+    pub fn part1(&self) -> String {
         let total: usize = self.decimals.iter().sum();
         let decimal = total.to_string();
         let snafu = Day::decimal_to_snafu(&decimal);
@@ -249,10 +232,32 @@ impl Day {
     }
 }
 
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            let snafu = line;
+            if let Some(column) = snafu.find(|c| !"=-012".contains(c)) {
+                return Err(Error::parse(i, column + 1, "Not a SNAFU digit"));
+            }
+            self.snafus.push(snafu.to_owned());
+            self.decimals.push(Day::snafu_to_decimal(snafu));
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Answer::None);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::str_array_to_string_array;
+    use crate::util::str_array_to_string_array;
 
     #[test]
     fn decimal_to_from_snafu() {
@@ -292,7 +297,7 @@ mod tests {
         );
         let lines = str_array_to_string_array(input.0);
         let mut day = Day::instance();
-        day.parse(&lines);
+        day.parse(&Input::from_lines(&lines)).unwrap();
         assert_eq!(day.part1(), input.1);
     }
 }
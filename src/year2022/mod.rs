@@ -0,0 +1,61 @@
+use crate::solution::{Puzzle, Year};
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day25;
+
+pub static YEAR: Year = Year {
+    year: 2022,
+    puzzles: &PUZZLES,
+};
+
+// Every day in the calendar, in day order
+pub static PUZZLES: [&Puzzle; 25] = [
+    &day01::PUZZLE,
+    &day02::PUZZLE,
+    &day03::PUZZLE,
+    &day04::PUZZLE,
+    &day05::PUZZLE,
+    &day06::PUZZLE,
+    &day07::PUZZLE,
+    &day08::PUZZLE,
+    &day09::PUZZLE,
+    &day10::PUZZLE,
+    &day11::PUZZLE,
+    &day12::PUZZLE,
+    &day13::PUZZLE,
+    &day14::PUZZLE,
+    &day15::PUZZLE,
+    &day16::PUZZLE,
+    &day17::PUZZLE,
+    &day18::PUZZLE,
+    &day19::PUZZLE,
+    &day20::PUZZLE,
+    &day21::PUZZLE,
+    &day22::PUZZLE,
+    &day23::PUZZLE,
+    &day24::PUZZLE,
+    &day25::PUZZLE,
+];
@@ -0,0 +1,90 @@
+use crate::error::{parse_number, Error, Result};
+use crate::input::Input;
+use crate::solution::{Answer, Puzzle, Solution};
+
+/*
+
+--- Day __DAY__: ---
+
+Paste the puzzle description here.
+"aoc2022 examples --year __YEAR__ __DAY__" saves the examples in it as data/__YEAR__/day__NN__/exampleN.txt
+
+For example:
+
+1
+2
+3
+
+*/
+
+static INPUT_FILE: &str = "data/__YEAR__/day__NN__/input.txt";
+
+pub static PUZZLE: Puzzle = Puzzle {
+    year: __YEAR__,
+    day: __DAY__,
+    input_file: INPUT_FILE,
+    instance: |_| Box::new(Day::instance()),
+};
+
+pub struct Day {
+    values: Vec<i64>,
+}
+
+impl Day {
+    pub fn instance() -> Day {
+        Day { values: Vec::new() }
+    }
+
+    pub fn part1(&self) -> i64 {
+        return self.values.iter().sum();
+    }
+
+    pub fn part2(&self) -> i64 {
+        return self.values.iter().max().copied().unwrap_or(0);
+    }
+}
+
+impl Solution for Day {
+    fn parse(&mut self, input: &Input) -> Result<()> {
+        for (i, line) in input.lines().enumerate() {
+            let value = parse_number(i, line, line)?;
+            self.values.push(value);
+        }
+        return Ok(());
+    }
+
+    fn part1(&mut self) -> Result<Answer> {
+        if self.values.is_empty() {
+            return Err(Error::input("No values"));
+        }
+        return Ok(Day::part1(self).into());
+    }
+
+    fn part2(&mut self) -> Result<Answer> {
+        return Ok(Day::part2(self).into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::str_array_to_string_array;
+
+    #[test]
+    fn part1() {
+        let input: Vec<&str> = vec!["1", "2", "3"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part1(), 6);
+    }
+
+    #[test]
+    fn part2() {
+        let input: Vec<&str> = vec!["1", "2", "3"];
+        let lines = str_array_to_string_array(input);
+        let mut day = Day::instance();
+        day.parse(&Input::from_lines(&lines)).unwrap();
+        assert_eq!(day.part2(), 3);
+    }
+}
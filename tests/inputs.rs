@@ -0,0 +1,77 @@
+// Runs every day of every year against each of its inputs in data/ and checks the answers
+// recorded next to them, an input that is not there is skipped so the suite still passes
+// without the data
+// The slow days only run in a release build or on their own with
+//   cargo test --test inputs -- --ignored
+use std::path::Path;
+
+use aoc2022::answers::Verdict;
+use aoc2022::runner::{input_names, load_file, named_input_file, run_part};
+use aoc2022::solution::{Answer, Puzzle, YEARS};
+
+// Days that take more than a few seconds in a debug build (year, day)
+const SLOW_DAYS: [(u32, u32); 1] = [(2022, 16)];
+
+fn is_slow(puzzle: &Puzzle) -> bool {
+    return SLOW_DAYS.contains(&(puzzle.year, puzzle.day));
+}
+
+fn check_day(puzzle: &Puzzle) {
+    let names = input_names(puzzle).unwrap();
+    if !Path::new(puzzle.input_file).exists() {
+        eprintln!(
+            "Skipping {} {} no input {}",
+            puzzle.name(),
+            puzzle.year,
+            puzzle.input_file
+        );
+    }
+    for name in names {
+        let filename = named_input_file(puzzle, &name);
+        let day_input = load_file(&filename).unwrap_or_else(|err| panic!("{err}"));
+        for part in 1..=2 {
+            let run = run_part(puzzle, &day_input.input, part);
+            let answer = run.answer.unwrap_or_else(|err| panic!("{filename}: {err}"));
+            match day_input.answers.check(part, &answer) {
+                Verdict::Correct => {}
+                Verdict::Wrong(expected) => panic!(
+                    "{} {filename} part {part} answered {answer} expected {expected}",
+                    puzzle.name()
+                ),
+                Verdict::Unknown => {
+                    if answer != Answer::None {
+                        eprintln!(
+                            "Skipping {} {filename} part {part} no recorded answer",
+                            puzzle.name()
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn every_day() {
+    for year in YEARS {
+        for &puzzle in year.puzzles {
+            if is_slow(puzzle) && cfg!(debug_assertions) {
+                eprintln!("Skipping {} {} in a debug build", puzzle.name(), year.year);
+                continue;
+            }
+            check_day(puzzle);
+        }
+    }
+}
+
+#[test]
+#[ignore = "slow in a debug build, every_day runs these in a release build"]
+fn slow_days() {
+    for year in YEARS {
+        for &puzzle in year.puzzles {
+            if is_slow(puzzle) {
+                check_day(puzzle);
+            }
+        }
+    }
+}